@@ -2,29 +2,121 @@
 
 pub mod ast {
 
+    use std::num::IntErrorKind;
     use std::slice::Iter;
+    use diagnostics::diagnostics::{Code, Diagnostic, DiagnosticSink, SortedSink, Span};
+    use sema::sema;
+    use lint::lint;
     use lexer::*;
+    use symtab::symtab::{
+        DeclarationError, Definition, Linkage, StaticInit, StorageClass, Symbol, SymbolKind,
+        SymbolTable,
+    };
+    use types::types::{AggregateRef, Qualifiers, Type};
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, PartialEq, Clone)]
     pub enum AstNodeType {
         Program,
         Function,
-        Statement,
-        Expression,
+        FunctionPrototype,
+        StaticVariable,
+        Parameter,
+        Block,
+        Declaration,
+        Return,
+        ExpressionStatement,
+        NullStatement,
+        If,
+        While,
+        DoWhile,
+        For,
+        Break,
+        Continue,
+        // Children are the controlling expression and the body.
+        Switch,
+        // A case label: the children are the lowest and highest value it
+        // matches, equal unless it is a range, then the labeled statement. The
+        // data numbers the labels of a switch in order.
+        Case,
+        Default,
+        // The data is the label name and the child the labeled statement.
+        Label,
+        // The data is the name of the target label.
+        Goto,
+        Constant,
+        // The data is the unique name of the declaration the parser found in
+        // scope for the name, which semantic analysis looks up to type it.
+        // An enumerator becomes a Constant.
+        Variable,
+        // A name with no declaration in scope where it is used, for semantic
+        // analysis to report; the data is the name.
+        Identifier,
+        UnaryOp,
+        BinaryOp,
+        Assign,
+        Call,
+        // A call through a pointer to a function: the first child is the
+        // pointer and the rest are the arguments. Every call is parsed as one
+        // of these, with the callee as written; semantic analysis turns a
+        // call of a function by its name into a Call.
+        IndirectCall,
+        // In the contents of a static variable the data is a byte offset
+        // added to the address.
+        AddressOf,
+        // A conversion of the child to the node's type. The data is
+        // "implicit" for one the language requires rather than a cast written
+        // in the source.
+        Cast,
+        // The parser marks the dereference `p->m` stands for with the data
+        // "arrow". It parses `a[i]` as one with the data "subscript" and both
+        // operands as children, which semantic analysis makes `*(a + i)`.
+        Dereference,
+        // Member access; the data is the member name and the child the struct
+        // or union. `p->m` is represented as `(*p).m`.
+        Member,
+        // A brace enclosed initializer with one child per element given.
+        InitializerList,
+        // The stdarg builtins. The first child is the va_list, decayed to a
+        // pointer to its structure; va_copy has the source as a second child.
+        // VaArg has the type of the argument it fetches.
+        VaStart,
+        VaArg,
+        VaEnd,
+        VaCopy,
+        // Zero bytes in the initial contents of a static object; the data is
+        // the byte count.
+        ZeroFill,
     }
 
     pub struct AstNode {
         pub node_type: AstNodeType,
         pub data: String,
-        pub child: Option<Box<AstNode>>,
+        pub children: Vec<AstNode>,
+        pub linkage: Linkage,
+        // The type of an expression, or of the object or function a
+        // declaration introduces. Statements have type void.
+        pub ty: Type,
+        // Where in the source the node is, for the nodes diagnostics are
+        // reported at: statements where they start, declarations and
+        // functions at their name, operators at the operator and calls at
+        // their closing parenthesis.
+        pub span: Option<Span>,
+        // Whether a syntax error cut short the statement or declaration the
+        // node is part of. The passes after parsing say nothing about such
+        // nodes, as what was meant is unknown.
+        pub recovered: bool,
     }
 
     impl AstNode {
         fn new(nt: AstNodeType) -> AstNode {
             let fun = AstNode {
                 node_type: nt,
-                child: None,
+                children: Vec::new(),
                 data: String::new(),
+                linkage: Linkage::None,
+                ty: Type::Void,
+                span: None,
+                recovered: false,
             };
             return fun;
         }
@@ -32,182 +124,4033 @@ pub mod ast {
         fn new_named(nt: AstNodeType, nm: String) -> AstNode {
             let fun = AstNode {
                 node_type: nt,
-                child: None,
+                children: Vec::new(),
                 data: nm,
+                linkage: Linkage::None,
+                ty: Type::Void,
+                span: None,
+                recovered: false,
             };
             return fun;
         }
 
-        fn add_child(&mut self, ch: Box<AstNode>) {
-            self.child = Some(ch);
+        pub fn new_typed(nt: AstNodeType, nm: String, ty: Type) -> AstNode {
+            let mut fun = AstNode::new_named(nt, nm);
+            fun.ty = ty;
+            return fun;
+        }
+
+        pub fn add_child(&mut self, ch: AstNode) {
+            self.children.push(ch);
+        }
+
+        fn mark_recovered(&mut self) {
+            self.recovered = true;
+            for child in self.children.iter_mut() {
+                child.mark_recovered();
+            }
+        }
+    }
+
+    // A pass that reads the tree. It overrides visit for the nodes it is
+    // interested in, calling walk to carry on into their children; by default
+    // every node is visited, parents before children. The lifetime lets a
+    // pass keep references to the nodes it finds.
+    pub trait Visitor<'n> {
+        fn visit(&mut self, node: &'n AstNode) {
+            walk(self, node);
+        }
+    }
+
+    pub fn walk<'n, V>(visitor: &mut V, node: &'n AstNode)
+    where
+        V: Visitor<'n> + ?Sized,
+    {
+        for child in node.children.iter() {
+            visitor.visit(child);
+        }
+    }
+
+    // A pass that rewrites the tree in place, walked like a Visitor. A node
+    // can be replaced as a whole by assigning to it.
+    pub trait MutVisitor {
+        fn visit_mut(&mut self, node: &mut AstNode) {
+            walk_mut(self, node);
+        }
+    }
+
+    pub fn walk_mut<V>(visitor: &mut V, node: &mut AstNode)
+    where
+        V: MutVisitor + ?Sized,
+    {
+        for child in node.children.iter_mut() {
+            visitor.visit_mut(child);
+        }
+    }
+
+    // Collects the nodes of one type, in source order.
+    struct Finder<'n> {
+        node_type: AstNodeType,
+        found: Vec<&'n AstNode>,
+    }
+
+    impl<'n> Visitor<'n> for Finder<'n> {
+        fn visit(&mut self, node: &'n AstNode) {
+            if node.node_type == self.node_type {
+                self.found.push(node);
+            }
+            walk(self, node);
         }
     }
 
-    pub fn parse<F>(arg: &mut Iter<lexer::TokenInfo>, errorf: &F) -> Box<AstNode>
+    pub fn find_all(node: &AstNode, node_type: AstNodeType) -> Vec<&AstNode> {
+        let mut finder = Finder {
+            node_type,
+            found: Vec::new(),
+        };
+        finder.visit(node);
+        return finder.found;
+    }
+
+    // Parses the tokens of a translation unit and analyzes the result,
+    // reporting errors and warnings to the sink. They are reported in source
+    // order, whichever pass finds them.
+    pub fn parse<S>(arg: &mut Iter<lexer::TokenInfo>, sink: &S) -> Box<AstNode>
     where
-        F: Fn(String),
+        S: DiagnosticSink,
     {
         let root = AstNode::new(AstNodeType::Program);
+        let sorted = SortedSink::new(sink);
 
         let mut boxed_root = Box::new(root);
 
-        parse_ast(&mut boxed_root, arg, errorf);
+        // Stray characters are reported here and otherwise ignored, so that
+        // they do not also derail the parse.
+        let tokens: Vec<lexer::TokenInfo> = arg
+            .as_slice()
+            .iter()
+            .filter(|token| {
+                if token.token_type != lexer::TokenType::Stray {
+                    return true;
+                }
+                sorted.report(stray_error(token));
+                return false;
+            })
+            .cloned()
+            .collect();
+        let mut tokens = tokens.iter();
+
+        let mut state = ParseState {
+            source: tokens.as_slice(),
+            tokens: &mut tokens,
+            sink: &sorted,
+            symbols: SymbolTable::new(),
+            recovering: false,
+            syntax_errors: 0,
+            defining: Vec::new(),
+        };
+        state.parse_ast(&mut boxed_root);
+        sema::analyze(&mut boxed_root, &state.symbols, &sorted);
+        lint::check(&boxed_root, &state.symbols, &sorted);
+        sorted.flush();
 
         return boxed_root;
     }
 
-    fn parse_ast<F>(node: &mut Box<AstNode>, tokens: &mut Iter<lexer::TokenInfo>, errorf: &F)
-    where
-        F: Fn(String),
-    {
-        match node.node_type {
-            AstNodeType::Program => {
-                let mut boxed_node = Box::new(AstNode::new((AstNodeType::Function)));
-                parse_ast(&mut boxed_node, tokens, errorf);
+    fn stray_error(token: &lexer::TokenInfo) -> Diagnostic {
+        let message = if token.data == "\u{fffd}" {
+            String::from("Stray byte that is not valid UTF-8 in program")
+        } else {
+            format!("Stray '{}' in program", token.data.escape_default())
+        };
+        return Diagnostic::error(Code::Syntax, message, Some(token.span()));
+    }
+
+    // Why an expression has no integer constant value.
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum ConstantError {
+        // It reads an object, calls a function or uses an operator that
+        // constant expressions may not.
+        NotConstant,
+        // An integer division or remainder by zero, at the operator.
+        DivisionByZero(Option<Span>),
+    }
+
+    // A signed operation on constants whose result its type cannot
+    // represent, and the value it wraps around to.
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Overflow {
+        pub ty: Type,
+        pub result: i64,
+        pub span: Option<Span>,
+    }
 
-                if boxed_node.data != "main" {
-                    errorf(String::from("main function has wrong name"));
+    // Evaluates an integer constant expression: literals and enumerators
+    // combined with the unary, arithmetic, comparison and logical operators
+    // and conversions between integer types. Arithmetic wraps around in the
+    // type of the operation, and each signed operation that overflows is
+    // added to the overflows. This is the one evaluator for every context
+    // that needs a constant: array sizes, enumerators, case labels, bit-field
+    // widths, _Static_assert and static initializers, and the warnings about
+    // constant operations that overflow or divide by zero elsewhere. There is
+    // no preprocessor, so no #if for it to serve: sources are compiled as
+    // already preprocessed.
+    pub fn evaluate_constant(
+        node: &AstNode,
+        overflows: &mut Vec<Overflow>,
+    ) -> Result<i64, ConstantError> {
+        if !node.ty.is_integer() {
+            return Err(ConstantError::NotConstant);
+        }
+        match node.node_type {
+            AstNodeType::Constant => {
+                return node
+                    .data
+                    .parse::<i64>()
+                    .map_err(|_| ConstantError::NotConstant);
+            }
+            AstNodeType::Cast => {
+                let operand = &node.children[0];
+                if operand.ty.is_floating() {
+                    let value = floating_value(operand).ok_or(ConstantError::NotConstant)?;
+                    if node.ty.size() == 8 && !node.ty.is_signed() {
+                        return Ok(value as u64 as i64);
+                    }
+                    return Ok(node.ty.truncate(value as i64));
                 }
-                //verify that fun has correct name
-                node.add_child(boxed_node);
+                let value = evaluate_constant(operand, overflows)?;
+                return Ok(node.ty.truncate(value));
             }
-            AstNodeType::Function => {
-                //consume intkeyword
-                let token = tokens.next();
-                if token.unwrap().token_type != lexer::TokenType::KeywordInt {
-                    errorf(String::from("Wrong token type: expected Int keyword"));
+            AstNodeType::UnaryOp => {
+                let value = evaluate_constant(&node.children[0], overflows)?;
+                let exact = match node.data.as_str() {
+                    "-" => -node.ty.order_key(value),
+                    "~" => return Ok(node.ty.truncate(!value)),
+                    "!" => return Ok((value == 0) as i64),
+                    _ => return Err(ConstantError::NotConstant),
+                };
+                return Ok(wrap(node, exact, overflows));
+            }
+            AstNodeType::BinaryOp => {
+                let lhs = evaluate_constant(&node.children[0], overflows)?;
+                // The right operand of && and || need not be constant if it is
+                // never evaluated.
+                match node.data.as_str() {
+                    "&&" if lhs == 0 => return Ok(0),
+                    "||" if lhs != 0 => return Ok(1),
+                    _ => {}
                 }
+                let rhs = evaluate_constant(&node.children[1], overflows)?;
+                // Both operands have been converted to a common type, whose
+                // signedness decides how they divide and compare.
+                let operands = &node.children[0].ty;
+                let (a, b) = (operands.order_key(lhs), operands.order_key(rhs));
+                let exact = match node.data.as_str() {
+                    "+" | "-" | "*" => return Ok(arithmetic(node, lhs, rhs, overflows)),
+                    "/" | "%" if b == 0 => return Err(ConstantError::DivisionByZero(node.span)),
+                    "/" => a / b,
+                    // The remainder is always representable, but MIN % -1
+                    // overflows as the division it comes from does.
+                    "%" => {
+                        let quotient = a / b;
+                        if node.ty.is_signed()
+                            && i128::from(node.ty.truncate(quotient as i64)) != quotient
+                        {
+                            overflows.push(Overflow {
+                                ty: node.ty.clone(),
+                                result: 0,
+                                span: node.span,
+                            });
+                        }
+                        a % b
+                    }
+                    "==" => (a == b) as i128,
+                    "!=" => (a != b) as i128,
+                    "<" => (a < b) as i128,
+                    "<=" => (a <= b) as i128,
+                    ">" => (a > b) as i128,
+                    ">=" => (a >= b) as i128,
+                    "&&" | "||" => (b != 0) as i128,
+                    _ => return Err(ConstantError::NotConstant),
+                };
+                return Ok(wrap(node, exact, overflows));
+            }
+            _ => return Err(ConstantError::NotConstant),
+        }
+    }
 
-                //consume name
-                let token_wrapped = tokens.next();
+    // Adds, subtracts or multiplies two constants at the width of the
+    // operation. Unsigned arithmetic wraps around; signed arithmetic that
+    // overflows is noted, even where 64 bits cannot hold the exact result.
+    fn arithmetic(node: &AstNode, lhs: i64, rhs: i64, overflows: &mut Vec<Overflow>) -> i64 {
+        let (wrapped, checked) = match node.data.as_str() {
+            "+" => (lhs.wrapping_add(rhs), lhs.checked_add(rhs)),
+            "-" => (lhs.wrapping_sub(rhs), lhs.checked_sub(rhs)),
+            _ => (lhs.wrapping_mul(rhs), lhs.checked_mul(rhs)),
+        };
+        if !node.ty.is_signed() {
+            return node.ty.truncate(wrapped);
+        }
+        match checked {
+            Some(exact) => return wrap(node, i128::from(exact), overflows),
+            None => {
+                let value = node.ty.truncate(wrapped);
+                overflows.push(Overflow {
+                    ty: node.ty.clone(),
+                    result: value,
+                    span: node.span,
+                });
+                return value;
+            }
+        }
+    }
 
-                //TODO error check
-                let token = token_wrapped.unwrap();
-                if token.token_type != lexer::TokenType::Identifier {
-                    errorf(String::from("Wrong token type: expected identifier"));
-                }
-                //println!("Found function name:{}", token.data);
-                node.data = token.data.clone();
+    // The exact result of an operation wrapped around to the operation's
+    // type, noting an overflow if the type is signed.
+    fn wrap(node: &AstNode, exact: i128, overflows: &mut Vec<Overflow>) -> i64 {
+        let value = node.ty.truncate(exact as i64);
+        if node.ty.is_signed() && i128::from(value) != exact {
+            overflows.push(Overflow {
+                ty: node.ty.clone(),
+                result: value,
+                span: node.span,
+            });
+        }
+        return value;
+    }
 
-                let token_wrapped = tokens.next();
-                //TODO error check
-                let token = token_wrapped.unwrap();
-                if token.token_type != lexer::TokenType::OpenParen {
-                    errorf(String::from(format!(
-                        "Wrong token type {}: expected open paren",
-                        token.token_type,
-                    )));
-                }
+    pub fn overflow_warning(overflow: &Overflow, fallback: Option<Span>) -> Diagnostic {
+        return Diagnostic::warning(
+            Code::Overflow,
+            format!(
+                "Integer overflow in expression of type '{}' results in '{}'",
+                overflow.ty, overflow.result
+            ),
+            overflow.span.or(fallback),
+        );
+    }
 
-                let token_wrapped = tokens.next();
-                //TODO error check
-                let token = token_wrapped.unwrap();
-                if token.token_type != lexer::TokenType::CloseParen {
-                    errorf(String::from("Wrong token type: expected close paren"));
-                }
+    // The value of an integer constant expression, or None if it has none.
+    pub fn constant_value(node: &AstNode) -> Option<i64> {
+        return evaluate_constant(node, &mut Vec::new()).ok();
+    }
 
-                let token_wrapped = tokens.next();
-                //TODO error check
-                let token = token_wrapped.unwrap();
-                if token.token_type != lexer::TokenType::OpenBrace {
-                    errorf(String::from("Wrong token type: expected open brace"));
+    // Evaluates an arithmetic constant expression of floating type, built from
+    // floating literals, converted integer constants and the arithmetic
+    // operators. Each result is rounded to the precision of its type.
+    pub fn floating_value(node: &AstNode) -> Option<f64> {
+        if !node.ty.is_floating() {
+            return None;
+        }
+        let value = match node.node_type {
+            AstNodeType::Constant => node.data.parse::<f64>().ok()?,
+            AstNodeType::Cast => {
+                let operand = &node.children[0];
+                if operand.ty.is_floating() {
+                    floating_value(operand)?
+                } else if operand.ty.size() == 8 && !operand.ty.is_signed() {
+                    constant_value(operand)? as u64 as f64
+                } else {
+                    constant_value(operand)? as f64
+                }
+            }
+            AstNodeType::UnaryOp if node.data == "-" => -floating_value(&node.children[0])?,
+            AstNodeType::BinaryOp => {
+                let lhs = floating_value(&node.children[0])?;
+                let rhs = floating_value(&node.children[1])?;
+                match node.data.as_str() {
+                    "+" => lhs + rhs,
+                    "-" => lhs - rhs,
+                    "*" => lhs * rhs,
+                    "/" => lhs / rhs,
+                    _ => return None,
                 }
+            }
+            _ => return None,
+        };
+        return Some(round_to(&node.ty, value));
+    }
 
-                let mut boxed_node = Box::new(AstNode::new((AstNodeType::Statement)));
-                parse_ast(&mut boxed_node, tokens, errorf);
+    fn round_to(ty: &Type, value: f64) -> f64 {
+        if *ty == Type::Float {
+            return f64::from(value as f32);
+        }
+        return value;
+    }
 
-                let token_wrapped = tokens.next();
-                //TODO error check
-                let token = token_wrapped.unwrap();
-                if token.token_type != lexer::TokenType::CloseBrace {
-                    errorf(String::from("Wrong token type: expected close brace"));
-                }
+    // Parses a floating literal, which is a double unless it has an f suffix.
+    fn floating_literal(text: &str) -> Option<(f64, Type)> {
+        let lower = text.to_lowercase();
+        match lower.strip_suffix('f') {
+            Some(digits) => {
+                let value = digits.parse::<f64>().ok()?;
+                return Some((round_to(&Type::Float, value), Type::Float));
+            }
+            None => return Some((lower.parse::<f64>().ok()?, Type::Double)),
+        }
+    }
+
+    // Parses an integer literal: decimal, octal with a leading 0 or
+    // hexadecimal with 0x, followed by an optional u and l or ll suffix. Its
+    // type is the first one its suffix allows that can represent the value,
+    // where decimal literals without u only take signed types. A literal too
+    // large for unsigned long long is out of range rather than malformed.
+    fn integer_literal(text: &str) -> Result<(i64, Type), (Code, String)> {
+        let invalid = (
+            Code::InvalidLiteral,
+            format!("Invalid integer literal '{}'", text),
+        );
+        let lower = text.to_lowercase();
+        let digits_end = lower.trim_end_matches(['u', 'l']).len();
+        let (digits, suffix) = lower.split_at(digits_end);
+        let (radix, digits) = match digits.strip_prefix("0x") {
+            Some(hex) => (16, hex),
+            None if digits.len() > 1 && digits.starts_with('0') => (8, &digits[1..]),
+            None => (10, digits),
+        };
+
+        let unsigned = suffix.contains('u');
+        let longs = suffix.matches('l').count();
+        let valid_suffix = matches!(suffix, "" | "u" | "l" | "ul" | "lu" | "ll" | "ull" | "llu");
+        if !valid_suffix {
+            return Err(invalid);
+        }
+        let value = match u64::from_str_radix(digits, radix) {
+            Ok(value) => value,
+            Err(ref error) if *error.kind() == IntErrorKind::PosOverflow => {
+                return Err((
+                    Code::OutOfRange,
+                    format!(
+                        "Integer literal '{}' is too large for any integer type",
+                        text
+                    ),
+                ));
+            }
+            Err(_) => return Err(invalid),
+        };
+
+        let candidates = [
+            Type::Int,
+            Type::UnsignedInt,
+            Type::Long,
+            Type::UnsignedLong,
+            Type::LongLong,
+            Type::UnsignedLongLong,
+        ];
+        let ty = candidates
+            .iter()
+            .skip(2 * longs)
+            .filter(|ty| !(unsigned && ty.is_signed()))
+            .filter(|ty| !(radix == 10 && !unsigned && !ty.is_signed()))
+            .find(|ty| {
+                ty.truncate(value as i64) as u64 == value && (value as i64 >= 0 || !ty.is_signed())
+            })
+            .cloned()
+            .unwrap_or(Type::UnsignedLongLong);
+        return Ok((value as i64, ty));
+    }
 
-                node.add_child(boxed_node);
+    // Decodes the bytes of a string or character literal, given with its
+    // quotes, resolving the escape sequences.
+    fn literal_bytes(text: &str) -> Result<Vec<u8>, String> {
+        let quote = text.chars().next().unwrap_or('"');
+        let mut bytes = Vec::new();
+        let mut chars = text.chars().skip(1).peekable();
+        let unterminated = format!("Missing terminating {} character", quote);
+        loop {
+            let c = match chars.next() {
+                Some(c) => c,
+                None => return Err(unterminated),
+            };
+            if c == quote {
+                return Ok(bytes);
             }
-            AstNodeType::Statement => {
-                //consume retkeyword
-                //TODO error check
-                let token = tokens.next();
-                if token.unwrap().token_type != lexer::TokenType::KeywordRet {
-                    errorf(String::from("Wrong token type: expected return keyword"));
+            if c != '\\' {
+                let mut buffer = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                continue;
+            }
+            let escape = match chars.next() {
+                Some(escape) => escape,
+                None => return Err(unterminated),
+            };
+            let value = match escape {
+                'n' => 10,
+                't' => 9,
+                'r' => 13,
+                'a' => 7,
+                'b' => 8,
+                'f' => 12,
+                'v' => 11,
+                '\\' | '\'' | '"' | '?' => escape as u32,
+                '0'..='7' => {
+                    // Up to three octal digits.
+                    let mut value = escape.to_digit(8).unwrap();
+                    for _ in 0..2 {
+                        match chars.peek().and_then(|c| c.to_digit(8)) {
+                            Some(digit) => value = value * 8 + digit,
+                            None => break,
+                        }
+                        chars.next();
+                    }
+                    if value > 255 {
+                        return Err(String::from("Octal escape sequence out of range"));
+                    }
+                    value
+                }
+                'x' => {
+                    let mut value: u32 = 0;
+                    let mut digits = 0;
+                    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) {
+                        value = value.saturating_mul(16).saturating_add(digit);
+                        digits += 1;
+                        chars.next();
+                    }
+                    if digits == 0 {
+                        return Err(String::from("\\x used with no following hex digits"));
+                    }
+                    if value > 255 {
+                        return Err(String::from("Hex escape sequence out of range"));
+                    }
+                    value
                 }
+                _ => return Err(format!("Unknown escape sequence '\\{}'", escape)),
+            };
+            bytes.push(value as u8);
+        }
+    }
+
+    // Wraps the expression in an implicit conversion to the type, unless it
+    // already has that type.
+    pub fn convert(node: AstNode, ty: &Type) -> AstNode {
+        if node.ty == *ty {
+            return node;
+        }
+        let mut cast = AstNode::new_typed(AstNodeType::Cast, String::from("implicit"), ty.clone());
+        cast.add_child(node);
+        return cast;
+    }
+
+    // An array used as a value is converted to a pointer to its first element,
+    // and a function to a pointer to the function. Only the operand of `&`
+    // or sizeof keeps its array or function type.
+    pub fn decay(node: AstNode) -> AstNode {
+        let pointee = match node.ty {
+            Type::Array(ref element, _) => (**element).clone(),
+            Type::Function(..) => node.ty.clone(),
+            _ => return node,
+        };
+        let mut address = AstNode::new_typed(
+            AstNodeType::AddressOf,
+            String::new(),
+            Type::pointer_to(pointee),
+        );
+        address.add_child(node);
+        return address;
+    }
+
+    // An initializer list giving a char array the bytes of a string literal,
+    // followed by as much of the terminating null as fits.
+    fn string_initializer(bytes: &[u8], ty: &Type) -> AstNode {
+        let element = ty.element().cloned().unwrap_or(Type::Char);
+        let length = match *ty {
+            Type::Array(_, length) => length,
+            _ => 0,
+        };
+        let mut list = AstNode::new_typed(AstNodeType::InitializerList, String::new(), ty.clone());
+        for byte in bytes.iter().chain(::std::iter::once(&0)).take(length) {
+            list.add_child(AstNode::new_typed(
+                AstNodeType::Constant,
+                element.truncate(i64::from(*byte)).to_string(),
+                element.clone(),
+            ));
+        }
+        return list;
+    }
 
-                let mut boxed_node = Box::new(AstNode::new((AstNodeType::Expression)));
-                parse_ast(&mut boxed_node, tokens, errorf);
-                node.add_child(boxed_node);
+    fn push_zero(init: &mut Vec<StaticInit>, bytes: i32) {
+        if bytes == 0 {
+            return;
+        }
+        if let Some(&mut StaticInit::Zero(ref mut run)) = init.last_mut() {
+            *run += bytes;
+            return;
+        }
+        init.push(StaticInit::Zero(bytes));
+    }
 
-                //consume semicolon
-                let token_wrapped = tokens.next();
-                let tk2 = token_wrapped.unwrap();
-                if tk2.token_type != lexer::TokenType::SemiColon {
-                    errorf(String::from("Wrong token type: expected semi-colon"));
+    // The type of the element at the index of an aggregate's initializer
+    // list, or None if the aggregate has no room for it. Only the first
+    // member of a union can be initialized.
+    pub fn element_type(ty: &Type, index: usize) -> Option<Type> {
+        match *ty.unqualified() {
+            Type::Array(ref element, length) => {
+                if length == 0 || index < length {
+                    return Some((**element).clone());
                 }
+                return None;
             }
-            AstNodeType::Expression => {
-                //consume value
-                let token_wrapped = tokens.next();
-                //TODO error check
-                let token = token_wrapped.unwrap();
-                if token.token_type != lexer::TokenType::Intliteral {
-                    errorf(String::from("Wrong token type: expceted int literal"));
+            Type::Struct(ref aggregate) => {
+                if aggregate.borrow().is_union && index > 0 {
+                    return None;
                 }
+                return aggregate.members().get(index).map(|m| m.0.clone());
+            }
+            _ => return None,
+        }
+    }
+
+    // Whether both types are pointers to the same type, ignoring the
+    // qualifiers of that type.
+    pub fn same_pointee(a: &Type, b: &Type) -> bool {
+        match (a.pointee(), b.pointee()) {
+            (Some(a), Some(b)) => return a.unqualified() == b.unqualified(),
+            _ => return false,
+        }
+    }
 
-                //println!("Found number:{}", token.data);
-                node.data = token.data.clone();
+    // Whether one type is a pointer to void and the other a pointer to an
+    // object, which convert to each other implicitly. Pointers to functions
+    // do not convert to or from `void *`.
+    pub fn void_pointer_pair(a: &Type, b: &Type) -> bool {
+        let is_void = |pointee: &Type| *pointee.unqualified() == Type::Void;
+        match (a.pointee(), b.pointee()) {
+            (Some(a), Some(b)) => {
+                return (is_void(a) && !b.is_function()) || (is_void(b) && !a.is_function());
             }
+            _ => return false,
         }
     }
 
-    mod test {
-        #[allow(unused_imports)]
-        use lexer::*;
-        use std::fs;
+    // Whether a struct has a const member, possibly in a nested struct, so
+    // that it cannot be assigned as a whole.
+    pub fn has_const_member(ty: &Type) -> bool {
+        match *ty.unqualified() {
+            Type::Struct(ref aggregate) => {
+                return aggregate
+                    .members()
+                    .iter()
+                    .any(|(member, _)| member.qualifiers().is_const || has_const_member(member));
+            }
+            Type::Array(ref element, _) => return has_const_member(element),
+            _ => return false,
+        }
+    }
 
-        #[test]
-        //#[ignore]
-        fn test_succeeding() {
-            let paths = fs::read_dir("test\\valid").unwrap();
-            for path in paths {
-                let thepath = path.unwrap();
-                println!("Testing parser against name: {}", thepath.path().display());
-                let token_list = lexer::lex(thepath.path());
+    pub fn is_lvalue(node: &AstNode) -> bool {
+        return node.node_type == AstNodeType::Variable
+            || node.node_type == AstNodeType::Dereference
+            || node.node_type == AstNodeType::Member;
+    }
 
-                let mut iter = token_list.iter();
-                let result = super::parse(&mut iter, &|x: String| {
-                    panic!(x);
-                });
+    // An integer constant expression with the value 0, possibly cast to
+    // `void *`.
+    pub fn is_null_pointer_constant(node: &AstNode) -> bool {
+        if node.node_type == AstNodeType::Cast && node.ty == Type::pointer_to(Type::Void) {
+            return is_null_pointer_constant(&node.children[0]);
+        }
+        return node.ty.is_integer() && constant_value(node) == Some(0);
+    }
 
-                println!(
-                    "Program NodeType:{:?} Data:{}",
-                    &result.node_type, &result.data
-                );
-                assert_eq!(&result.node_type, &super::AstNodeType::Program);
+    // The qualifiers of the object an lvalue designates, which its expression
+    // type leaves out: those it was declared with, those of the pointer's
+    // target for a dereference, and for a member those of both the member and
+    // the struct.
+    pub fn lvalue_qualifiers(symbols: &SymbolTable, node: &AstNode) -> Qualifiers {
+        match node.node_type {
+            AstNodeType::Variable => {
+                return symbols
+                    .lookup_unique(&node.data)
+                    .map(|symbol| symbol.ty.qualifiers())
+                    .unwrap_or_default();
+            }
+            AstNodeType::Dereference => {
+                return node.children[0]
+                    .ty
+                    .pointee()
+                    .map(|pointee| pointee.qualifiers())
+                    .unwrap_or_default();
+            }
+            AstNodeType::Member => {
+                let object = &node.children[0];
+                let member = match object.ty {
+                    Type::Struct(ref aggregate) => aggregate
+                        .member(&node.data)
+                        .map(|(ty, _)| ty.qualifiers())
+                        .unwrap_or_default(),
+                    _ => Qualifiers::default(),
+                };
+                return member.union(lvalue_qualifiers(symbols, object));
+            }
+            _ => return Qualifiers::default(),
+        }
+    }
+
+    // The parameters of a function declared with the declarator. When the
+    // function type comes from a typedef, its parameters have no names.
+    fn function_parameters(declarator: &Declarator, ty: &Type) -> Vec<Parameter> {
+        if let Some(Derivation::Function(parameters, _)) = declarator.derivations.last() {
+            return parameters.clone();
+        }
+        match *ty {
+            Type::Function(_, ref types, _) => {
+                return types.iter().map(|t| (None, t.clone(), None)).collect()
+            }
+            _ => return Vec::new(),
+        }
+    }
+
+    // One step in building the type of a declarator from its base type. A
+    // function keeps the names of its parameters for its definition, and
+    // whether it is variadic.
+    // A parameter of a function declarator: its name if it has one, its
+    // type and where it is declared.
+    type Parameter = (Option<String>, Type, Option<Span>);
+
+    #[derive(Clone)]
+    enum Derivation {
+        Pointer(Qualifiers),
+        Array(usize),
+        Function(Vec<Parameter>, bool),
+    }
+
+    // The name a declarator declares, if any, where it is, and the
+    // derivations that build its type from the base type, in the order they
+    // apply.
+    struct Declarator {
+        name: Option<String>,
+        span: Option<Span>,
+        derivations: Vec<Derivation>,
+    }
+
+    struct ParseState<'a, 'b: 'a, S: 'a>
+    where
+        S: DiagnosticSink,
+    {
+        // All of the tokens, so that diagnostics can find the ones already
+        // consumed.
+        source: &'b [lexer::TokenInfo],
+        tokens: &'a mut Iter<'b, lexer::TokenInfo>,
+        sink: &'a S,
+        symbols: SymbolTable,
+        // Set after a syntax error until the parser has skipped to the end of
+        // the statement or declaration. Errors are not reported meanwhile, as
+        // they would mostly be consequences of the first one.
+        recovering: bool,
+        // The number of syntax errors so far, reported or not, which tells
+        // whether an item was cut short.
+        syntax_errors: usize,
+        // The structs and unions whose members are being parsed, which may
+        // not be defined again inside their own definition.
+        defining: Vec<Type>,
+    }
+
+    // Binary operators from lowest to highest precedence; all of them are left
+    // associative.
+    static BINARY_PRECEDENCE: &[&[(lexer::TokenType, &str)]] = &[
+        &[(lexer::TokenType::LogicalOr, "||")],
+        &[(lexer::TokenType::LogicalAnd, "&&")],
+        &[
+            (lexer::TokenType::Equal, "=="),
+            (lexer::TokenType::NotEqual, "!="),
+        ],
+        &[
+            (lexer::TokenType::Less, "<"),
+            (lexer::TokenType::LessEqual, "<="),
+            (lexer::TokenType::Greater, ">"),
+            (lexer::TokenType::GreaterEqual, ">="),
+        ],
+        &[
+            (lexer::TokenType::Plus, "+"),
+            (lexer::TokenType::Minus, "-"),
+        ],
+        &[
+            (lexer::TokenType::Star, "*"),
+            (lexer::TokenType::Slash, "/"),
+            (lexer::TokenType::Percent, "%"),
+        ],
+    ];
+
+    // The typing rules in sema report through the parser, so that nothing
+    // is reported while it recovers from a syntax error.
+    impl<'a, 'b, S> DiagnosticSink for ParseState<'a, 'b, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn report(&self, diagnostic: Diagnostic) {
+            if !self.recovering {
+                self.sink.report(diagnostic);
+            }
+        }
+    }
+
+    impl<'a, 'b, S> ParseState<'a, 'b, S>
+    where
+        S: DiagnosticSink,
+    {
+        // Reports an error at the last token consumed, which is where most
+        // errors are noticed.
+        fn error(&self, code: Code, message: String) {
+            self.report(Diagnostic::error(code, message, self.last_span()));
+        }
+
+        // Reports a declaration the symbol table rejected at the name it
+        // declares, pointing back at the earlier declaration it conflicts with.
+        fn declaration_error(&self, error: DeclarationError, span: Option<Span>) {
+            let (code, message, previous) = error;
+            let span = span.or(self.last_span());
+            let mut diagnostic = Diagnostic::error(code, message, span);
+            if let Some(span) = previous {
+                diagnostic =
+                    diagnostic.with_label(span, String::from("previous declaration is here"));
+            }
+            self.report(diagnostic);
+        }
+
+        // Evaluates an expression that must be an integer constant, warning
+        // about each operation in it that overflows and reporting a division
+        // by zero at the operator. An expression that is not constant at all
+        // is left to the caller to report in the terms of its context.
+        fn evaluate_required(&self, expression: &AstNode) -> Result<i64, ConstantError> {
+            let mut overflows = Vec::new();
+            let result = evaluate_constant(expression, &mut overflows);
+            for overflow in overflows {
+                self.report(overflow_warning(&overflow, self.last_span()));
+            }
+            if let Err(ConstantError::DivisionByZero(span)) = result {
+                self.report(Diagnostic::error(
+                    Code::NotConstant,
+                    String::from("Division by zero in constant expression"),
+                    span.or(self.last_span()),
+                ));
+            }
+            return result;
+        }
+
+        // Reports the next token as not fitting the grammar and starts
+        // recovering.
+        fn syntax_error(&mut self, message: String) {
+            let span = self.next_span();
+            self.syntax_error_at(message, span);
+        }
+
+        // Like syntax_error, for a token already consumed.
+        fn syntax_error_at(&mut self, message: String, span: Option<Span>) {
+            self.report(Diagnostic::error(Code::Syntax, message, span));
+            self.recovering = true;
+            self.syntax_errors += 1;
+        }
+
+        fn last_span(&self) -> Option<Span> {
+            let consumed = self.source.len() - self.remaining();
+            if consumed == 0 {
+                return self.next_span();
+            }
+            return Some(self.source[consumed - 1].span());
+        }
+
+        // The span of the next token, or the column just past the last token
+        // at the end of input.
+        fn next_span(&self) -> Option<Span> {
+            if let Some(token) = self.tokens.as_slice().first() {
+                return Some(token.span());
+            }
+            let last = self.source.last()?.span();
+            return Some(Span {
+                line: last.line,
+                start: last.end,
+                end: last.end + 1,
+            });
+        }
+
+        fn peek_type(&self) -> Option<lexer::TokenType> {
+            return self.peek_type_at(0);
+        }
+
+        fn peek_type_at(&self, index: usize) -> Option<lexer::TokenType> {
+            return self
+                .tokens
+                .as_slice()
+                .get(index)
+                .map(|t| t.token_type.clone());
+        }
+
+        fn next_is(&self, token_type: lexer::TokenType) -> bool {
+            return self.peek_type() == Some(token_type);
+        }
+
+        // Consumes the next token if it is of the expected type. Otherwise
+        // reports a syntax error and leaves the token for the parser to
+        // resynchronize at. Reaching an expected semi-colon ends recovery.
+        fn expect(
+            &mut self,
+            token_type: lexer::TokenType,
+            what: &str,
+        ) -> Option<&'b lexer::TokenInfo> {
+            return self.expect_closing(token_type, what, None);
+        }
+
+        // Like expect, for a token that closes the bracket at `open`, which a
+        // missing one is traced back to.
+        fn expect_closing(
+            &mut self,
+            token_type: lexer::TokenType,
+            what: &str,
+            open: Option<&'b lexer::TokenInfo>,
+        ) -> Option<&'b lexer::TokenInfo> {
+            let message = match self.tokens.as_slice().first() {
+                Some(token) if token.token_type == token_type => {
+                    if token_type == lexer::TokenType::SemiColon {
+                        self.recovering = false;
+                    }
+                    return self.tokens.next();
+                }
+                Some(token) => format!("Unexpected {}: expected {}", token, what),
+                None => format!("Unexpected end of input: expected {}", what),
+            };
+            // A token missing at the end of a line is reported there rather
+            // than at whatever starts the next line.
+            let span = match (self.last_span(), self.next_span()) {
+                (Some(last), Some(next)) if last.line < next.line => Some(Span {
+                    line: last.line,
+                    start: last.end,
+                    end: last.end + 1,
+                }),
+                (_, next) => next,
+            };
+            let mut diagnostic = Diagnostic::error(Code::Syntax, message, span);
+            if let Some(open) = open {
+                diagnostic = diagnostic
+                    .with_label(open.span(), format!("to match this {}", open.token_type));
+            }
+            self.report(diagnostic);
+            self.recovering = true;
+            self.syntax_errors += 1;
+            return None;
+        }
 
-                match result.child {
-                    Some(ref x) => {
-                        //unwrap() is doing Some(x) we need a reference via Some(ref x)
-                        assert_eq!(x.node_type, super::AstNodeType::Function);
-                        assert_eq!(x.data, String::from("main"));
-
-                        match x.child {
-                            Some(ref y) => {
-                                assert_eq!(y.node_type, super::AstNodeType::Statement);
-
-                                match y.child {
-                                    Some(ref z) => {
-                                        assert_eq!(z.node_type, super::AstNodeType::Expression);
-                                    }
-                                    None => unreachable!(),
+        // Whether the next token is the first on its line.
+        fn next_starts_line(&self) -> bool {
+            let consumed = self.source.len() - self.remaining();
+            return match (consumed, self.tokens.as_slice().first()) {
+                (0, _) | (_, None) => true,
+                (_, Some(token)) => token.span().line > self.source[consumed - 1].span().line,
+            };
+        }
+
+        // The number of tokens left, which tells whether the parser has made
+        // progress.
+        fn remaining(&self) -> usize {
+            return self.tokens.as_slice().len();
+        }
+
+        // Skips tokens after a syntax error in the item that started with
+        // `remaining` tokens left, up to and including the next semi-colon,
+        // or up to the closing brace of the enclosing block. Blocks opened on
+        // the way are skipped as a whole, so a broken function definition is
+        // passed over entirely. Braces the item opened before the error, such
+        // as that of an initializer, are closed first and the item then runs
+        // on to its semi-colon. A declaration beginning a line outside any
+        // such braces is taken to start the next item, since the error is
+        // most likely a missing semi-colon before it.
+        fn synchronize(&mut self, remaining: usize) {
+            let consumed =
+                &self.source[self.source.len() - remaining..][..remaining - self.remaining()];
+            let mut depth = 0;
+            for token in consumed {
+                match token.token_type {
+                    lexer::TokenType::OpenBrace => depth += 1,
+                    lexer::TokenType::CloseBrace if depth > 0 => depth -= 1,
+                    _ => {}
+                }
+            }
+            let mut closing_item = depth > 0;
+            while let Some(token_type) = self.peek_type() {
+                if depth == 0 && self.next_starts_line() && self.next_is_declaration() {
+                    break;
+                }
+                match token_type {
+                    lexer::TokenType::SemiColon if depth == 0 => {
+                        self.tokens.next();
+                        break;
+                    }
+                    lexer::TokenType::CloseBrace if depth == 0 => break,
+                    lexer::TokenType::OpenBrace => depth += 1,
+                    lexer::TokenType::CloseBrace => {
+                        depth -= 1;
+                        if depth == 0 && !closing_item {
+                            self.tokens.next();
+                            break;
+                        }
+                        if depth == 0 {
+                            closing_item = false;
+                        }
+                    }
+                    _ => {}
+                }
+                self.tokens.next();
+            }
+            self.recovering = false;
+        }
+
+        // Resynchronizes after a syntax error in an item of a list that runs
+        // to a closing brace or the end of input. An item that could not even
+        // begin at the next token skips just that token, so the list always
+        // comes to an end.
+        fn finish_item(&mut self, remaining: usize) {
+            if self.remaining() == remaining {
+                if let Some(token) = self.tokens.next() {
+                    if !self.recovering {
+                        self.error(Code::Syntax, format!("Unexpected {}", token));
+                    }
+                }
+                self.recovering = false;
+            } else if self.recovering {
+                self.synchronize(remaining);
+            }
+        }
+
+        fn parse_ast(&mut self, node: &mut AstNode) {
+            match node.node_type {
+                AstNodeType::Program => {
+                    // A function with an error in its body keeps its other
+                    // statements, which are marked on their own.
+                    while self.peek_type().is_some() {
+                        let remaining = self.remaining();
+                        let (errors, items) = (self.syntax_errors, node.children.len());
+                        self.parse_external_declaration(node);
+                        if self.syntax_errors != errors {
+                            for item in node.children[items..].iter_mut() {
+                                if item.node_type != AstNodeType::Function {
+                                    item.mark_recovered();
+                                }
+                            }
+                        }
+                        self.finish_item(remaining);
+                    }
+
+                    // Every declaration of a file scope object has been merged by
+                    // now, so each one that is defined here is emitted exactly once.
+                    for object in self.symbols.statics() {
+                        let mut variable = AstNode::new_typed(
+                            AstNodeType::StaticVariable,
+                            object.name.clone(),
+                            object.ty.clone(),
+                        );
+                        variable.linkage = object.linkage;
+                        match object.definition {
+                            Definition::None => continue,
+                            Definition::Tentative => {}
+                            Definition::Initialized(ref init) => {
+                                for piece in init.iter() {
+                                    variable.add_child(match *piece {
+                                        StaticInit::Value(ref ty, value) => AstNode::new_typed(
+                                            AstNodeType::Constant,
+                                            value.to_string(),
+                                            ty.clone(),
+                                        ),
+                                        StaticInit::Zero(bytes) => AstNode::new_named(
+                                            AstNodeType::ZeroFill,
+                                            bytes.to_string(),
+                                        ),
+                                        StaticInit::Address(ref ty, ref name, offset) => {
+                                            let pointer = Type::pointer_to(ty.clone());
+                                            let mut address = AstNode::new_typed(
+                                                AstNodeType::AddressOf,
+                                                offset.to_string(),
+                                                pointer,
+                                            );
+                                            address.add_child(AstNode::new_typed(
+                                                AstNodeType::Variable,
+                                                name.clone(),
+                                                ty.clone(),
+                                            ));
+                                            address
+                                        }
+                                    });
                                 }
                             }
-                            None => unreachable!(),
                         }
+                        node.add_child(variable);
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        // Parses a function definition, adding it to the program, or a file
+        // scope declaration of a comma separated list of declarators.
+        fn parse_external_declaration(&mut self, program: &mut AstNode) {
+            if self.next_is(lexer::TokenType::KeywordStaticAssert) {
+                self.parse_static_assert();
+                return;
+            }
+            let (storage, base, tagged) = self.parse_specifiers();
+            if tagged && self.next_is(lexer::TokenType::SemiColon) {
+                self.tokens.next();
+                return;
+            }
+            if storage == StorageClass::Typedef {
+                self.parse_typedef(base);
+                return;
+            }
+            let mut first = true;
+            loop {
+                let declarator = self.parse_declarator();
+                let name = self.declarator_name(&declarator);
+                let ty = self.derive_type(base.clone(), &declarator.derivations);
+
+                if ty.is_function() {
+                    // A function body ends the declaration, which must then
+                    // have no other declarators.
+                    if !first && self.next_is(lexer::TokenType::OpenBrace) {
+                        self.error(
+                            Code::Syntax,
+                            format!("Function '{}' defined in a list of declarators", name),
+                        );
+                    }
+                    let parameters = function_parameters(&declarator, &ty);
+                    let mut function_node = AstNode::new_named(AstNodeType::Function, name);
+                    function_node.span = declarator.span;
+                    self.parse_function(&mut function_node, ty, parameters, storage);
+                    let defined = function_node.node_type == AstNodeType::Function;
+                    program.add_child(function_node);
+                    if defined {
+                        return;
                     }
-                    None => unreachable!(),
+                } else {
+                    self.parse_global_variable(&name, ty, storage, declarator.span);
+                }
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                self.tokens.next();
+                first = false;
+            }
+            self.expect(lexer::TokenType::SemiColon, "semi-colon");
+        }
+
+        // Parses any type qualifiers following the star of a pointer
+        // declarator.
+        fn parse_qualifiers(&mut self) -> Qualifiers {
+            let mut qualifiers = Qualifiers::default();
+            loop {
+                match self.peek_type() {
+                    Some(lexer::TokenType::KeywordConst) => qualifiers.is_const = true,
+                    Some(lexer::TokenType::KeywordVolatile) => qualifiers.is_volatile = true,
+                    Some(lexer::TokenType::KeywordRestrict) => qualifiers.is_restrict = true,
+                    _ => return qualifiers,
                 }
+                self.tokens.next();
             }
         }
 
+        // Parses the declaration specifiers: one type specifier, at most one
+        // storage class and any type qualifiers, in any order. Also returns whether a struct, union or
+        // enum specifier was used, since a declaration may then leave out the
+        // declarator.
+        fn parse_specifiers(&mut self) -> (StorageClass, Type, bool) {
+            let mut storage = StorageClass::Auto;
+            let mut storage_count = 0;
+            let mut ty = None;
+            let mut tagged = false;
+            let mut basic = Vec::new();
+            let mut qualifiers = Qualifiers::default();
+            loop {
+                match self.peek_type() {
+                    Some(lexer::TokenType::KeywordConst)
+                    | Some(lexer::TokenType::KeywordVolatile)
+                    | Some(lexer::TokenType::KeywordRestrict) => {
+                        qualifiers = qualifiers.union(self.parse_qualifiers());
+                        continue;
+                    }
+                    Some(lexer::TokenType::KeywordStatic) => {
+                        storage = StorageClass::Static;
+                        storage_count += 1;
+                    }
+                    Some(lexer::TokenType::KeywordExtern) => {
+                        storage = StorageClass::Extern;
+                        storage_count += 1;
+                    }
+                    Some(lexer::TokenType::KeywordTypedef) => {
+                        storage = StorageClass::Typedef;
+                        storage_count += 1;
+                    }
+                    // An identifier is only a type name where no type has been
+                    // given yet, so `T T;` declares a variable named T.
+                    Some(lexer::TokenType::Identifier) if ty.is_none() && basic.is_empty() => {
+                        match self.next_typedef() {
+                            Some(alias) => ty = Some(alias),
+                            None => break,
+                        }
+                    }
+                    Some(lexer::TokenType::KeywordInt)
+                    | Some(lexer::TokenType::KeywordChar)
+                    | Some(lexer::TokenType::KeywordShort)
+                    | Some(lexer::TokenType::KeywordLong)
+                    | Some(lexer::TokenType::KeywordSigned)
+                    | Some(lexer::TokenType::KeywordUnsigned)
+                    | Some(lexer::TokenType::KeywordFloat)
+                    | Some(lexer::TokenType::KeywordDouble) => {
+                        basic.push(self.peek_type().unwrap());
+                    }
+                    Some(lexer::TokenType::KeywordVoid) => {
+                        if ty.is_some() || !basic.is_empty() {
+                            self.error(
+                                Code::InvalidSpecifiers,
+                                String::from("Multiple types in declaration"),
+                            );
+                        }
+                        ty = Some(Type::Void);
+                    }
+                    Some(lexer::TokenType::KeywordVaList) => {
+                        if ty.is_some() || !basic.is_empty() {
+                            self.error(
+                                Code::InvalidSpecifiers,
+                                String::from("Multiple types in declaration"),
+                            );
+                        }
+                        ty = Some(self.symbols.va_list().clone());
+                    }
+                    Some(lexer::TokenType::KeywordStruct)
+                    | Some(lexer::TokenType::KeywordUnion) => {
+                        if ty.is_some() || !basic.is_empty() {
+                            self.error(
+                                Code::InvalidSpecifiers,
+                                String::from("Multiple types in declaration"),
+                            );
+                        }
+                        ty = Some(self.parse_struct_specifier());
+                        tagged = true;
+                        continue;
+                    }
+                    Some(lexer::TokenType::KeywordEnum) => {
+                        if ty.is_some() || !basic.is_empty() {
+                            self.error(
+                                Code::InvalidSpecifiers,
+                                String::from("Multiple types in declaration"),
+                            );
+                        }
+                        ty = Some(self.parse_enum_specifier());
+                        tagged = true;
+                        continue;
+                    }
+                    _ => break,
+                }
+                self.tokens.next();
+            }
+
+            if storage_count > 1 {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Multiple storage classes in declaration"),
+                );
+            }
+            if !basic.is_empty() {
+                if ty.is_some() {
+                    self.error(
+                        Code::InvalidSpecifiers,
+                        String::from("Multiple types in declaration"),
+                    );
+                }
+                ty = Some(self.basic_type(&basic));
+            }
+            let ty = match ty {
+                Some(ty) => ty,
+                None => {
+                    self.expect(lexer::TokenType::KeywordInt, "type specifier");
+                    Type::Int
+                }
+            };
+            if qualifiers.is_restrict && !ty.is_pointer() {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    format!("Restrict requires a pointer type ('{}' is invalid)", ty),
+                );
+                qualifiers.is_restrict = false;
+            }
+            return (storage, ty.qualified(qualifiers), tagged);
+        }
+
+        // Combines the keywords naming an arithmetic type, given in any order,
+        // into that type. Each keyword may appear once, except that long may
+        // appear twice.
+        fn basic_type(&self, keywords: &[lexer::TokenType]) -> Type {
+            let count =
+                |keyword: lexer::TokenType| keywords.iter().filter(|&k| *k == keyword).count();
+            let int = count(lexer::TokenType::KeywordInt);
+            let char = count(lexer::TokenType::KeywordChar);
+            let short = count(lexer::TokenType::KeywordShort);
+            let long = count(lexer::TokenType::KeywordLong);
+            let signed = count(lexer::TokenType::KeywordSigned);
+            let unsigned = count(lexer::TokenType::KeywordUnsigned);
+            let float = count(lexer::TokenType::KeywordFloat);
+            let double = count(lexer::TokenType::KeywordDouble);
+
+            if float + double > 0 {
+                if double == 1 && long == 1 && keywords.len() == 2 {
+                    self.error(
+                        Code::Unsupported,
+                        String::from("'long double' is not supported"),
+                    );
+                } else if keywords.len() > 1 {
+                    self.error(
+                        Code::InvalidSpecifiers,
+                        String::from("Multiple types in declaration"),
+                    );
+                }
+                if double > 0 {
+                    return Type::Double;
+                }
+                return Type::Float;
+            }
+
+            if int > 1 {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Duplicate int keyword in declaration"),
+                );
+            }
+            if char > 1 || short > 1 || signed > 1 || unsigned > 1 {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Duplicate type keyword in declaration"),
+                );
+            }
+            if long > 2 {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("'long long long' is too long"),
+                );
+            }
+            if signed > 0 && unsigned > 0 {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Cannot combine signed and unsigned in declaration"),
+                );
+            }
+            if (char > 0 && int + short + long > 0) || (short > 0 && long > 0) {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Multiple types in declaration"),
+                );
+            }
+
+            let is_unsigned = unsigned > 0;
+            if char > 0 {
+                if is_unsigned {
+                    return Type::UnsignedChar;
+                } else if signed > 0 {
+                    return Type::SignedChar;
+                }
+                return Type::Char;
+            }
+            match (short > 0, long, is_unsigned) {
+                (true, _, false) => return Type::Short,
+                (true, _, true) => return Type::UnsignedShort,
+                (false, 0, false) => return Type::Int,
+                (false, 0, true) => return Type::UnsignedInt,
+                (false, 1, false) => return Type::Long,
+                (false, 1, true) => return Type::UnsignedLong,
+                (false, _, false) => return Type::LongLong,
+                (false, _, true) => return Type::UnsignedLongLong,
+            }
+        }
+
+        // Parses `enum tag`, `enum tag { enumerators }` or `enum { enumerators }`.
+        // Enumerators are int constants declared in the current scope, each one
+        // more than the one before unless it is given a value. Enumerated types
+        // are int.
+        fn parse_enum_specifier(&mut self) -> Type {
+            self.expect(lexer::TokenType::KeywordEnum, "enum keyword");
+            let mut tag = String::new();
+            if self.next_is(lexer::TokenType::Identifier) {
+                tag = self.tokens.next().unwrap().data.clone();
+            } else if !self.next_is(lexer::TokenType::OpenBrace) {
+                self.expect(lexer::TokenType::Identifier, "enum tag or open brace");
+                return Type::Int;
+            }
+
+            if !self.next_is(lexer::TokenType::OpenBrace) {
+                if let Err((code, message, _)) = self.symbols.lookup_enum(&tag) {
+                    self.error(code, message);
+                }
+                return Type::Int;
+            }
+            if !tag.is_empty() {
+                if let Err((code, message, _)) = self.symbols.declare_enum(&tag) {
+                    self.error(code, message);
+                }
+            }
+
+            self.expect(lexer::TokenType::OpenBrace, "open brace");
+            let mut value: i64 = 0;
+            while let Some(token) = self.expect(lexer::TokenType::Identifier, "enumerator") {
+                let (name, span) = (token.data.clone(), Some(token.span()));
+                if self.next_is(lexer::TokenType::Assign) {
+                    self.tokens.next();
+                    let expression = self.parse_constant_expression();
+                    match self.evaluate_required(&expression) {
+                        Ok(v) => value = v,
+                        Err(ConstantError::NotConstant) => self.error(
+                            Code::NotConstant,
+                            format!("Enumerator value for '{}' is not an integer constant", name),
+                        ),
+                        Err(_) => {}
+                    }
+                }
+                if value < i64::from(i32::MIN) || value > i64::from(i32::MAX) {
+                    self.error(
+                        Code::OutOfRange,
+                        format!("Enumerator value for '{}' is out of range of int", name),
+                    );
+                }
+                if !self.symbols.declare_constant(&name, value, span) {
+                    self.declaration_error(
+                        (
+                            Code::Redefinition,
+                            format!("Redefinition of enumerator '{}'", name),
+                            self.symbols.current_scope_span(&name),
+                        ),
+                        span,
+                    );
+                }
+                value += 1;
+
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                self.tokens.next();
+                if self.next_is(lexer::TokenType::CloseBrace) {
+                    break;
+                }
+            }
+            self.expect(lexer::TokenType::CloseBrace, "close brace");
+            return Type::Int;
+        }
+
+        // Parses `struct tag`, `struct tag { members }` or `struct { members }`,
+        // and the same for unions.
+        fn parse_struct_specifier(&mut self) -> Type {
+            let is_union = self.next_is(lexer::TokenType::KeywordUnion);
+            self.tokens.next();
+
+            let mut tag = String::new();
+            if self.next_is(lexer::TokenType::Identifier) {
+                tag = self.tokens.next().unwrap().data.clone();
+            } else if !self.next_is(lexer::TokenType::OpenBrace) {
+                self.expect(lexer::TokenType::Identifier, "struct tag or open brace");
+                return Type::Int;
+            }
+
+            // A definition, or a declaration of just the tag, introduces a
+            // new type in this scope even if an outer scope has one.
+            let defining = self.next_is(lexer::TokenType::OpenBrace);
+            let current_scope_only = defining || self.next_is(lexer::TokenType::SemiColon);
+            let ty = match self.symbols.declare_tag(&tag, is_union, current_scope_only) {
+                Ok(ty) => ty,
+                Err((code, message, _)) => {
+                    self.error(code, message);
+                    Type::Struct(AggregateRef::new(&tag, is_union))
+                }
+            };
+            if !defining {
+                return ty;
+            }
+
+            let mut ty = ty;
+            if ty.is_complete() {
+                self.error(Code::Redefinition, format!("Redefinition of '{}'", ty));
+            } else if self.defining.contains(&ty) {
+                // The inner definition gets a type of its own, so that the
+                // outer one cannot contain itself.
+                self.error(
+                    Code::Redefinition,
+                    format!("Nested redefinition of '{}'", ty),
+                );
+                ty = Type::Struct(AggregateRef::new(&tag, is_union));
+            }
+            self.defining.push(ty.clone());
+            let members = self.parse_members(is_union);
+            self.defining.pop();
+            if let Type::Struct(ref aggregate) = ty {
+                if !aggregate.define(members) {
+                    self.error(Code::InvalidDeclarator, format!("'{}' is too large", ty));
+                }
+            }
+            return ty;
+        }
+
+        // Parses `{ member-declaration* }` into the member names and types.
+        // The last member of a struct with other members may be a flexible
+        // array member, an array without a length that takes no space.
+        fn parse_members(&mut self, is_union: bool) -> Vec<(String, Type)> {
+            let mut members: Vec<(String, Type)> = Vec::new();
+            // The start of the declaration of each member, for diagnostics.
+            let mut spans = Vec::new();
+            let open = self.expect(lexer::TokenType::OpenBrace, "open brace");
+            while self.peek_type().is_some() && !self.next_is(lexer::TokenType::CloseBrace) {
+                let remaining = self.remaining();
+                let start = self.next_span();
+                self.parse_member_declaration(&mut members);
+                spans.resize(members.len(), start);
+                self.finish_item(remaining);
+            }
+            self.expect_closing(lexer::TokenType::CloseBrace, "close brace", open);
+            if members.is_empty() {
+                self.error(
+                    Code::InvalidDeclarator,
+                    String::from("Struct or union has no members"),
+                );
+            }
+            for (index, (name, ty)) in members.iter().enumerate() {
+                if !ty.is_incomplete_array() {
+                    continue;
+                }
+                let message = if is_union {
+                    format!("Flexible array member '{}' in a union", name)
+                } else if index + 1 != members.len() {
+                    format!(
+                        "Flexible array member '{}' is not at the end of the struct",
+                        name
+                    )
+                } else if index == 0 {
+                    format!(
+                        "Flexible array member '{}' in a struct with no other members",
+                        name
+                    )
+                } else {
+                    continue;
+                };
+                self.report(Diagnostic::error(
+                    Code::IncompleteType,
+                    message,
+                    spans[index],
+                ));
+            }
+            return members;
+        }
+
+        // Parses one member declaration, adding each of its comma separated
+        // members to the list.
+        fn parse_member_declaration(&mut self, members: &mut Vec<(String, Type)>) {
+            if self.next_is(lexer::TokenType::KeywordStaticAssert) {
+                self.parse_static_assert();
+                return;
+            }
+            let (storage, base, tagged) = self.parse_specifiers();
+            if storage != StorageClass::Auto {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Storage class in member declaration"),
+                );
+            }
+            // A nested struct or enum definition declares its tag and
+            // enumerators, not a member.
+            if tagged && self.next_is(lexer::TokenType::SemiColon) {
+                self.tokens.next();
+                return;
+            }
+
+            loop {
+                self.parse_member_declarator(base.clone(), members);
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                self.tokens.next();
+            }
+            self.expect(lexer::TokenType::SemiColon, "semi-colon");
+        }
+
+        fn parse_member_declarator(&mut self, base: Type, members: &mut Vec<(String, Type)>) {
+            let declarator = self.parse_declarator();
+            let ty = self.derive_type(base, &declarator.derivations);
+            if self.next_is(lexer::TokenType::Colon) {
+                self.parse_bit_field(declarator.name, ty, members);
+                return;
+            }
+            let name = match declarator.name {
+                Some(ref name) => name.clone(),
+                None => {
+                    self.expect(lexer::TokenType::Identifier, "member name");
+                    return;
+                }
+            };
+
+            if ty.is_function() {
+                self.error(
+                    Code::InvalidDeclarator,
+                    format!("Field '{}' declared as a function", name),
+                );
+                return;
+            }
+            // An array without a length is a flexible array member, checked
+            // once all the members are known.
+            let flexible = ty.is_incomplete_array()
+                && ty.element().is_some_and(|element| element.is_complete());
+            if !ty.is_complete() && !flexible {
+                self.error(
+                    Code::IncompleteType,
+                    format!("Field '{}' has incomplete type '{}'", name, ty),
+                );
+                return;
+            }
+            if members.iter().any(|m| m.0 == name) {
+                self.error(Code::Redefinition, format!("Duplicate member '{}'", name));
+            }
+            members.push((name, ty));
+        }
+
+        // Parses the width of a bit-field after its declarator. Bit-fields are
+        // not supported, but one that is well formed is reported as such, and
+        // a named one is still added as an ordinary member so that its uses
+        // do not report further errors.
+        fn parse_bit_field(
+            &mut self,
+            name: Option<String>,
+            ty: Type,
+            members: &mut Vec<(String, Type)>,
+        ) {
+            self.expect(lexer::TokenType::Colon, "colon");
+            let width = self.parse_constant_expression();
+            let field = match name {
+                Some(ref name) => format!("bit-field '{}'", name),
+                None => String::from("unnamed bit-field"),
+            };
+            if self.check_bit_field_width(&field, name.is_some(), &ty, &width) {
+                let message = match name {
+                    Some(ref name) => format!("Bit-field '{}' is not supported", name),
+                    None => String::from("Bit-fields are not supported"),
+                };
+                self.error(Code::Unsupported, message);
+            }
+            if let Some(name) = name {
+                if ty.is_complete() && !members.iter().any(|m| m.0 == name) {
+                    members.push((name, ty));
+                }
+            }
+        }
+
+        // Checks that a bit-field has integer type and a constant width that
+        // fits in it. Only an unnamed bit-field may have zero width.
+        fn check_bit_field_width(
+            &self,
+            field: &str,
+            named: bool,
+            ty: &Type,
+            width: &AstNode,
+        ) -> bool {
+            if !ty.is_integer() {
+                self.error(
+                    Code::InvalidDeclarator,
+                    format!("Invalid type '{}' for {}", ty, field),
+                );
+                return false;
+            }
+            let width = match self.evaluate_required(width) {
+                Ok(width) => width,
+                Err(ConstantError::NotConstant) => {
+                    self.error(
+                        Code::NotConstant,
+                        format!("Width of {} is not an integer constant", field),
+                    );
+                    return false;
+                }
+                Err(_) => return false,
+            };
+            let bits = ty.size() as i64 * 8;
+            let message = if width < 0 {
+                format!("Width of {} is negative ({})", field, width)
+            } else if width == 0 && named {
+                format!("Zero width for {}", field)
+            } else if width > bits {
+                format!(
+                    "Width of {} ({} bits) exceeds the width of its type ({} bits)",
+                    field, width, bits
+                )
+            } else {
+                return true;
+            };
+            self.error(Code::InvalidDeclarator, message);
+            return false;
+        }
+
+        // Parses a sequence of `[N]`, giving a length of zero to `[]`.
+        fn parse_array_lengths(&mut self) -> Vec<usize> {
+            let mut lengths = Vec::new();
+            while self.next_is(lexer::TokenType::OpenBracket) {
+                self.tokens.next();
+                let mut length = 0;
+                if !self.next_is(lexer::TokenType::CloseBracket) {
+                    let size = self.parse_expression();
+                    let size = sema::value(size, &self.symbols, self);
+                    match self.evaluate_required(&size) {
+                        Ok(value) if value > i64::from(i32::MAX) => {
+                            self.error(Code::InvalidDeclarator, String::from("Array is too large"));
+                            length = 1;
+                        }
+                        Ok(value) if value > 0 => length = value as usize,
+                        Ok(_) => {
+                            self.error(
+                                Code::InvalidDeclarator,
+                                String::from("Array size must be greater than zero"),
+                            );
+                            length = 1;
+                        }
+                        Err(ConstantError::NotConstant) => {
+                            self.error(
+                                Code::NotConstant,
+                                String::from("Array size is not an integer constant"),
+                            );
+                            length = 1;
+                        }
+                        Err(_) => length = 1,
+                    }
+                }
+                self.expect(lexer::TokenType::CloseBracket, "close bracket");
+                lengths.push(length);
+            }
+            return lengths;
+        }
+
+        // Parses a type name, as used by casts and sizeof: specifiers without
+        // a storage class followed by an abstract declarator, one that declares
+        // no name.
+        fn parse_type_name(&mut self) -> Type {
+            let (storage, base, _) = self.parse_specifiers();
+            if storage != StorageClass::Auto {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    String::from("Storage class not allowed in type name"),
+                );
+            }
+            let declarator = self.parse_declarator();
+            if let Some(name) = declarator.name {
+                self.error(
+                    Code::Syntax,
+                    format!("Unexpected identifier '{}' in type name", name),
+                );
+            }
+            return self.derive_type(base, &declarator.derivations);
+        }
+
+        // Parses a declarator, which may leave out the name. The pointers
+        // apply to the base type first, then the array and function suffixes
+        // from the last one, then whatever is nested in parentheses: `*a[3]`
+        // is an array of pointers while `(*a)[3]` is a pointer to an array, and
+        // `(*f)(int)` a pointer to a function.
+        fn parse_declarator(&mut self) -> Declarator {
+            let mut derivations = Vec::new();
+            while self.next_is(lexer::TokenType::Star) {
+                self.tokens.next();
+                let qualifiers = self.parse_qualifiers();
+                derivations.push(Derivation::Pointer(qualifiers));
+            }
+
+            let mut name = None;
+            let mut span = None;
+            let mut nested = Vec::new();
+            if self.next_is_nested_declarator() {
+                self.tokens.next();
+                let inner = self.parse_declarator();
+                self.expect(lexer::TokenType::CloseParen, "close paren");
+                name = inner.name;
+                span = inner.span;
+                nested = inner.derivations;
+            } else if self.next_is(lexer::TokenType::Identifier) {
+                let token = self.tokens.next().unwrap();
+                name = Some(token.data.clone());
+                span = Some(token.span());
+            }
+
+            let mut suffixes = Vec::new();
+            loop {
+                if self.next_is(lexer::TokenType::OpenBracket) {
+                    for length in self.parse_array_lengths() {
+                        suffixes.push(Derivation::Array(length));
+                    }
+                } else if self.next_is(lexer::TokenType::OpenParen) {
+                    let (parameters, variadic) = self.parse_parameters();
+                    suffixes.push(Derivation::Function(parameters, variadic));
+                } else {
+                    break;
+                }
+            }
+            derivations.extend(suffixes.into_iter().rev());
+            derivations.extend(nested);
+            return Declarator {
+                name,
+                span,
+                derivations,
+            };
+        }
+
+        // A parenthesis in a declarator either nests another declarator or
+        // starts the parameters of a function, which begin with a type name or
+        // are empty.
+        fn next_is_nested_declarator(&self) -> bool {
+            if !self.next_is(lexer::TokenType::OpenParen) {
+                return false;
+            }
+            match self.peek_type_at(1) {
+                Some(lexer::TokenType::Star)
+                | Some(lexer::TokenType::OpenParen)
+                | Some(lexer::TokenType::OpenBracket) => return true,
+                Some(lexer::TokenType::Identifier) => return !self.starts_type_name(1),
+                _ => return false,
+            }
+        }
+
+        // The name of a declarator that must have one.
+        fn declarator_name(&mut self, declarator: &Declarator) -> String {
+            match declarator.name {
+                Some(ref name) => return name.clone(),
+                None => {
+                    self.syntax_error(String::from("Declarator has no name: expected identifier"));
+                    return String::new();
+                }
+            }
+        }
+
+        // Applies the derivations of a declarator to its base type, reporting
+        // the types C does not allow: arrays of incomplete types or of
+        // functions, and functions returning arrays or functions. An array
+        // type is only reported once, for its innermost incomplete part.
+        fn derive_type(&self, base: Type, derivations: &[Derivation]) -> Type {
+            let mut ty = base;
+            for derivation in derivations.iter() {
+                ty = match *derivation {
+                    Derivation::Pointer(qualifiers) => Type::pointer_to(ty).qualified(qualifiers),
+                    Derivation::Array(length) => {
+                        let mut length = length;
+                        if ty.is_function() {
+                            self.error(
+                                Code::InvalidDeclarator,
+                                format!("Array of functions of type '{}'", ty),
+                            );
+                        } else if ty.is_incomplete_array() || (!ty.is_array() && !ty.is_complete())
+                        {
+                            self.error(
+                                Code::IncompleteType,
+                                String::from("Array has incomplete element type"),
+                            );
+                        } else if i64::from(ty.size()) * length as i64 > i64::from(i32::MAX) {
+                            // Every length fits in an int, so the product
+                            // fits in a long.
+                            self.error(Code::InvalidDeclarator, String::from("Array is too large"));
+                            length = 1;
+                        }
+                        Type::Array(Box::new(ty), length)
+                    }
+                    Derivation::Function(ref parameters, variadic) => {
+                        if ty.is_function() {
+                            self.error(
+                                Code::InvalidDeclarator,
+                                format!("Function cannot return function type '{}'", ty),
+                            );
+                        } else if ty.is_array() {
+                            self.error(
+                                Code::InvalidDeclarator,
+                                format!("Function cannot return array type '{}'", ty),
+                            );
+                        }
+                        // Qualifiers on the return type and the parameters
+                        // make no difference to the function's type.
+                        let types = parameters
+                            .iter()
+                            .map(|p| p.1.unqualified().clone())
+                            .collect();
+                        Type::Function(Box::new(ty.unqualified().clone()), types, variadic)
+                    }
+                };
+            }
+            return ty;
+        }
+
+        // Warns that a block scope declaration hides a variable of an
+        // enclosing scope. Redeclaring a name in the same scope is an error
+        // reported elsewhere.
+        fn check_shadow(&self, name: &str, span: Option<Span>) {
+            if self.symbols.declared_in_current_scope(name) {
+                return;
+            }
+            let shadows = match self.symbols.lookup(name) {
+                Some(symbol) => matches!(symbol.kind, SymbolKind::Variable | SymbolKind::Static),
+                None => false,
+            };
+            if shadows {
+                self.report(Diagnostic::warning(
+                    Code::Shadow,
+                    format!(
+                        "Declaration of '{}' shadows a variable in an outer scope",
+                        name
+                    ),
+                    span,
+                ));
+            }
+        }
+
+        fn check_complete(&self, name: &str, ty: &Type) {
+            if ty.is_incomplete_array() {
+                self.error(
+                    Code::IncompleteType,
+                    format!("Array size missing in '{}'", name),
+                );
+            } else if !ty.is_complete() {
+                self.error(
+                    Code::IncompleteType,
+                    format!("Variable '{}' has incomplete type '{}'", name, ty),
+                );
+            }
+        }
+
+        // The type named by the next token, if it is an identifier declared as
+        // a typedef in the innermost scope that declares it.
+        fn next_typedef(&self) -> Option<Type> {
+            let token = self.tokens.as_slice().first()?;
+            if token.token_type != lexer::TokenType::Identifier {
+                return None;
+            }
+            match self.symbols.lookup(&token.data) {
+                Some(&Symbol {
+                    kind: SymbolKind::Typedef,
+                    ref ty,
+                    ..
+                }) => return Some(ty.clone()),
+                _ => return None,
+            }
+        }
+
+        // Parses the declarator of a typedef and declares the name as an alias
+        // for its type.
+        // Parses the comma separated declarators of a typedef declaration.
+        fn parse_typedef(&mut self, base: Type) {
+            loop {
+                self.parse_typedef_declarator(base.clone());
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                self.tokens.next();
+            }
+            self.expect(lexer::TokenType::SemiColon, "semi-colon");
+        }
+
+        fn parse_typedef_declarator(&mut self, base: Type) {
+            let declarator = self.parse_declarator();
+            let name = match declarator.name {
+                Some(ref name) => name.clone(),
+                None => {
+                    self.expect(lexer::TokenType::Identifier, "identifier");
+                    return;
+                }
+            };
+            let ty = self.derive_type(base, &declarator.derivations);
+            if self.next_is(lexer::TokenType::Assign) {
+                self.error(
+                    Code::InvalidSpecifiers,
+                    format!("Typedef '{}' cannot have an initializer", name),
+                );
+                self.tokens.next();
+                self.parse_initializer(&mut ty.clone());
+            }
+            if let Err(error) = self.symbols.declare_typedef(&name, ty, declarator.span) {
+                self.declaration_error(error, declarator.span);
+            }
+        }
+
+        // Whether the token at the index begins a type name: a type specifier
+        // keyword or an identifier declared as a typedef.
+        fn starts_type_name(&self, index: usize) -> bool {
+            let token = match self.tokens.as_slice().get(index) {
+                Some(token) => token,
+                None => return false,
+            };
+            match token.token_type {
+                lexer::TokenType::KeywordVoid
+                | lexer::TokenType::KeywordInt
+                | lexer::TokenType::KeywordChar
+                | lexer::TokenType::KeywordShort
+                | lexer::TokenType::KeywordLong
+                | lexer::TokenType::KeywordSigned
+                | lexer::TokenType::KeywordUnsigned
+                | lexer::TokenType::KeywordFloat
+                | lexer::TokenType::KeywordDouble
+                | lexer::TokenType::KeywordStruct
+                | lexer::TokenType::KeywordUnion
+                | lexer::TokenType::KeywordEnum
+                | lexer::TokenType::KeywordVaList
+                | lexer::TokenType::KeywordConst
+                | lexer::TokenType::KeywordVolatile
+                | lexer::TokenType::KeywordRestrict => return true,
+                lexer::TokenType::Identifier => match self.symbols.lookup(&token.data) {
+                    Some(symbol) => return symbol.kind == SymbolKind::Typedef,
+                    None => return false,
+                },
+                _ => return false,
+            }
+        }
+
+        fn next_is_declaration(&self) -> bool {
+            return self.starts_type_name(0)
+                || self.next_is(lexer::TokenType::KeywordTypedef)
+                || self.next_is(lexer::TokenType::KeywordStatic)
+                || self.next_is(lexer::TokenType::KeywordExtern);
+        }
+
+        // Declares or defines a function of the given type, whose parameters
+        // have been parsed with its declarator.
+        fn parse_function(
+            &mut self,
+            node: &mut AstNode,
+            ty: Type,
+            parameters: Vec<Parameter>,
+            storage: StorageClass,
+        ) {
+            let name = node.data.clone();
+            let return_type = match ty {
+                Type::Function(ref return_type, ..) => (**return_type).clone(),
+                _ => unreachable!(),
+            };
+            node.ty = ty;
+
+            // Parameters are declared in the same scope as the body's
+            // outermost declarations.
+            self.symbols.enter_scope();
+            for (parameter_name, parameter_type, span) in parameters {
+                let mut parameter = AstNode::new_typed(
+                    AstNodeType::Parameter,
+                    String::new(),
+                    parameter_type.unqualified().clone(),
+                );
+                if let Some(parameter_name) = parameter_name {
+                    match self.symbols.declare(&parameter_name, parameter_type, span) {
+                        Some(unique_name) => parameter.data = unique_name,
+                        None => {
+                            self.declaration_error(
+                                (
+                                    Code::Redefinition,
+                                    format!("Redefinition of parameter '{}'", parameter_name),
+                                    self.symbols.current_scope_span(&parameter_name),
+                                ),
+                                span,
+                            );
+                        }
+                    }
+                }
+                node.add_child(parameter);
+            }
+
+            let is_definition = self.next_is(lexer::TokenType::OpenBrace);
+            // Structs are not yet passed and returned the way the ABI requires.
+            if return_type.is_struct() || node.children.iter().any(|p| p.ty.is_struct()) {
+                self.error(
+                    Code::Unsupported,
+                    format!(
+                        "Function '{}' passes or returns a struct by value, which is not supported",
+                        name
+                    ),
+                );
+            }
+            match self.symbols.declare_function(
+                &name,
+                node.ty.clone(),
+                is_definition,
+                storage,
+                node.span,
+            ) {
+                Ok(linkage) => node.linkage = linkage,
+                Err(error) => self.declaration_error(error, node.span),
+            }
+
+            if is_definition {
+                // The body's brace is a safe place to pick up again after an
+                // error in the declarator.
+                self.recovering = false;
+                let body = self.parse_compound();
+                node.add_child(body);
+            } else {
+                node.node_type = AstNodeType::FunctionPrototype;
+            }
+            self.symbols.exit_scope();
+        }
+
+        fn parse_global_variable(
+            &mut self,
+            name: &str,
+            ty: Type,
+            storage: StorageClass,
+            span: Option<Span>,
+        ) {
+            let mut ty = ty;
+            let mut definition = if storage == StorageClass::Extern {
+                Definition::None
+            } else {
+                Definition::Tentative
+            };
+            if self.next_is(lexer::TokenType::Assign) {
+                self.tokens.next();
+                definition = Definition::Initialized(self.parse_constant_initializer(&mut ty));
+            }
+            if definition != Definition::None {
+                self.check_complete(name, &ty);
+            }
+
+            if let Err(error) = self
+                .symbols
+                .declare_global(name, ty, storage, definition, span)
+            {
+                self.declaration_error(error, span);
+            }
+        }
+
+        // Objects with static storage duration are initialized before the
+        // program starts, so their initializers must be constants.
+        fn parse_constant_initializer(&mut self, ty: &mut Type) -> Vec<StaticInit> {
+            let initializer = self.parse_initializer(ty);
+            let initializer =
+                sema::initializer(initializer, ty, &self.symbols, self.last_span(), self);
+            lint::check_static_initializer(&initializer, &self.symbols, self.last_span(), self);
+            let mut init = Vec::new();
+            self.static_initializer(ty, &initializer, &mut init);
+            return init;
+        }
+
+        // Lays out a parsed initializer as the initial contents of a static
+        // object, zero filling the elements it leaves out.
+        fn static_initializer(&self, ty: &Type, initializer: &AstNode, init: &mut Vec<StaticInit>) {
+            let ty = ty.unqualified();
+            if let Type::Array(ref element, length) = *ty {
+                for index in 0..length {
+                    match initializer.children.get(index) {
+                        Some(child) => self.static_initializer(element, child, init),
+                        None => push_zero(init, element.size()),
+                    }
+                }
+                return;
+            }
+            if let Type::Struct(ref aggregate) = *ty {
+                if initializer.node_type != AstNodeType::InitializerList {
+                    self.error(
+                        Code::NotConstant,
+                        String::from("Initializer element is not a compile-time constant"),
+                    );
+                    return;
+                }
+                let mut position = 0;
+                for (index, (member, offset)) in aggregate.members().into_iter().enumerate() {
+                    if element_type(ty, index).is_none() {
+                        break;
+                    }
+                    push_zero(init, offset - position);
+                    match initializer.children.get(index) {
+                        Some(child) => self.static_initializer(&member, child, init),
+                        None => push_zero(init, member.size()),
+                    }
+                    position = offset + member.size();
+                }
+                push_zero(init, ty.size() - position);
+                return;
+            }
+            // Floating objects are laid out as their IEEE 754 bit patterns.
+            if ty.is_floating() {
+                match floating_value(initializer) {
+                    Some(value) => {
+                        let bits = if *ty == Type::Float {
+                            i64::from((value as f32).to_bits())
+                        } else {
+                            value.to_bits() as i64
+                        };
+                        if bits == 0 {
+                            push_zero(init, ty.size());
+                        } else {
+                            init.push(StaticInit::Value(ty.clone(), bits));
+                        }
+                    }
+                    None => self.error(
+                        Code::NotConstant,
+                        String::from("Initializer element is not a compile-time constant"),
+                    ),
+                }
+                return;
+            }
+            // A pointer can only be initialized with an integer constant,
+            // usually a null pointer constant, converted to pointer types, or
+            // with the address of a function or static object, such as a
+            // string literal.
+            let mut initializer = initializer;
+            while ty.is_pointer()
+                && initializer.node_type == AstNodeType::Cast
+                && initializer.ty.is_pointer()
+            {
+                initializer = &initializer.children[0];
+            }
+            if let Some((base, name, offset)) = self.address_constant(initializer) {
+                init.push(StaticInit::Address(base, name, offset));
+                return;
+            }
+            match self.evaluate_required(initializer) {
+                Ok(0) => push_zero(init, ty.size()),
+                Ok(value) => init.push(StaticInit::Value(ty.clone(), value)),
+                Err(ConstantError::NotConstant) => {
+                    self.error(
+                        Code::NotConstant,
+                        String::from("Initializer element is not a compile-time constant"),
+                    );
+                }
+                Err(_) => {}
+            }
+        }
+
+        // An address constant: a pointer to a function or static object plus
+        // a constant byte offset, such as `&a[1]`, `a + 1` or `&g.m[1]`.
+        // Returns the type and name of the object and the offset.
+        fn address_constant(&self, node: &AstNode) -> Option<(Type, String, i64)> {
+            match node.node_type {
+                AstNodeType::Cast if node.ty.is_pointer() && node.children[0].ty.is_pointer() => {
+                    return self.address_constant(&node.children[0]);
+                }
+                AstNodeType::AddressOf => return self.lvalue_constant(&node.children[0]),
+                AstNodeType::BinaryOp if node.data == "+" || node.data == "-" => {
+                    let pointer = &node.children[0];
+                    let scale = match pointer.ty.pointee() {
+                        Some(pointee) if node.children[1].ty.is_integer() => pointee.size(),
+                        _ => return None,
+                    };
+                    let (base, name, offset) = self.address_constant(pointer)?;
+                    let index = constant_value(&node.children[1])?;
+                    let bytes = index.wrapping_mul(i64::from(scale));
+                    if node.data == "+" {
+                        return Some((base, name, offset.wrapping_add(bytes)));
+                    }
+                    return Some((base, name, offset.wrapping_sub(bytes)));
+                }
+                _ => return None,
+            }
+        }
+
+        // The object and byte offset an lvalue designates when its address is
+        // an address constant.
+        fn lvalue_constant(&self, node: &AstNode) -> Option<(Type, String, i64)> {
+            match node.node_type {
+                AstNodeType::Variable => {
+                    let is_static = node.ty.is_function()
+                        || self.symbols.statics().iter().any(|s| s.name == node.data);
+                    if !is_static {
+                        return None;
+                    }
+                    return Some((node.ty.clone(), node.data.clone(), 0));
+                }
+                AstNodeType::Dereference => return self.address_constant(&node.children[0]),
+                AstNodeType::Member => {
+                    let member = match node.children[0].ty {
+                        Type::Struct(ref aggregate) => aggregate.member(&node.data)?.1,
+                        _ => return None,
+                    };
+                    let (base, name, offset) = self.lvalue_constant(&node.children[0])?;
+                    return Some((base, name, offset + i64::from(member)));
+                }
+                _ => return None,
+            }
+        }
+
+        // Parses the initializer of an object of the given type. Arrays take a
+        // brace enclosed list, as may structs and unions; it becomes an
+        // InitializerList node nested to follow the type. An array declared
+        // without a length takes it from its initializer.
+        fn parse_initializer(&mut self, ty: &mut Type) -> AstNode {
+            if self.next_is_string_initializer(ty) {
+                return self.parse_string_initializer(ty);
+            }
+            if !self.next_is(lexer::TokenType::OpenBrace) {
+                let value = self.parse_assignment();
+                if ty.is_array() {
+                    self.error(
+                        Code::InvalidInitializer,
+                        String::from("Array initializer must be an initializer list"),
+                    );
+                    return AstNode::new_typed(
+                        AstNodeType::InitializerList,
+                        String::new(),
+                        ty.clone(),
+                    );
+                }
+                return value;
+            }
+            self.tokens.next();
+
+            // Braces around a scalar initializer are allowed but do nothing.
+            if !ty.is_aggregate() {
+                let value = self.parse_initializer(ty);
+                let mut excess = false;
+                while self.next_is(lexer::TokenType::Comma) {
+                    self.tokens.next();
+                    if self.next_is(lexer::TokenType::CloseBrace) {
+                        break;
+                    }
+                    excess = true;
+                    self.parse_assignment();
+                }
+                if excess {
+                    self.error(
+                        Code::InvalidInitializer,
+                        String::from("Excess elements in scalar initializer"),
+                    );
+                }
+                self.expect(lexer::TokenType::CloseBrace, "close brace");
+                return value;
+            }
+
+            let mut list =
+                AstNode::new_typed(AstNodeType::InitializerList, String::new(), ty.clone());
+            self.parse_aggregate_elements(&mut list, true);
+            self.expect(lexer::TokenType::CloseBrace, "close brace");
+            if ty.is_incomplete_array() {
+                let element = ty.element().unwrap().clone();
+                *ty = Type::Array(Box::new(element), list.children.len());
+                list.ty = ty.clone();
+            }
+            return list;
+        }
+
+        // Whether a char array is initialized with a string literal, which may
+        // be enclosed in braces.
+        fn next_is_string_initializer(&self, ty: &Type) -> bool {
+            let is_char_array = ty.element().is_some_and(|e| e.is_character());
+            let literal_next = self.next_is(lexer::TokenType::Stringliteral)
+                || (self.next_is(lexer::TokenType::OpenBrace)
+                    && self.peek_type_at(1) == Some(lexer::TokenType::Stringliteral));
+            return is_char_array && literal_next;
+        }
+
+        // Parses a string literal initializing a char array. An array without
+        // a length is sized to hold the terminating null; otherwise only the
+        // null may be left out for lack of room.
+        fn parse_string_initializer(&mut self, ty: &mut Type) -> AstNode {
+            let braced = self.next_is(lexer::TokenType::OpenBrace);
+            if braced {
+                self.tokens.next();
+            }
+            let first = self.tokens.next().unwrap();
+            let bytes = self.parse_string_literal(first);
+            if braced {
+                self.expect(lexer::TokenType::CloseBrace, "close brace");
+            }
+            match *ty {
+                Type::Array(_, 0) => {
+                    let element = ty.element().unwrap().clone();
+                    *ty = Type::Array(Box::new(element), bytes.len() + 1);
+                }
+                Type::Array(_, length) if bytes.len() > length => {
+                    self.error(
+                        Code::InvalidInitializer,
+                        String::from("Initializer-string for char array is too long"),
+                    );
+                }
+                _ => {}
+            }
+            return string_initializer(&bytes, ty);
+        }
+
+        // Parses the elements of an array, struct or union initializer into the
+        // list. Inside braces the list runs to the closing brace; a nested
+        // aggregate written without its own braces only takes as many elements
+        // as it has room for and leaves the rest to the enclosing list.
+        fn parse_aggregate_elements(&mut self, list: &mut AstNode, braced: bool) {
+            let mut reported = false;
+            loop {
+                if self.peek_type().is_none() || self.next_is(lexer::TokenType::CloseBrace) {
+                    break;
+                }
+                let element = element_type(&list.ty, list.children.len());
+                if element.is_none() && !braced {
+                    break;
+                }
+                let mut element_ty = element.clone().unwrap_or(Type::Int);
+                let item = if element_ty.is_aggregate()
+                    && !self.next_is(lexer::TokenType::OpenBrace)
+                    && !self.next_is_string_initializer(&element_ty)
+                {
+                    let mut nested =
+                        AstNode::new_typed(AstNodeType::InitializerList, String::new(), element_ty);
+                    self.parse_aggregate_elements(&mut nested, false);
+                    nested
+                } else {
+                    self.parse_initializer(&mut element_ty)
+                };
+                if element.is_some() {
+                    list.add_child(item);
+                } else if !reported {
+                    let kind = if list.ty.is_array() {
+                        "array"
+                    } else {
+                        "struct"
+                    };
+                    self.error(
+                        Code::InvalidInitializer,
+                        format!("Excess elements in {} initializer", kind),
+                    );
+                    reported = true;
+                }
+
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                if !braced {
+                    let now_full = element_type(&list.ty, list.children.len()).is_none();
+                    let closes = self
+                        .tokens
+                        .as_slice()
+                        .get(1)
+                        .map(|t| t.token_type == lexer::TokenType::CloseBrace)
+                        .unwrap_or(true);
+                    if now_full || closes {
+                        break;
+                    }
+                }
+                self.tokens.next();
+            }
+        }
+
+        // Parses the parenthesized parameter list of a function declarator and
+        // whether it ends with `...`. Both `()` and `(void)` declare a function
+        // without parameters. A parameter declared as an array or a function
+        // is really a pointer.
+        fn parse_parameters(&mut self) -> (Vec<Parameter>, bool) {
+            let mut parameters = Vec::new();
+            let open = self.expect(lexer::TokenType::OpenParen, "open paren");
+            let only_void = self.next_is(lexer::TokenType::KeywordVoid)
+                && self.peek_type_at(1) == Some(lexer::TokenType::CloseParen);
+            if only_void {
+                self.tokens.next();
+                self.tokens.next();
+                return (parameters, false);
+            }
+            if self.next_is(lexer::TokenType::CloseParen) {
+                self.tokens.next();
+                return (parameters, false);
+            }
+
+            let mut variadic = false;
+            loop {
+                if self.next_is(lexer::TokenType::Ellipsis) {
+                    self.tokens.next();
+                    if parameters.is_empty() {
+                        self.error(
+                            Code::InvalidDeclarator,
+                            String::from(
+                                "Variadic function must have a named parameter before '...'",
+                            ),
+                        );
+                    }
+                    variadic = true;
+                    break;
+                }
+                let (storage, base, _) = self.parse_specifiers();
+                if storage != StorageClass::Auto {
+                    self.error(
+                        Code::InvalidSpecifiers,
+                        String::from("Invalid storage class for parameter"),
+                    );
+                }
+                let declarator = self.parse_declarator();
+                let ty = match self.derive_type(base, &declarator.derivations) {
+                    Type::Array(element, _) => Type::Pointer(element),
+                    ty @ Type::Function(..) => Type::pointer_to(ty),
+                    ty => ty,
+                };
+                if ty == Type::Void {
+                    self.error(
+                        Code::InvalidDeclarator,
+                        String::from("Parameter cannot have type 'void'"),
+                    );
+                }
+                parameters.push((declarator.name, ty, declarator.span));
+
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                self.tokens.next();
+            }
+            self.expect_closing(lexer::TokenType::CloseParen, "close paren", open);
+            return (parameters, variadic);
+        }
+
+        // Parses `{ block-item* }`, opening a new scope for its declarations.
+        fn parse_block(&mut self) -> AstNode {
+            self.symbols.enter_scope();
+            let block = self.parse_compound();
+            self.symbols.exit_scope();
+            return block;
+        }
+
+        // Parses `{ block-item* }` in the current scope.
+        fn parse_compound(&mut self) -> AstNode {
+            let mut block = AstNode::new(AstNodeType::Block);
+            let open = self.expect(lexer::TokenType::OpenBrace, "open brace");
+
+            while self.peek_type().is_some() && !self.next_is(lexer::TokenType::CloseBrace) {
+                let remaining = self.remaining();
+                let errors = self.syntax_errors;
+                for mut item in self.parse_block_item() {
+                    if self.syntax_errors != errors {
+                        item.mark_recovered();
+                    }
+                    block.add_child(item);
+                }
+                self.finish_item(remaining);
+            }
+
+            self.expect_closing(lexer::TokenType::CloseBrace, "close brace", open);
+            return block;
+        }
+
+        fn parse_block_item(&mut self) -> Vec<AstNode> {
+            if self.next_is(lexer::TokenType::KeywordStaticAssert) {
+                self.parse_static_assert();
+                return Vec::new();
+            }
+            if self.next_is_declaration() {
+                return self.parse_declaration();
+            }
+            return vec![self.parse_statement()];
+        }
+
+        // Parses `_Static_assert(expression, "message");`, which may appear
+        // wherever a declaration may and declares nothing. The expression
+        // must be an integer constant; if it is zero, the message is
+        // reported as an error at the keyword.
+        fn parse_static_assert(&mut self) {
+            let span = self.tokens.next().map(|t| t.span());
+            let open = self.expect(lexer::TokenType::OpenParen, "open paren");
+            let condition = self.parse_constant_expression();
+            let value = self.evaluate_required(&condition);
+            self.expect(lexer::TokenType::Comma, "comma");
+            let message = match self.expect(lexer::TokenType::Stringliteral, "string literal") {
+                Some(token) => self.parse_string_literal(token),
+                None => Vec::new(),
+            };
+            self.expect_closing(lexer::TokenType::CloseParen, "close paren", open);
+            self.expect(lexer::TokenType::SemiColon, "semi-colon");
+            match value {
+                Ok(0) => self.report(Diagnostic::error(
+                    Code::StaticAssertion,
+                    format!(
+                        "Static assertion failed: \"{}\"",
+                        String::from_utf8_lossy(&message)
+                    ),
+                    span,
+                )),
+                Err(ConstantError::NotConstant) => self.report(Diagnostic::error(
+                    Code::NotConstant,
+                    String::from("Static assertion expression is not an integer constant"),
+                    span,
+                )),
+                _ => {}
+            }
+        }
+
+        // Parses a block scope declaration of a comma separated list of
+        // declarators, returning a Declaration for each automatic variable.
+        fn parse_declaration(&mut self) -> Vec<AstNode> {
+            let (storage, base, tagged) = self.parse_specifiers();
+            let mut declarations = Vec::new();
+            if tagged && self.next_is(lexer::TokenType::SemiColon) {
+                self.tokens.next();
+                return declarations;
+            }
+            if storage == StorageClass::Typedef {
+                self.parse_typedef(base);
+                return declarations;
+            }
+            loop {
+                if let Some(declaration) = self.parse_init_declarator(storage, base.clone()) {
+                    declarations.push(declaration);
+                }
+                if !self.next_is(lexer::TokenType::Comma) {
+                    break;
+                }
+                self.tokens.next();
+            }
+            self.expect(lexer::TokenType::SemiColon, "semi-colon");
+            return declarations;
+        }
+
+        // Parses one declarator of a block scope declaration and its
+        // initializer. Functions and static and extern variables are declared
+        // in the symbol table only.
+        fn parse_init_declarator(&mut self, storage: StorageClass, base: Type) -> Option<AstNode> {
+            let declarator = self.parse_declarator();
+            let mut ty = self.derive_type(base, &declarator.derivations);
+            let mut declaration =
+                AstNode::new_typed(AstNodeType::Declaration, String::new(), ty.clone());
+            declaration.span = declarator.span;
+            let name = match declarator.name {
+                Some(name) => name,
+                None => {
+                    self.expect(lexer::TokenType::Identifier, "identifier");
+                    return None;
+                }
+            };
+
+            // A function declared in a block is the file scope function.
+            if ty.is_function() {
+                if storage == StorageClass::Static {
+                    self.error(
+                        Code::InvalidSpecifiers,
+                        format!("Invalid storage class for block scope function '{}'", name),
+                    );
+                }
+                if let Err(error) = self.symbols.declare_function(
+                    &name,
+                    ty,
+                    false,
+                    StorageClass::Extern,
+                    declarator.span,
+                ) {
+                    self.declaration_error(error, declarator.span);
+                }
+                return None;
+            }
+
+            if storage != StorageClass::Extern {
+                self.check_shadow(&name, declarator.span);
+            }
+            match storage {
+                StorageClass::Static => {
+                    let mut init = Vec::new();
+                    if self.next_is(lexer::TokenType::Assign) {
+                        self.tokens.next();
+                        init = self.parse_constant_initializer(&mut ty);
+                    }
+                    self.check_complete(&name, &ty);
+                    if self
+                        .symbols
+                        .declare_local_static(&name, ty, init, declarator.span)
+                        .is_none()
+                    {
+                        self.declaration_error(
+                            (
+                                Code::Redefinition,
+                                format!("Redeclaration of '{}'", name),
+                                self.symbols.current_scope_span(&name),
+                            ),
+                            declarator.span,
+                        );
+                    }
+                    return None;
+                }
+                StorageClass::Extern => {
+                    if self.next_is(lexer::TokenType::Assign) {
+                        self.error(
+                            Code::InvalidSpecifiers,
+                            format!(
+                                "Block scope extern declaration of '{}' cannot have an initializer",
+                                name
+                            ),
+                        );
+                        self.tokens.next();
+                        self.parse_assignment();
+                    }
+                    if let Err(error) =
+                        self.symbols
+                            .declare_local_extern(&name, ty, declarator.span)
+                    {
+                        self.declaration_error(error, declarator.span);
+                    }
+                    return None;
+                }
+                StorageClass::Auto => {}
+                StorageClass::Typedef => unreachable!(),
+            }
+
+            // The variable is in scope in its own initializer, except that an
+            // array needs its initializer to know its length.
+            let mut initializer = None;
+            if ty.is_incomplete_array() && self.next_is(lexer::TokenType::Assign) {
+                self.tokens.next();
+                initializer = Some(self.parse_initializer(&mut ty));
+            }
+            self.check_complete(&name, &ty);
+            declaration.ty = ty;
+
+            match self
+                .symbols
+                .declare(&name, declaration.ty.clone(), declarator.span)
+            {
+                Some(unique_name) => declaration.data = unique_name,
+                None => {
+                    self.declaration_error(
+                        (
+                            Code::Redefinition,
+                            format!("Redeclaration of '{}'", name),
+                            self.symbols.current_scope_span(&name),
+                        ),
+                        declarator.span,
+                    );
+                    declaration.data = name.clone();
+                }
+            }
+
+            if initializer.is_none() && self.next_is(lexer::TokenType::Assign) {
+                self.tokens.next();
+                let mut ty = declaration.ty.clone();
+                initializer = Some(self.parse_initializer(&mut ty));
+            }
+            if let Some(init) = initializer {
+                declaration.add_child(init);
+            }
+            return Some(declaration);
+        }
+
+        // Parses a statement, which is placed where it starts.
+        fn parse_statement(&mut self) -> AstNode {
+            let span = self.next_span();
+            let mut statement = self.parse_unplaced_statement();
+            if statement.span.is_none() {
+                statement.span = span;
+            }
+            return statement;
+        }
+
+        fn parse_unplaced_statement(&mut self) -> AstNode {
+            match self.peek_type() {
+                Some(lexer::TokenType::KeywordRet) => {
+                    self.tokens.next();
+                    let mut statement = AstNode::new(AstNodeType::Return);
+                    if !self.next_is(lexer::TokenType::SemiColon) {
+                        let expression = self.parse_expression();
+                        statement.add_child(expression);
+                    }
+                    self.expect(lexer::TokenType::SemiColon, "semi-colon");
+                    return statement;
+                }
+                Some(lexer::TokenType::OpenBrace) => {
+                    return self.parse_block();
+                }
+                Some(lexer::TokenType::SemiColon) => {
+                    self.tokens.next();
+                    return AstNode::new(AstNodeType::NullStatement);
+                }
+                Some(lexer::TokenType::KeywordIf) => {
+                    self.tokens.next();
+                    let mut statement = AstNode::new(AstNodeType::If);
+                    let condition = self.parse_condition();
+                    statement.add_child(condition);
+                    let then_branch = self.parse_statement();
+                    statement.add_child(then_branch);
+                    if self.next_is(lexer::TokenType::KeywordElse) {
+                        self.tokens.next();
+                        let else_branch = self.parse_statement();
+                        statement.add_child(else_branch);
+                    }
+                    return statement;
+                }
+                Some(lexer::TokenType::KeywordWhile) => {
+                    self.tokens.next();
+                    let mut statement = AstNode::new(AstNodeType::While);
+                    let condition = self.parse_condition();
+                    statement.add_child(condition);
+                    let body = self.parse_statement();
+                    statement.add_child(body);
+                    return statement;
+                }
+                Some(lexer::TokenType::KeywordDo) => {
+                    self.tokens.next();
+                    let mut statement = AstNode::new(AstNodeType::DoWhile);
+                    let body = self.parse_statement();
+                    statement.add_child(body);
+                    self.expect(lexer::TokenType::KeywordWhile, "while keyword");
+                    let condition = self.parse_condition();
+                    statement.add_child(condition);
+                    self.expect(lexer::TokenType::SemiColon, "semi-colon");
+                    return statement;
+                }
+                Some(lexer::TokenType::KeywordFor) => {
+                    return self.parse_for();
+                }
+                Some(lexer::TokenType::KeywordGoto) => {
+                    self.tokens.next();
+                    let mut statement = AstNode::new(AstNodeType::Goto);
+                    if let Some(token) = self.expect(lexer::TokenType::Identifier, "label name") {
+                        statement.data = token.data.clone();
+                        statement.span = Some(token.span());
+                    }
+                    self.expect(lexer::TokenType::SemiColon, "semi-colon");
+                    return statement;
+                }
+                Some(lexer::TokenType::Identifier) if self.next_is_label() => {
+                    let token = self.tokens.next().unwrap();
+                    self.tokens.next();
+                    let mut label = AstNode::new_named(AstNodeType::Label, token.data.clone());
+                    let statement = self.parse_statement();
+                    label.add_child(statement);
+                    return label;
+                }
+                Some(lexer::TokenType::KeywordSwitch) => {
+                    return self.parse_switch();
+                }
+                Some(lexer::TokenType::KeywordCase) => {
+                    return self.parse_case();
+                }
+                Some(lexer::TokenType::KeywordDefault) => {
+                    self.tokens.next();
+                    self.expect(lexer::TokenType::Colon, "colon");
+                    let mut label = AstNode::new(AstNodeType::Default);
+                    let statement = self.parse_statement();
+                    label.add_child(statement);
+                    return label;
+                }
+                Some(lexer::TokenType::KeywordBreak) => {
+                    self.tokens.next();
+                    self.expect(lexer::TokenType::SemiColon, "semi-colon");
+                    return AstNode::new(AstNodeType::Break);
+                }
+                Some(lexer::TokenType::KeywordContinue) => {
+                    self.tokens.next();
+                    self.expect(lexer::TokenType::SemiColon, "semi-colon");
+                    return AstNode::new(AstNodeType::Continue);
+                }
+                _ => {
+                    let mut statement = AstNode::new(AstNodeType::ExpressionStatement);
+                    let expression = self.parse_expression();
+                    statement.add_child(expression);
+                    self.expect(lexer::TokenType::SemiColon, "semi-colon");
+                    return statement;
+                }
+            }
+        }
+
+        // Parses the parenthesised controlling expression of if, while and do.
+        fn parse_condition(&mut self) -> AstNode {
+            self.expect(lexer::TokenType::OpenParen, "open paren");
+            let condition = self.parse_expression();
+            self.expect(lexer::TokenType::CloseParen, "close paren");
+            return condition;
+        }
+
+        // An identifier followed by a colon starts a labeled statement.
+        fn next_is_label(&self) -> bool {
+            return self
+                .tokens
+                .as_slice()
+                .get(1)
+                .map(|t| t.token_type == lexer::TokenType::Colon)
+                .unwrap_or(false);
+        }
+
+        fn parse_switch(&mut self) -> AstNode {
+            self.expect(lexer::TokenType::KeywordSwitch, "switch keyword");
+            let mut statement = AstNode::new(AstNodeType::Switch);
+            let condition = self.parse_condition();
+            statement.add_child(condition);
+            let body = self.parse_statement();
+            statement.add_child(body);
+            return statement;
+        }
+
+        // Parses `case value:` or the range `case low ... high:` followed by
+        // the labeled statement. The values are left in their own type for
+        // semantic analysis to convert to the type of the switch.
+        fn parse_case(&mut self) -> AstNode {
+            self.expect(lexer::TokenType::KeywordCase, "case keyword");
+            let mut label = AstNode::new(AstNodeType::Case);
+            let low = self.parse_case_value();
+            let high = if self.next_is(lexer::TokenType::Ellipsis) {
+                self.tokens.next();
+                self.parse_case_value()
+            } else {
+                AstNode::new_typed(AstNodeType::Constant, low.data.clone(), low.ty.clone())
+            };
+            self.expect(lexer::TokenType::Colon, "colon");
+            label.add_child(low);
+            label.add_child(high);
+            let statement = self.parse_statement();
+            label.add_child(statement);
+            return label;
+        }
+
+        // A case value folded to a constant of the expression's type.
+        fn parse_case_value(&mut self) -> AstNode {
+            let expression = self.parse_constant_expression();
+            match self.evaluate_required(&expression) {
+                Ok(value) => {
+                    return AstNode::new_typed(
+                        AstNodeType::Constant,
+                        value.to_string(),
+                        expression.ty,
+                    );
+                }
+                Err(error) => {
+                    if error == ConstantError::NotConstant {
+                        self.error(
+                            Code::NotConstant,
+                            String::from("Case label does not reduce to an integer constant"),
+                        );
+                    }
+                    return AstNode::new_typed(AstNodeType::Constant, String::from("0"), Type::Int);
+                }
+            }
+        }
+
+        // A for statement always has four children: init, condition, post and
+        // body. Omitted clauses become null statements, except for a missing
+        // condition which is always true. A declaration in the init clause is
+        // scoped to the loop.
+        fn parse_for(&mut self) -> AstNode {
+            let mut statement = AstNode::new(AstNodeType::For);
+            self.expect(lexer::TokenType::KeywordFor, "for keyword");
+            self.expect(lexer::TokenType::OpenParen, "open paren");
+            self.symbols.enter_scope();
+
+            if self.next_is_declaration() {
+                if self.next_is(lexer::TokenType::KeywordStatic)
+                    || self.next_is(lexer::TokenType::KeywordExtern)
+                    || self.next_is(lexer::TokenType::KeywordTypedef)
+                {
+                    self.error(
+                        Code::InvalidSpecifiers,
+                        String::from(
+                            "Declaration in for loop initializer must not have a storage class",
+                        ),
+                    );
+                }
+                // Several declarators are kept together in a block, which
+                // does not start a scope of its own.
+                let mut declarations = self.parse_declaration();
+                let init = if declarations.len() == 1 {
+                    declarations.pop().unwrap()
+                } else {
+                    let mut block = AstNode::new(AstNodeType::Block);
+                    for declaration in declarations {
+                        block.add_child(declaration);
+                    }
+                    block
+                };
+                statement.add_child(init);
+            } else {
+                let init = self.parse_optional_expression(lexer::TokenType::SemiColon);
+                statement.add_child(init);
+                self.expect(lexer::TokenType::SemiColon, "semi-colon");
+            }
+
+            let condition = if self.next_is(lexer::TokenType::SemiColon) {
+                AstNode::new_typed(AstNodeType::Constant, String::from("1"), Type::Int)
+            } else {
+                self.parse_expression()
+            };
+            statement.add_child(condition);
+            self.expect(lexer::TokenType::SemiColon, "semi-colon");
+
+            let post = self.parse_optional_expression(lexer::TokenType::CloseParen);
+            statement.add_child(post);
+            self.expect(lexer::TokenType::CloseParen, "close paren");
+
+            let body = self.parse_statement();
+            statement.add_child(body);
+
+            self.symbols.exit_scope();
+            return statement;
+        }
+
+        fn parse_optional_expression(&mut self, terminator: lexer::TokenType) -> AstNode {
+            if self.next_is(terminator) {
+                return AstNode::new(AstNodeType::NullStatement);
+            }
+            let mut statement = AstNode::new(AstNodeType::ExpressionStatement);
+            let expression = self.parse_expression();
+            statement.add_child(expression);
+            return statement;
+        }
+
+        fn parse_expression(&mut self) -> AstNode {
+            return self.parse_assignment();
+        }
+
+        // Assignment is right associative and binds looser than every binary
+        // operator, so it is handled separately from the precedence table.
+        fn parse_assignment(&mut self) -> AstNode {
+            let lhs = self.parse_binary(0);
+            if !self.next_is(lexer::TokenType::Assign) {
+                return lhs;
+            }
+            let span = self.tokens.next().map(|t| t.span());
+            let rhs = self.parse_assignment();
+            let mut assign = AstNode::new(AstNodeType::Assign);
+            assign.span = span;
+            assign.add_child(lhs);
+            assign.add_child(rhs);
+            return assign;
+        }
+
+        fn parse_binary(&mut self, level: usize) -> AstNode {
+            if level == BINARY_PRECEDENCE.len() {
+                return self.parse_unary();
+            }
+
+            let mut lhs = self.parse_binary(level + 1);
+            loop {
+                let next = self.peek_type();
+                let operator = BINARY_PRECEDENCE[level]
+                    .iter()
+                    .find(|op| Some(op.0.clone()) == next);
+                match operator {
+                    Some(&(_, text)) => {
+                        let span = self.tokens.next().map(|t| t.span());
+                        let rhs = self.parse_binary(level + 1);
+                        let mut binary =
+                            AstNode::new_named(AstNodeType::BinaryOp, String::from(text));
+                        binary.span = span;
+                        binary.add_child(lhs);
+                        binary.add_child(rhs);
+                        lhs = binary;
+                    }
+                    None => return lhs,
+                }
+            }
+        }
+
+        // Parses an expression whose value must be known while parsing, such
+        // as an array length, and types it at once.
+        fn parse_constant_expression(&mut self) -> AstNode {
+            let expression = self.parse_binary(0);
+            return sema::value(expression, &self.symbols, self);
+        }
+
+        fn parse_unary(&mut self) -> AstNode {
+            match self.peek_type() {
+                Some(lexer::TokenType::KeywordSizeof) | Some(lexer::TokenType::KeywordAlignof) => {
+                    return self.parse_sizeof();
+                }
+                Some(lexer::TokenType::OpenParen) if self.starts_type_name(1) => {
+                    return self.parse_cast();
+                }
+                _ => {}
+            }
+            let (node_type, operator) = match self.peek_type() {
+                Some(lexer::TokenType::Minus) => (AstNodeType::UnaryOp, "-"),
+                Some(lexer::TokenType::Plus) => (AstNodeType::UnaryOp, "+"),
+                Some(lexer::TokenType::Tilde) => (AstNodeType::UnaryOp, "~"),
+                Some(lexer::TokenType::Bang) => (AstNodeType::UnaryOp, "!"),
+                Some(lexer::TokenType::Ampersand) => (AstNodeType::AddressOf, ""),
+                Some(lexer::TokenType::Star) => (AstNodeType::Dereference, ""),
+                _ => return self.parse_postfix(),
+            };
+            let mut unary = AstNode::new_named(node_type, String::from(operator));
+            unary.span = self.tokens.next().map(|t| t.span());
+            unary.add_child(self.parse_unary());
+            return unary;
+        }
+
+        // Parses `sizeof` or `_Alignof` applied to a parenthesized type name,
+        // or `sizeof` applied to an expression, which is not evaluated. The
+        // result is a constant of type unsigned long, taken from the same
+        // layout the code generator allocates objects with.
+        fn parse_sizeof(&mut self) -> AstNode {
+            let is_sizeof = self.next_is(lexer::TokenType::KeywordSizeof);
+            let keyword = if is_sizeof { "sizeof" } else { "_Alignof" };
+            self.tokens.next();
+
+            let ty = if self.next_is(lexer::TokenType::OpenParen) && self.starts_type_name(1) {
+                self.tokens.next();
+                let ty = self.parse_type_name();
+                self.expect(lexer::TokenType::CloseParen, "close paren");
+                ty
+            } else {
+                if !is_sizeof {
+                    self.error(
+                        Code::Syntax,
+                        String::from("Expected type name after '_Alignof'"),
+                    );
+                }
+                let operand = self.parse_unary();
+                sema::type_of(operand, &self.symbols, self)
+            };
+            return sema::size_of(keyword, &ty, self.last_span(), self);
+        }
+
+        // Parses `(type-name) operand`, leaving semantic analysis to check
+        // that the operand can be converted. The cast is placed at the end of
+        // its operand.
+        fn parse_cast(&mut self) -> AstNode {
+            self.tokens.next();
+            // A cast yields a value, which is never qualified.
+            let ty = self.parse_type_name().unqualified().clone();
+            self.expect(lexer::TokenType::CloseParen, "close paren");
+            let operand = self.parse_unary();
+
+            let mut cast = AstNode::new_typed(AstNodeType::Cast, String::new(), ty);
+            cast.span = self.last_span();
+            cast.add_child(operand);
+            return cast;
+        }
+
+        // Parses a primary expression followed by any number of subscripts,
+        // member accesses and calls. `a[i]` means `*(a + i)` and `p->m`
+        // means `(*p).m`; both are marked so that semantic analysis can check
+        // their operands as written.
+        fn parse_postfix(&mut self) -> AstNode {
+            let mut expression = self.parse_primary();
+            loop {
+                match self.peek_type() {
+                    Some(lexer::TokenType::OpenParen) => {
+                        expression = self.parse_call(expression);
+                    }
+                    Some(lexer::TokenType::OpenBracket) => {
+                        expression = self.parse_subscript(expression);
+                    }
+                    Some(lexer::TokenType::Dot) => {
+                        self.tokens.next();
+                        expression = self.parse_member(expression);
+                    }
+                    Some(lexer::TokenType::Arrow) => {
+                        let span = self.tokens.next().map(|t| t.span());
+                        let mut object =
+                            AstNode::new_named(AstNodeType::Dereference, String::from("arrow"));
+                        object.span = span;
+                        object.add_child(expression);
+                        expression = self.parse_member(object);
+                    }
+                    _ => return expression,
+                }
+            }
+        }
+
+        fn parse_subscript(&mut self, expression: AstNode) -> AstNode {
+            self.expect(lexer::TokenType::OpenBracket, "open bracket");
+            let index = self.parse_expression();
+            self.expect(lexer::TokenType::CloseBracket, "close bracket");
+            let mut element =
+                AstNode::new_named(AstNodeType::Dereference, String::from("subscript"));
+            element.span = self.last_span();
+            element.add_child(expression);
+            element.add_child(index);
+            return element;
+        }
+
+        // Parses the member name after `.` or `->`.
+        fn parse_member(&mut self, object: AstNode) -> AstNode {
+            let name = match self.expect(lexer::TokenType::Identifier, "member name") {
+                Some(token) => token.data.clone(),
+                None => return object,
+            };
+            let mut member = AstNode::new_named(AstNodeType::Member, name);
+            member.span = self.last_span();
+            member.add_child(object);
+            return member;
+        }
+
+        // Parses the argument list of a call of the expression. Every call
+        // is parsed as an IndirectCall; semantic analysis calls a function
+        // named by its callee directly.
+        fn parse_call(&mut self, callee: AstNode) -> AstNode {
+            let mut call = AstNode::new(AstNodeType::IndirectCall);
+            call.add_child(callee);
+            let open = self.expect(lexer::TokenType::OpenParen, "open paren");
+            if !self.next_is(lexer::TokenType::CloseParen) {
+                loop {
+                    call.add_child(self.parse_assignment());
+                    if !self.next_is(lexer::TokenType::Comma) {
+                        break;
+                    }
+                    self.tokens.next();
+                }
+            }
+            self.expect_closing(lexer::TokenType::CloseParen, "close paren", open);
+            call.span = self.last_span();
+            return call;
+        }
+
+        // Decodes a string literal and the literals directly following it,
+        // which are concatenated.
+        fn parse_string_literal(&mut self, first: &lexer::TokenInfo) -> Vec<u8> {
+            let mut bytes = Vec::new();
+            let mut token = first;
+            loop {
+                match literal_bytes(&token.data) {
+                    Ok(more) => bytes.extend(more),
+                    Err(message) => self.error(Code::InvalidLiteral, message),
+                }
+                if !self.next_is(lexer::TokenType::Stringliteral) {
+                    return bytes;
+                }
+                token = self.tokens.next().unwrap();
+            }
+        }
+
+        // Parses the parenthesized operands of a stdarg builtin into the node.
+        // The second operand of va_start names the last parameter but is not
+        // otherwise used, and that of va_arg is a type name, which gives the
+        // node its type.
+        fn parse_builtin(&mut self, node_type: AstNodeType) -> AstNode {
+            let mut node = AstNode::new(node_type);
+            node.span = self.last_span();
+            self.expect(lexer::TokenType::OpenParen, "open paren");
+            node.add_child(self.parse_assignment());
+            if node.node_type != AstNodeType::VaEnd {
+                self.expect(lexer::TokenType::Comma, "comma");
+                match node.node_type {
+                    AstNodeType::VaStart => {
+                        self.parse_assignment();
+                    }
+                    AstNodeType::VaArg => {
+                        node.ty = self.parse_type_name().unqualified().clone();
+                    }
+                    _ => node.add_child(self.parse_assignment()),
+                }
+            }
+            self.expect(lexer::TokenType::CloseParen, "close paren");
+            if node.node_type == AstNodeType::VaArg {
+                node.span = self.last_span();
+            }
+            return node;
+        }
+
+        fn parse_primary(&mut self) -> AstNode {
+            // The end of a statement or block is left for the parser to
+            // resynchronize at.
+            if self.next_is(lexer::TokenType::SemiColon)
+                || self.next_is(lexer::TokenType::CloseBrace)
+            {
+                let found = self.peek_type().unwrap();
+                self.syntax_error(format!("Unexpected {}: expected expression", found));
+                return AstNode::new_typed(AstNodeType::Constant, String::from("0"), Type::Int);
+            }
+            let token_wrapped = self.tokens.next();
+            match token_wrapped {
+                Some(token) => match token.token_type {
+                    lexer::TokenType::Intliteral => match integer_literal(&token.data) {
+                        Ok((value, ty)) => {
+                            let mut constant =
+                                AstNode::new_typed(AstNodeType::Constant, value.to_string(), ty);
+                            constant.span = Some(token.span());
+                            return constant;
+                        }
+                        Err((code, message)) => self.error(code, message),
+                    },
+                    lexer::TokenType::Floatliteral => match floating_literal(&token.data) {
+                        Some((value, ty)) => {
+                            let mut constant = AstNode::new_typed(
+                                AstNodeType::Constant,
+                                format!("{:?}", value),
+                                ty,
+                            );
+                            constant.span = Some(token.span());
+                            return constant;
+                        }
+                        None => {
+                            self.error(
+                                Code::InvalidLiteral,
+                                format!("Invalid floating literal '{}'", token.data),
+                            );
+                        }
+                    },
+                    // A name is bound to the declaration in scope here, as
+                    // which one that is changes as the parser goes.
+                    lexer::TokenType::Identifier => {
+                        let mut name = match self.symbols.lookup(&token.data) {
+                            Some(symbol) => AstNode::new_named(
+                                AstNodeType::Variable,
+                                symbol.unique_name.clone(),
+                            ),
+                            None => AstNode::new_named(AstNodeType::Identifier, token.data.clone()),
+                        };
+                        name.span = Some(token.span());
+                        return name;
+                    }
+                    // A string literal is an unnamed static object, typed
+                    // like any other literal.
+                    lexer::TokenType::Stringliteral => {
+                        let bytes = self.parse_string_literal(token);
+                        let ty = Type::Array(Box::new(Type::Char), bytes.len() + 1);
+                        let mut init = Vec::new();
+                        self.static_initializer(&ty, &string_initializer(&bytes, &ty), &mut init);
+                        let name = self.symbols.declare_string(ty.clone(), init);
+                        return AstNode::new_typed(AstNodeType::Variable, name, ty);
+                    }
+                    // A character constant has type int, with the value of
+                    // its char.
+                    lexer::TokenType::Charliteral => match literal_bytes(&token.data) {
+                        Ok(ref bytes) if bytes.len() == 1 => {
+                            return AstNode::new_typed(
+                                AstNodeType::Constant,
+                                (bytes[0] as i8).to_string(),
+                                Type::Int,
+                            );
+                        }
+                        Ok(ref bytes) if bytes.is_empty() => {
+                            self.error(
+                                Code::InvalidLiteral,
+                                String::from("Empty character constant"),
+                            );
+                        }
+                        Ok(_) => {
+                            self.error(
+                                Code::Unsupported,
+                                String::from(
+                                    "Multi-character character constants are not supported",
+                                ),
+                            );
+                        }
+                        Err(message) => self.error(Code::InvalidLiteral, message),
+                    },
+                    lexer::TokenType::KeywordVaStart => {
+                        return self.parse_builtin(AstNodeType::VaStart);
+                    }
+                    lexer::TokenType::KeywordVaArg => {
+                        return self.parse_builtin(AstNodeType::VaArg)
+                    }
+                    lexer::TokenType::KeywordVaEnd => {
+                        return self.parse_builtin(AstNodeType::VaEnd)
+                    }
+                    lexer::TokenType::KeywordVaCopy => {
+                        return self.parse_builtin(AstNodeType::VaCopy);
+                    }
+                    // Parentheses only group; `&(a)` takes the address of the
+                    // array itself.
+                    lexer::TokenType::OpenParen => {
+                        let expression = self.parse_expression();
+                        self.expect_closing(
+                            lexer::TokenType::CloseParen,
+                            "close paren",
+                            Some(token),
+                        );
+                        return expression;
+                    }
+                    _ => {
+                        self.syntax_error_at(
+                            format!("Unexpected {}: expected expression", token),
+                            Some(token.span()),
+                        );
+                    }
+                },
+                None => {
+                    self.syntax_error(String::from("Unexpected end of input: expected expression"));
+                }
+            }
+            return AstNode::new_typed(AstNodeType::Constant, String::from("0"), Type::Int);
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use lexer::*;
+        use diagnostics::diagnostics::{Code, Diagnostic, Severity, Span};
+        use types::types::Type;
+        use std::cell::RefCell;
+        use std::fs;
+        use std::path::Path;
+
+        // The errors and warnings reported while parsing the file.
+        fn parse_diagnostics(path: &Path) -> (Vec<String>, Vec<String>) {
+            let token_list = lexer::lex(path);
+            let errors = RefCell::new(Vec::new());
+            let warnings = RefCell::new(Vec::new());
+
+            let mut iter = token_list.iter();
+            super::parse(&mut iter, &|x: Diagnostic| match x.severity {
+                Severity::Error => errors.borrow_mut().push(x.message),
+                Severity::Warning => warnings.borrow_mut().push(x.message),
+            });
+
+            return (errors.into_inner(), warnings.into_inner());
+        }
+
+        fn parse_all_diagnostics(path: &Path) -> Vec<Diagnostic> {
+            let token_list = lexer::lex(path);
+            let diagnostics = RefCell::new(Vec::new());
+            super::parse(&mut token_list.iter(), &|x: Diagnostic| {
+                diagnostics.borrow_mut().push(x)
+            });
+            return diagnostics.into_inner();
+        }
+
+        // The line and column of each error reported while parsing the file.
+        fn error_positions(path: &Path) -> Vec<(u32, u32)> {
+            let token_list = lexer::lex(path);
+            let positions = RefCell::new(Vec::new());
+            super::parse(&mut token_list.iter(), &|x: Diagnostic| {
+                let span = x.span.unwrap();
+                positions.borrow_mut().push((span.line, span.start));
+            });
+            return positions.into_inner();
+        }
+
+        fn parse_errors(path: &Path) -> Vec<String> {
+            return parse_diagnostics(path).0;
+        }
+
+        fn collect_programs(dir: &Path, programs: &mut Vec<std::path::PathBuf>) {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    collect_programs(&path, programs);
+                } else if path.extension().and_then(|e| e.to_str()) == Some("c") {
+                    programs.push(path);
+                }
+            }
+        }
+
+        fn count_errors(tokens: &[lexer::TokenInfo]) -> usize {
+            let errors = RefCell::new(0);
+            super::parse(&mut tokens.iter(), &|x: Diagnostic| {
+                if x.severity == Severity::Error {
+                    *errors.borrow_mut() += 1;
+                }
+            });
+            return errors.into_inner();
+        }
+
+        // Every test program cut short after each of its tokens, and with
+        // each single token left out, must be parsed without panicking.
+        #[test]
+        fn test_malformed_input() {
+            let mut programs = Vec::new();
+            collect_programs(Path::new("test"), &mut programs);
+            for path in programs {
+                println!("Mangling: {}", path.display());
+                let tokens = lexer::lex(&path);
+                for end in 0..tokens.len() {
+                    count_errors(&tokens[..end]);
+                    let mut missing = tokens.clone();
+                    missing.remove(end);
+                    count_errors(&missing);
+                }
+            }
+        }
+
+        #[test]
+        //#[ignore]
+        fn test_succeeding() {
+            let paths = fs::read_dir("test/valid").unwrap();
+            for path in paths {
+                let thepath = path.unwrap().path();
+                if thepath.extension().and_then(|e| e.to_str()) != Some("c") {
+                    continue;
+                }
+                println!("Testing parser against name: {}", thepath.display());
+                let token_list = lexer::lex(&thepath);
+
+                let mut iter = token_list.iter();
+                let result = super::parse(&mut iter, &|x: Diagnostic| {
+                    if x.severity == Severity::Error {
+                        panic!("{}", x.message);
+                    }
+                });
+
+                println!(
+                    "Program NodeType:{:?} Data:{}",
+                    &result.node_type, &result.data
+                );
+                assert_eq!(&result.node_type, &super::AstNodeType::Program);
+
+                let function = &result.children[0];
+                assert_eq!(result.children.len(), 1);
+                assert_eq!(function.node_type, super::AstNodeType::Function);
+                assert_eq!(function.data, String::from("main"));
+
+                let body = &function.children[0];
+                assert_eq!(body.node_type, super::AstNodeType::Block);
+
+                let statement = &body.children[0];
+                assert_eq!(statement.node_type, super::AstNodeType::Return);
+                assert_eq!(
+                    statement.children[0].node_type,
+                    super::AstNodeType::Constant
+                );
+            }
+        }
+
+        fn assert_all_parse(dir: &str) {
+            for path in fs::read_dir(dir).unwrap() {
+                let thepath = path.unwrap().path();
+                println!("Testing parser against name: {}", thepath.display());
+                let errors = parse_errors(&thepath);
+                assert!(errors.is_empty(), "{:?}", errors);
+            }
+        }
+
+        fn assert_all_rejected(dir: &str) {
+            for path in fs::read_dir(dir).unwrap() {
+                let thepath = path.unwrap().path();
+                println!("Testing parser against name: {}", thepath.display());
+                let errors = parse_errors(&thepath);
+                assert!(!errors.is_empty());
+            }
+        }
+
+        #[test]
+        fn test_valid_blocks() {
+            assert_all_parse("test/blocks/valid");
+        }
+
+        #[test]
+        fn test_invalid_blocks() {
+            assert_all_rejected("test/blocks/invalid");
+        }
+
+        #[test]
+        fn test_valid_loops() {
+            assert_all_parse("test/loops/valid");
+        }
+
+        #[test]
+        fn test_invalid_loops() {
+            assert_all_rejected("test/loops/invalid");
+        }
+
+        #[test]
+        fn test_valid_functions() {
+            assert_all_parse("test/functions/valid");
+        }
+
+        #[test]
+        fn test_invalid_functions() {
+            assert_all_rejected("test/functions/invalid");
+        }
+
+        #[test]
+        fn test_valid_globals() {
+            assert_all_parse("test/globals/valid");
+        }
+
+        #[test]
+        fn test_invalid_globals() {
+            assert_all_rejected("test/globals/invalid");
+        }
+
+        #[test]
+        fn test_valid_pointers() {
+            assert_all_parse("test/pointers/valid");
+        }
+
+        #[test]
+        fn test_invalid_pointers() {
+            assert_all_rejected("test/pointers/invalid");
+        }
+
+        #[test]
+        fn test_valid_arrays() {
+            assert_all_parse("test/arrays/valid");
+        }
+
+        #[test]
+        fn test_invalid_arrays() {
+            assert_all_rejected("test/arrays/invalid");
+        }
+
+        #[test]
+        fn test_valid_structs() {
+            assert_all_parse("test/structs/valid");
+            assert_all_parse("test/structs/layout");
+        }
+
+        #[test]
+        fn test_invalid_structs() {
+            assert_all_rejected("test/structs/invalid");
+        }
+
+        #[test]
+        fn test_valid_enums() {
+            assert_all_parse("test/enums/valid");
+        }
+
+        #[test]
+        fn test_invalid_enums() {
+            assert_all_rejected("test/enums/invalid");
+        }
+
+        #[test]
+        fn test_valid_typedefs() {
+            assert_all_parse("test/typedefs/valid");
+        }
+
+        #[test]
+        fn test_invalid_typedefs() {
+            assert_all_rejected("test/typedefs/invalid");
+        }
+
+        #[test]
+        fn test_valid_static_assert() {
+            assert_all_parse("test/static_assert/valid");
+        }
+
+        #[test]
+        fn test_invalid_static_assert() {
+            assert_all_rejected("test/static_assert/invalid");
+        }
+
+        // A failed static assertion reports its message.
+        #[test]
+        fn test_static_assert_message() {
+            assert_eq!(
+                parse_errors(Path::new("test/static_assert/invalid/failed.c")),
+                vec!["Static assertion failed: \"int is 8 bytes\""]
+            );
+            assert_eq!(
+                parse_errors(Path::new("test/static_assert/invalid/not_constant.c")),
+                vec!["Static assertion expression is not an integer constant"]
+            );
+        }
+
+        #[test]
+        fn test_valid_integers() {
+            assert_all_parse("test/integers/valid");
+        }
+
+        #[test]
+        fn test_invalid_integers() {
+            assert_all_rejected("test/integers/invalid");
+        }
+
+        #[test]
+        fn test_valid_floats() {
+            assert_all_parse("test/floats/valid");
+        }
+
+        #[test]
+        fn test_invalid_floats() {
+            assert_all_rejected("test/floats/invalid");
+        }
+
+        #[test]
+        fn test_valid_sizeof() {
+            assert_all_parse("test/sizeof/valid");
+        }
+
+        #[test]
+        fn test_invalid_sizeof() {
+            assert_all_rejected("test/sizeof/invalid");
+        }
+
+        #[test]
+        fn test_valid_casts() {
+            assert_all_parse("test/casts/valid");
+        }
+
+        #[test]
+        fn test_invalid_casts() {
+            assert_all_rejected("test/casts/invalid");
+        }
+
+        #[test]
+        fn test_valid_function_pointers() {
+            assert_all_parse("test/function_pointers/valid");
+        }
+
+        #[test]
+        fn test_invalid_function_pointers() {
+            assert_all_rejected("test/function_pointers/invalid");
+            // A function declared with `()` takes no parameters, and is
+            // named as one declared with `(void)`.
+            assert_eq!(
+                parse_errors(Path::new(
+                    "test/function_pointers/invalid/function_arithmetic.c"
+                )),
+                vec![
+                    "Incompatible types: cannot convert 'int' to 'int (*)(void)'",
+                    "Invalid operands to binary + ('int (*)(void)' and 'int')",
+                ]
+            );
+        }
+
+        #[test]
+        fn test_valid_goto() {
+            assert_all_parse("test/goto/valid");
+        }
+
+        #[test]
+        fn test_invalid_goto() {
+            assert_all_rejected("test/goto/invalid");
+        }
+
+        #[test]
+        fn test_valid_switch() {
+            assert_all_parse("test/switch/valid");
+        }
+
+        #[test]
+        fn test_invalid_switch() {
+            assert_all_rejected("test/switch/invalid");
+        }
+
+        #[test]
+        fn test_valid_variadic() {
+            assert_all_parse("test/variadic/valid");
+        }
+
+        #[test]
+        fn test_invalid_variadic() {
+            assert_all_rejected("test/variadic/invalid");
+        }
+
+        #[test]
+        fn test_valid_qualifiers() {
+            assert_all_parse("test/qualifiers/valid");
+        }
+
+        #[test]
+        fn test_invalid_qualifiers() {
+            assert_all_rejected("test/qualifiers/invalid");
+        }
+
+        #[test]
+        fn test_valid_sema() {
+            assert_all_parse("test/sema/valid");
+        }
+
+        #[test]
+        fn test_invalid_sema() {
+            assert_all_rejected("test/sema/invalid");
+        }
+
+        #[test]
+        fn test_valid_warnings() {
+            assert_all_parse("test/warnings/valid");
+        }
+
+        #[test]
+        fn test_discarded_qualifier_warnings() {
+            let (errors, warnings) =
+                parse_diagnostics(Path::new("test/qualifiers/valid/discards.c"));
+            assert!(errors.is_empty(), "{:?}", errors);
+            assert_eq!(
+                warnings,
+                vec![
+                    "Converting 'const char *' to 'char *' discards qualifiers",
+                    "Converting 'volatile int *' to 'int *' discards qualifiers",
+                    "Converting 'const char *' to 'void *' discards qualifiers",
+                    "Converting 'const void *' to 'char *' discards qualifiers",
+                    "Converting 'const char *' to 'char *' discards qualifiers",
+                ]
+            );
+        }
+
+        #[test]
+        fn test_invalid_recovery() {
+            assert_all_rejected("test/recovery/invalid");
+        }
+
+        // After a syntax error the parser skips to the end of the statement,
+        // declaration or block and carries on, so each error is reported once.
+        #[test]
+        fn test_recovery_reports_every_error() {
+            let errors = parse_errors(Path::new("test/recovery/invalid/several_errors.c"));
+            assert_eq!(
+                errors,
+                vec![
+                    "Unexpected ';': expected expression",
+                    "Unexpected ';': expected expression",
+                    "Unexpected ';': expected member name",
+                    "Unexpected '{': expected type specifier",
+                    "Unexpected identifier 'y': expected semi-colon",
+                    "Unexpected '}': expected semi-colon",
+                ]
+            );
+            let errors = parse_errors(Path::new("test/recovery/invalid/error_after_recovery.c"));
+            assert_eq!(
+                errors,
+                vec![
+                    "Unexpected integer constant '2': expected semi-colon",
+                    "Use of undeclared identifier 'undeclared'",
+                ]
+            );
+        }
+
+        // A declaration on the line after a missing semi-colon is still
+        // declared, and the body of a function with a broken parameter list
+        // is still parsed, so errors are neither invented nor lost.
+        #[test]
+        fn test_recovery_at_declarations() {
+            let path = Path::new("test/recovery/invalid/missing_semicolon.c");
+            assert_eq!(
+                parse_errors(path),
+                vec!["Unexpected 'int': expected semi-colon"]
+            );
+            assert_eq!(error_positions(path), vec![(2, 14)]);
+            let path = Path::new("test/recovery/invalid/broken_parameters.c");
+            assert_eq!(
+                parse_errors(path),
+                vec![
+                    "Unexpected '{': expected type specifier",
+                    "Unexpected ';': expected expression",
+                ]
+            );
+            assert_eq!(error_positions(path), vec![(1, 11), (2, 13)]);
+        }
+
+        // The braces of an initializer cut short by an error are skipped as a
+        // whole, and nothing more is said about a statement or declaration
+        // after a syntax error in it: not that its variable is unused, nor
+        // anything about the type of an undeclared function's result.
+        #[test]
+        fn test_recovery_has_no_follow_ups() {
+            let diagnostics =
+                parse_all_diagnostics(Path::new("test/recovery/invalid/no_follow_ups.c"));
+            let messages: Vec<(u32, &str)> = diagnostics
+                .iter()
+                .map(|d| (d.span.unwrap().line, d.message.as_str()))
+                .collect();
+            assert_eq!(
+                messages,
+                vec![
+                    (2, "Unused variable 'unused'"),
+                    (3, "Unexpected '{': expected expression"),
+                    (4, "Unexpected ';': expected close paren"),
+                    (5, "Call to undeclared function 'undeclared'"),
+                ]
+            );
+        }
+
+        // A character that cannot start a token is reported where it is and
+        // otherwise ignored.
+        #[test]
+        fn test_stray_characters() {
+            let path = Path::new("test/recovery/invalid/stray_characters.c");
+            assert_eq!(
+                parse_errors(path),
+                vec![
+                    "Stray '@' in program",
+                    "Stray '#' in program",
+                    "Stray byte that is not valid UTF-8 in program",
+                ]
+            );
+            assert_eq!(error_positions(path), vec![(3, 11), (4, 14), (7, 10)]);
+        }
+
+        // An array whose size does not fit in an int is rejected rather than
+        // laid out, and so is a frame its locals together would overflow.
+        #[test]
+        fn test_arrays_too_large() {
+            let path = Path::new("test/arrays/invalid/too_large.c");
+            assert_eq!(parse_errors(path), vec!["Array is too large"; 4]);
+            assert_eq!(
+                error_positions(path),
+                vec![(1, 22), (2, 11), (5, 16), (7, 37)]
+            );
+            assert_eq!(
+                parse_errors(Path::new("test/arrays/invalid/frame_too_large.c")),
+                vec!["Local variables of 'main' are too large for a stack frame"]
+            );
+        }
+
+        // A struct redefined inside its own definition, or too large to lay
+        // out, is an error rather than a panic.
+        #[test]
+        fn test_struct_definition_errors() {
+            let path = Path::new("test/structs/invalid/nested_redefinition.c");
+            assert_eq!(
+                parse_errors(path),
+                vec!["Nested redefinition of 'struct s'"]
+            );
+            assert_eq!(error_positions(path), vec![(3, 12)]);
+            assert_eq!(
+                parse_errors(Path::new("test/structs/invalid/too_large.c")),
+                vec!["'struct huge' is too large"]
+            );
+        }
+
+        // A bit-field needs an integer type and a constant width that fits
+        // in it; one that has both is still unsupported.
+        #[test]
+        fn test_bit_field_widths() {
+            assert_eq!(
+                parse_errors(Path::new("test/structs/invalid/bit_field_widths.c")),
+                vec![
+                    "Width of bit-field 'negative' is negative (-1)",
+                    "Zero width for bit-field 'zero'",
+                    "Bit-fields are not supported",
+                    "Width of bit-field 'wide' (9 bits) exceeds the width of its type (8 bits)",
+                    "Invalid type 'float' for bit-field 'real'",
+                    "Width of bit-field 'varying' is not an integer constant",
+                    "Bit-field 'whole' is not supported",
+                ]
+            );
+        }
+
+        // A syntax error is reported at the token that does not fit, or just
+        // past the end of the line when the token missing there is what is
+        // expected; other errors at the last token read.
+        #[test]
+        fn test_error_positions() {
+            assert_eq!(
+                error_positions(Path::new("test/recovery/invalid/several_errors.c")),
+                vec![(2, 20), (3, 17), (6, 27), (8, 12), (13, 14), (15, 22)]
+            );
+            assert_eq!(
+                error_positions(Path::new("test/recovery/invalid/error_after_recovery.c")),
+                vec![(2, 15), (7, 12)]
+            );
+            assert_eq!(
+                error_positions(Path::new("test/recovery/invalid/truncated_function.c")),
+                vec![(1, 10)]
+            );
+            assert_eq!(
+                error_positions(Path::new("test/recovery/invalid/misplaced_tokens.c")),
+                vec![(3, 9), (4, 12)]
+            );
+        }
+
+        // Diagnostics carry a code for the kind of problem and point back to
+        // related places in the source.
+        #[test]
+        fn test_diagnostic_codes_and_labels() {
+            let diagnostics =
+                parse_all_diagnostics(Path::new("test/recovery/invalid/unterminated_block.c"));
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].code, Code::Syntax);
+            assert_eq!(diagnostics[0].labels.len(), 1);
+            assert_eq!(
+                diagnostics[0].labels[0].span,
+                Span {
+                    line: 1,
+                    start: 16,
+                    end: 17
+                }
+            );
+            assert_eq!(diagnostics[0].labels[0].message, "to match this '{'");
+
+            let diagnostics =
+                parse_all_diagnostics(Path::new("test/goto/invalid/duplicate_label.c"));
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].code, Code::Redefinition);
+            assert_eq!(diagnostics[0].span.unwrap().line, 5);
+            assert_eq!(diagnostics[0].labels[0].span.line, 3);
+
+            let diagnostics =
+                parse_all_diagnostics(Path::new("test/goto/invalid/label_in_other_function.c"));
+            // The statement after the goto is also reported as unreachable.
+            let diagnostics: Vec<&Diagnostic> = diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .collect();
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].code, Code::Undeclared);
+            assert_eq!(diagnostics[0].span.unwrap().line, 7);
+
+            // A declaration that conflicts with an earlier one is reported at
+            // its name and points back at the earlier one.
+            let labels = |path: &str| -> Vec<(u32, u32)> {
+                let diagnostics: Vec<Diagnostic> = parse_all_diagnostics(Path::new(path))
+                    .into_iter()
+                    .filter(|d| d.severity == Severity::Error)
+                    .collect();
+                assert_eq!(diagnostics.len(), 1);
+                assert_eq!(diagnostics[0].labels.len(), 1);
+                assert_eq!(
+                    diagnostics[0].labels[0].message,
+                    "previous declaration is here"
+                );
+                let (span, previous) =
+                    (diagnostics[0].span.unwrap(), diagnostics[0].labels[0].span);
+                return vec![(span.line, span.start), (previous.line, previous.start)];
+            };
+            assert_eq!(
+                labels("test/blocks/invalid/redeclare_in_list.c"),
+                vec![(2, 16), (2, 9)]
+            );
+            assert_eq!(
+                labels("test/functions/invalid/redefinition.c"),
+                vec![(5, 5), (1, 5)]
+            );
+            assert_eq!(
+                labels("test/functions/invalid/conflicting_prototypes.c"),
+                vec![(2, 5), (1, 5)]
+            );
+            // The conflicting declaration is the file scope one, not the local
+            // that hides it.
+            assert_eq!(
+                labels("test/globals/invalid/extern_conflicts_with_global.c"),
+                vec![(6, 21), (1, 5)]
+            );
+
+            let diagnostics = parse_all_diagnostics(Path::new("test/qualifiers/valid/discards.c"));
+            assert!(diagnostics
+                .iter()
+                .all(|d| d.severity == Severity::Warning && d.code == Code::DiscardedQualifiers));
+        }
+
+        // Where the grammar requires a constant, a division by zero is an
+        // error at the operator.
+        #[test]
+        fn test_constant_division_by_zero() {
+            assert_eq!(
+                error_positions(Path::new(
+                    "test/integers/invalid/array_size_division_by_zero.c"
+                )),
+                vec![(1, 14)]
+            );
+            assert_eq!(
+                error_positions(Path::new("test/integers/invalid/case_division_by_zero.c")),
+                vec![(4, 12)]
+            );
+        }
+
+        #[test]
+        fn test_literal_too_large() {
+            let diagnostics =
+                parse_all_diagnostics(Path::new("test/integers/invalid/literal_too_large.c"));
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].code, Code::OutOfRange);
+            assert_eq!(
+                diagnostics[0].message,
+                "Integer literal '99999999999999999999' is too large for any integer type"
+            );
+            assert_eq!(diagnostics[0].span.unwrap().start, 16);
+        }
+
+        struct ConstantCounter {
+            count: usize,
+        }
+
+        impl<'n> super::Visitor<'n> for ConstantCounter {
+            fn visit(&mut self, node: &'n super::AstNode) {
+                if node.node_type == super::AstNodeType::Constant {
+                    self.count += 1;
+                }
+                super::walk(self, node);
+            }
+        }
+
+        // Negates every int constant, replacing the node as a whole.
+        struct Negator;
+
+        impl super::MutVisitor for Negator {
+            fn visit_mut(&mut self, node: &mut super::AstNode) {
+                if node.node_type == super::AstNodeType::Constant && node.ty == Type::Int {
+                    let mut negated = super::AstNode::new_typed(
+                        super::AstNodeType::UnaryOp,
+                        String::from("-"),
+                        Type::Int,
+                    );
+                    negated.add_child(::std::mem::replace(
+                        node,
+                        super::AstNode::new(super::AstNodeType::NullStatement),
+                    ));
+                    *node = negated;
+                    return;
+                }
+                super::walk_mut(self, node);
+            }
+        }
+
+        #[test]
+        fn test_visitors() {
+            let token_list = lexer::lex(Path::new("test/sema/valid/conversions.c"));
+            let mut program = super::parse(&mut token_list.iter(), &|x: Diagnostic| {
+                assert_eq!(x.severity, Severity::Warning, "{}", x.message);
+            });
+            let constants = super::find_all(&program, super::AstNodeType::Constant);
+            let ints = constants.iter().filter(|c| c.ty == Type::Int).count();
+            let mut counter = ConstantCounter { count: 0 };
+            super::Visitor::visit(&mut counter, &program);
+            assert_eq!(counter.count, constants.len());
+
+            super::MutVisitor::visit_mut(&mut Negator, &mut program);
+            let negations = super::find_all(&program, super::AstNodeType::UnaryOp);
+            assert_eq!(negations.len(), ints);
+            assert!(negations
+                .iter()
+                .all(|n| n.children[0].node_type == super::AstNodeType::Constant));
+        }
     }
 }
@@ -2,18 +2,224 @@
 
 pub mod codegen {
 
+    use std::collections::HashMap;
     use std::path::Path;
     use fileutils::write_lines;
     use ast::ast::*;
+    use symtab::symtab::Linkage;
+    use types::types::Type;
 
     pub struct CodeGenState {
         current_node: Option<Box<AstNode>>,
+        label_counter: u32,
+        stack_offsets: HashMap<String, i32>,
+        // Break targets of the enclosing loops and switches and continue
+        // targets of the enclosing loops, innermost last.
+        break_labels: Vec<String>,
+        continue_labels: Vec<String>,
+        // Label prefixes of the enclosing switch statements, innermost last.
+        switch_labels: Vec<String>,
+        // Assembly labels of the goto labels in the current function.
+        goto_labels: HashMap<String, String>,
+        // Number of 8 byte values pushed since the prologue, used to keep %rsp
+        // 16 byte aligned at call instructions.
+        stack_depth: usize,
+        // Set while generating a variadic function.
+        variadic_frame: Option<VariadicFrame>,
+    }
+
+    // Where a variadic function finds the arguments that follow its named
+    // parameters, in the form va_start stores in a va_list.
+    #[derive(Clone, Copy)]
+    struct VariadicFrame {
+        // Frame offset of the register save area, which holds the six
+        // integer argument registers followed by the eight SSE registers.
+        save_area: i32,
+        // Offsets into the save area of the first unnamed argument passed in
+        // an integer register and in an SSE register.
+        gp_offset: i32,
+        fp_offset: i32,
+        // Offset from %rbp of the first unnamed argument passed on the stack.
+        overflow_offset: i32,
+    }
+
+    // System V integer argument registers, in order.
+    static ARGUMENT_REGISTERS: [&str; 6] = ["%rdi", "%rsi", "%rdx", "%rcx", "%r8", "%r9"];
+    static ARGUMENT_REGISTERS_32: [&str; 6] = ["%edi", "%esi", "%edx", "%ecx", "%r8d", "%r9d"];
+    static ARGUMENT_REGISTERS_16: [&str; 6] = ["%di", "%si", "%dx", "%cx", "%r8w", "%r9w"];
+    static ARGUMENT_REGISTERS_8: [&str; 6] = ["%dil", "%sil", "%dl", "%cl", "%r8b", "%r9b"];
+
+    // Symbols get a leading underscore in the Mach-O object format.
+    fn symbol_name(name: &str) -> String {
+        if cfg!(target_os = "macos") {
+            return format!("_{}", name);
+        }
+        return String::from(name);
+    }
+
+    // The accumulator and scratch registers sized to hold a value of the type.
+    // Values narrower than int are kept sign or zero extended to 32 bits, so
+    // they are operated on as 32 bit values.
+    fn ax(ty: &Type) -> &'static str {
+        if ty.size() == 8 {
+            return "%rax";
+        }
+        return "%eax";
+    }
+
+    fn cx(ty: &Type) -> &'static str {
+        if ty.size() == 8 {
+            return "%rcx";
+        }
+        return "%ecx";
+    }
+
+    // Largest number of entries in a switch jump table.
+    const MAX_JUMP_TABLE: i128 = 1024;
+
+    // Finds the case labels belonging to a switch body as (low, high, index)
+    // triples, leaving out those of nested switch statements. The values are
+    // ordered the way the controlling expression's type orders them.
+    struct CaseCollector {
+        cases: Vec<(i128, i128, String)>,
+        has_default: bool,
+    }
+
+    impl<'n> Visitor<'n> for CaseCollector {
+        fn visit(&mut self, node: &'n AstNode) {
+            match node.node_type {
+                AstNodeType::Switch => return,
+                AstNodeType::Case => {
+                    let ty = &node.children[0].ty;
+                    let low = ty.order_key(node.children[0].data.parse().unwrap());
+                    let high = ty.order_key(node.children[1].data.parse().unwrap());
+                    self.cases.push((low, high, node.data.clone()));
+                }
+                AstNodeType::Default => self.has_default = true,
+                _ => {}
+            }
+            walk(self, node);
+        }
+    }
+
+    // Floating values are kept in %xmm0 instead of the accumulator, and the
+    // SSE instructions operating on them are suffixed by their precision.
+    fn sse(ty: &Type) -> &'static str {
+        if *ty.unqualified() == Type::Float {
+            return "ss";
+        }
+        return "sd";
+    }
+
+    // Where an argument is passed: in the next free register of its class,
+    // or on the stack once those run out.
+    #[derive(Clone, Copy, PartialEq)]
+    enum ArgumentLocation {
+        Integer(usize),
+        Sse(usize),
+        Stack,
+    }
+
+    fn classify_arguments(types: &[&Type]) -> Vec<ArgumentLocation> {
+        let (mut integers, mut vectors) = (0, 0);
+        let mut locations = Vec::new();
+        for ty in types.iter() {
+            if ty.is_floating() && vectors < 8 {
+                locations.push(ArgumentLocation::Sse(vectors));
+                vectors += 1;
+            } else if !ty.is_floating() && integers < ARGUMENT_REGISTERS.len() {
+                locations.push(ArgumentLocation::Integer(integers));
+                integers += 1;
+            } else {
+                locations.push(ArgumentLocation::Stack);
+            }
+        }
+        return locations;
+    }
+
+    // The part of the accumulator exactly as wide as the type, for storing a
+    // value to memory.
+    fn sized_ax(ty: &Type) -> &'static str {
+        match ty.size() {
+            1 => return "%al",
+            2 => return "%ax",
+            4 => return "%eax",
+            _ => return "%rax",
+        }
+    }
+
+    fn argument_register(index: usize, ty: &Type) -> &'static str {
+        match ty.size() {
+            1 => return ARGUMENT_REGISTERS_8[index],
+            2 => return ARGUMENT_REGISTERS_16[index],
+            4 => return ARGUMENT_REGISTERS_32[index],
+            _ => return ARGUMENT_REGISTERS[index],
+        }
+    }
+
+    // Loads a value of the type from memory into the accumulator, extending
+    // narrow values to 32 bits according to their signedness.
+    fn load(ty: &Type, source: &str) -> String {
+        if ty.is_floating() {
+            return format!("mov{} {}, %xmm0", sse(ty), source);
+        }
+        let instruction = match (ty.size(), ty.is_signed()) {
+            (1, true) => "movsbl",
+            (1, false) => "movzbl",
+            (2, true) => "movswl",
+            (2, false) => "movzwl",
+            (4, _) => "movl",
+            _ => "movq",
+        };
+        return format!("{} {}, {}", instruction, source, ax(ty));
+    }
+
+    fn store(ty: &Type, destination: &str) -> String {
+        if ty.is_floating() {
+            return format!("mov{} %xmm0, {}", sse(ty), destination);
+        }
+        return format!("mov {}, {}", sized_ax(ty), destination);
+    }
+
+    fn read_only_section() -> &'static str {
+        if cfg!(target_os = "macos") {
+            return ".const";
+        }
+        return ".section .rodata";
+    }
+
+    // Whether an immediate operand of a 64 bit instruction can hold the
+    // value, which it sign extends from 32 bits.
+    fn fits_immediate(value: i128) -> bool {
+        return value >= i128::from(i32::MIN) && value <= i128::from(i32::MAX);
+    }
+
+    // Loads an integer constant into the accumulator. A 64 bit value that a
+    // sign extended 32 bit immediate cannot hold needs movabs.
+    fn generate_integer_constant(ty: &Type, value: i64, emitstack: &mut Vec<String>) {
+        if ty.size() == 8 && !fits_immediate(i128::from(value)) {
+            emitstack.push(format!("movabs ${}, %rax", value));
+        } else {
+            emitstack.push(format!("mov ${}, {}", value, ax(ty)));
+        }
+    }
+
+    fn align_to(n: i32, align: i32) -> i32 {
+        return (n + align - 1) / align * align;
     }
 
     impl CodeGenState {
         pub fn new(root_node: Box<AstNode>) -> CodeGenState {
             let state = CodeGenState {
                 current_node: Some(root_node),
+                label_counter: 0,
+                stack_offsets: HashMap::new(),
+                break_labels: Vec::new(),
+                continue_labels: Vec::new(),
+                switch_labels: Vec::new(),
+                goto_labels: HashMap::new(),
+                stack_depth: 0,
+                variadic_frame: None,
             };
             return state;
         }
@@ -22,20 +228,16 @@ pub mod codegen {
         where
             P: AsRef<Path>,
         {
-            let mut emit_stack: &mut Vec<String> = &mut Vec::new();
-            self.generate_code(&mut emit_stack);
-            write_lines(filename, &emit_stack);
+            let emit_stack: &mut Vec<String> = &mut Vec::new();
+            self.generate_code(emit_stack);
+            write_lines(filename, emit_stack);
         }
 
         fn generate_code(&mut self, emit_stack: &mut Vec<String>) {
-            let arg_stack: &mut Vec<String> = &mut Vec::new();
-
-            match self.current_node {
-                Some(ref valid) => {
-                    self.generate_code_recurse(valid, arg_stack, emit_stack);
-                    // for i in &mut emit_stack.iter().rev() {
-                    //     println!("EMMIT:{}", i);
-                    // }
+            match self.current_node.take() {
+                Some(valid) => {
+                    self.generate_code_recurse(&valid, emit_stack);
+                    self.current_node = Some(valid);
                 }
                 None => {
                     unreachable!();
@@ -43,69 +245,1508 @@ pub mod codegen {
             }
         }
 
-        fn generate_code_recurse(
-            &self,
-            node: &AstNode,
-            argstack: &mut Vec<String>,
+        fn new_label(&mut self, kind: &str) -> String {
+            self.label_counter += 1;
+            return format!(".L{}{}", kind, self.label_counter);
+        }
+
+        // Labels are scoped to their function, so each one is given a fresh
+        // assembly label the first time the function mentions it.
+        fn goto_label(&mut self, name: &str) -> String {
+            if let Some(label) = self.goto_labels.get(name) {
+                return label.clone();
+            }
+            let label = self.new_label(&format!("label_{}_", name));
+            self.goto_labels.insert(String::from(name), label.clone());
+            return label;
+        }
+
+        // Gives every declaration in the function body its own slot below %rbp.
+        // Shadowed variables have distinct unique names so they never share a
+        // slot.
+        fn allocate_locals(&mut self, node: &AstNode, frame_size: &mut i32) {
+            for declaration in find_all(node, AstNodeType::Declaration) {
+                self.allocate_slot(&declaration.data, &declaration.ty, frame_size);
+            }
+        }
+
+        fn allocate_slot(&mut self, name: &str, ty: &Type, frame_size: &mut i32) {
+            *frame_size = align_to(*frame_size + ty.size(), ty.align());
+            self.stack_offsets.insert(String::from(name), -*frame_size);
+        }
+
+        // Locals live in the frame; anything else has static storage and is
+        // addressed relative to %rip so the output stays position independent.
+        fn variable_location(&self, name: &str) -> String {
+            match self.stack_offsets.get(name) {
+                Some(offset) => return format!("{}(%rbp)", offset),
+                None => return format!("{}(%rip)", symbol_name(name)),
+            }
+        }
+
+        // Emits a test of the value in the accumulator against zero, setting
+        // the flags for a following conditional jump or set instruction.
+        // A floating value is first turned into 0 or 1 in the accumulator,
+        // counting NaN as nonzero.
+        fn compare_to_zero(&self, ty: &Type, emitstack: &mut Vec<String>) {
+            if ty.is_floating() {
+                emitstack.push(String::from("xorps %xmm1, %xmm1"));
+                emitstack.push(format!("ucomi{} %xmm1, %xmm0", sse(ty)));
+                emitstack.push(String::from("setne %al"));
+                emitstack.push(String::from("setp %cl"));
+                emitstack.push(String::from("or %cl, %al"));
+                emitstack.push(String::from("movzbl %al, %eax"));
+                emitstack.push(String::from("cmp $0, %eax"));
+                return;
+            }
+            emitstack.push(format!("cmp $0, {}", ax(ty)));
+        }
+
+        // Computes the address of an lvalue into %rax.
+        fn generate_address(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            match node.node_type {
+                // A function may be defined in a shared library, so its
+                // address is loaded from the global offset table.
+                AstNodeType::Variable if node.ty.is_function() => {
+                    emitstack.push(format!(
+                        "mov {}@GOTPCREL(%rip), %rax",
+                        symbol_name(&node.data)
+                    ));
+                }
+                AstNodeType::Variable => {
+                    let location = self.variable_location(&node.data);
+                    emitstack.push(format!("lea {}, %rax", location));
+                }
+                AstNodeType::Dereference => {
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                }
+                AstNodeType::Member => {
+                    let object = &node.children[0];
+                    self.generate_code_recurse(object, emitstack);
+                    let offset = match object.ty {
+                        Type::Struct(ref aggregate) => aggregate.member(&node.data).unwrap().1,
+                        _ => unreachable!(),
+                    };
+                    if offset != 0 {
+                        emitstack.push(format!("add ${}, %rax", offset));
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        // Loads the value of the given type from the address in %rax. The
+        // value of an array or struct is its address, so nothing is loaded.
+        fn generate_load(&self, ty: &Type, emitstack: &mut Vec<String>) {
+            if !ty.is_aggregate() {
+                emitstack.push(load(ty, "(%rax)"));
+            }
+        }
+
+        // Copies an object of the given size from the address in %rcx to the
+        // address in %rax, using the widest moves that fit.
+        fn generate_copy(&self, size: i32, emitstack: &mut Vec<String>) {
+            let mut offset = 0;
+            for &(width, register) in [(8, "%rdx"), (4, "%edx"), (2, "%dx"), (1, "%dl")].iter() {
+                while size - offset >= width {
+                    emitstack.push(format!("mov {}(%rcx), {}", offset, register));
+                    emitstack.push(format!("mov {}, {}(%rax)", register, offset));
+                    offset += width;
+                }
+            }
+        }
+
+        fn generate_code_recurse(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            match node.node_type {
+                AstNodeType::Program => {
+                    for child in node.children.iter() {
+                        self.generate_code_recurse(child, emitstack);
+                    }
+                }
+                AstNodeType::Function => {
+                    self.generate_function(node, emitstack);
+                }
+                AstNodeType::FunctionPrototype => {}
+                AstNodeType::StaticVariable => {
+                    self.generate_static_variable(node, emitstack);
+                }
+                AstNodeType::Parameter => {}
+                AstNodeType::Block => {
+                    for child in node.children.iter() {
+                        self.generate_code_recurse(child, emitstack);
+                    }
+                }
+                AstNodeType::Declaration => {
+                    if let Some(init) = node.children.first() {
+                        let offset = self.stack_offsets[&node.data];
+                        self.generate_initializer(init, &node.ty, offset, emitstack);
+                    }
+                }
+                AstNodeType::Return => {
+                    if let Some(value) = node.children.first() {
+                        self.generate_code_recurse(value, emitstack);
+                    }
+                    self.generate_epilogue(emitstack);
+                }
+                AstNodeType::ExpressionStatement => {
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                }
+                AstNodeType::NullStatement => {}
+                AstNodeType::If => {
+                    let else_label = self.new_label("else");
+                    let end_label = self.new_label("if_end");
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                    self.compare_to_zero(&node.children[0].ty, emitstack);
+                    emitstack.push(format!("je {}", else_label));
+                    self.generate_code_recurse(&node.children[1], emitstack);
+                    emitstack.push(format!("jmp {}", end_label));
+                    emitstack.push(format!("{}:", else_label));
+                    if let Some(else_branch) = node.children.get(2) {
+                        self.generate_code_recurse(else_branch, emitstack);
+                    }
+                    emitstack.push(format!("{}:", end_label));
+                }
+                AstNodeType::While => {
+                    let break_label = self.new_label("break");
+                    let continue_label = self.new_label("continue");
+                    emitstack.push(format!("{}:", continue_label));
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                    self.compare_to_zero(&node.children[0].ty, emitstack);
+                    emitstack.push(format!("je {}", break_label));
+                    self.generate_loop_body(
+                        &node.children[1],
+                        &break_label,
+                        &continue_label,
+                        emitstack,
+                    );
+                    emitstack.push(format!("jmp {}", continue_label));
+                    emitstack.push(format!("{}:", break_label));
+                }
+                AstNodeType::DoWhile => {
+                    let start_label = self.new_label("do");
+                    let break_label = self.new_label("break");
+                    let continue_label = self.new_label("continue");
+                    emitstack.push(format!("{}:", start_label));
+                    self.generate_loop_body(
+                        &node.children[0],
+                        &break_label,
+                        &continue_label,
+                        emitstack,
+                    );
+                    emitstack.push(format!("{}:", continue_label));
+                    self.generate_code_recurse(&node.children[1], emitstack);
+                    self.compare_to_zero(&node.children[1].ty, emitstack);
+                    emitstack.push(format!("jne {}", start_label));
+                    emitstack.push(format!("{}:", break_label));
+                }
+                AstNodeType::For => {
+                    let start_label = self.new_label("for");
+                    let break_label = self.new_label("break");
+                    let continue_label = self.new_label("continue");
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                    emitstack.push(format!("{}:", start_label));
+                    self.generate_code_recurse(&node.children[1], emitstack);
+                    self.compare_to_zero(&node.children[1].ty, emitstack);
+                    emitstack.push(format!("je {}", break_label));
+                    self.generate_loop_body(
+                        &node.children[3],
+                        &break_label,
+                        &continue_label,
+                        emitstack,
+                    );
+                    emitstack.push(format!("{}:", continue_label));
+                    self.generate_code_recurse(&node.children[2], emitstack);
+                    emitstack.push(format!("jmp {}", start_label));
+                    emitstack.push(format!("{}:", break_label));
+                }
+                AstNodeType::Break => {
+                    let label = self.break_labels.last().expect("break outside of a loop");
+                    emitstack.push(format!("jmp {}", label));
+                }
+                AstNodeType::Continue => {
+                    let label = self
+                        .continue_labels
+                        .last()
+                        .expect("continue outside of a loop");
+                    emitstack.push(format!("jmp {}", label));
+                }
+                AstNodeType::Switch => {
+                    self.generate_switch(node, emitstack);
+                }
+                AstNodeType::Case => {
+                    let label = format!("{}_case{}", self.switch_labels.last().unwrap(), node.data);
+                    emitstack.push(format!("{}:", label));
+                    self.generate_code_recurse(&node.children[2], emitstack);
+                }
+                AstNodeType::Label => {
+                    let label = self.goto_label(&node.data);
+                    emitstack.push(format!("{}:", label));
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                }
+                AstNodeType::Goto => {
+                    let label = self.goto_label(&node.data);
+                    emitstack.push(format!("jmp {}", label));
+                }
+                AstNodeType::Default => {
+                    let label = format!("{}_default", self.switch_labels.last().unwrap());
+                    emitstack.push(format!("{}:", label));
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                }
+                AstNodeType::Constant if node.ty.is_floating() => {
+                    let value: f64 = node.data.parse().unwrap();
+                    self.generate_floating_constant(&node.ty, value, emitstack);
+                }
+                AstNodeType::Constant => {
+                    let value: i64 = node.data.parse().unwrap();
+                    generate_integer_constant(&node.ty, value, emitstack);
+                }
+                AstNodeType::Variable => {
+                    let location = self.variable_location(&node.data);
+                    if node.ty.is_aggregate() {
+                        emitstack.push(format!("lea {}, %rax", location));
+                    } else {
+                        emitstack.push(load(&node.ty, &location));
+                    }
+                }
+                AstNodeType::Cast => {
+                    // A converted integer constant is loaded already converted.
+                    if let Some(value) = constant_value(node) {
+                        generate_integer_constant(&node.ty, value, emitstack);
+                    } else {
+                        let operand = &node.children[0];
+                        self.generate_code_recurse(operand, emitstack);
+                        self.generate_conversion(&operand.ty, &node.ty, emitstack);
+                    }
+                }
+                AstNodeType::AddressOf => {
+                    self.generate_address(&node.children[0], emitstack);
+                }
+                AstNodeType::Dereference => {
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                    self.generate_load(&node.ty, emitstack);
+                }
+                AstNodeType::Member => {
+                    self.generate_address(node, emitstack);
+                    self.generate_load(&node.ty, emitstack);
+                }
+                AstNodeType::Assign => {
+                    let target = &node.children[0];
+                    if node.ty.is_struct() {
+                        // Struct assignment copies the whole object and leaves
+                        // the target's address as the result.
+                        self.generate_code_recurse(&node.children[1], emitstack);
+                        self.push("%rax", emitstack);
+                        self.generate_address(target, emitstack);
+                        self.pop("%rcx", emitstack);
+                        self.generate_copy(node.ty.size(), emitstack);
+                    } else if target.node_type == AstNodeType::Variable {
+                        self.generate_code_recurse(&node.children[1], emitstack);
+                        let location = self.variable_location(&target.data);
+                        emitstack.push(store(&node.ty, &location));
+                    } else {
+                        self.generate_address(target, emitstack);
+                        self.push("%rax", emitstack);
+                        self.generate_code_recurse(&node.children[1], emitstack);
+                        self.pop("%rcx", emitstack);
+                        emitstack.push(store(&node.ty, "(%rcx)"));
+                    }
+                }
+                AstNodeType::UnaryOp => {
+                    let operand = &node.children[0];
+                    self.generate_code_recurse(operand, emitstack);
+                    match node.data.as_str() {
+                        // Negating a floating value flips its sign bit.
+                        "-" if node.ty == Type::Double => {
+                            emitstack.push(String::from("movq %xmm0, %rax"));
+                            emitstack.push(String::from("btc $63, %rax"));
+                            emitstack.push(String::from("movq %rax, %xmm0"));
+                        }
+                        "-" if node.ty == Type::Float => {
+                            emitstack.push(String::from("movd %xmm0, %eax"));
+                            emitstack.push(String::from("xor $0x80000000, %eax"));
+                            emitstack.push(String::from("movd %eax, %xmm0"));
+                        }
+                        "-" => emitstack.push(format!("neg {}", ax(&node.ty))),
+                        "~" => emitstack.push(format!("not {}", ax(&node.ty))),
+                        "!" => {
+                            self.compare_to_zero(&operand.ty, emitstack);
+                            emitstack.push(String::from("mov $0, %eax"));
+                            emitstack.push(String::from("sete %al"));
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                AstNodeType::BinaryOp => {
+                    self.generate_binary_op(node, emitstack);
+                }
+                AstNodeType::Call | AstNodeType::IndirectCall => {
+                    self.generate_call(node, emitstack);
+                }
+                AstNodeType::VaStart => {
+                    let frame = self
+                        .variadic_frame
+                        .expect("va_start outside variadic function");
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                    emitstack.push(format!("movl ${}, (%rax)", frame.gp_offset));
+                    emitstack.push(format!("movl ${}, 4(%rax)", frame.fp_offset));
+                    emitstack.push(format!("lea {}(%rbp), %rcx", frame.overflow_offset));
+                    emitstack.push(String::from("mov %rcx, 8(%rax)"));
+                    emitstack.push(format!("lea {}(%rbp), %rcx", frame.save_area));
+                    emitstack.push(String::from("mov %rcx, 16(%rax)"));
+                }
+                AstNodeType::VaArg => {
+                    self.generate_va_arg(node, emitstack);
+                }
+                AstNodeType::VaEnd => {
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                }
+                AstNodeType::VaCopy => {
+                    self.generate_code_recurse(&node.children[0], emitstack);
+                    self.push("%rax", emitstack);
+                    self.generate_code_recurse(&node.children[1], emitstack);
+                    emitstack.push(String::from("mov %rax, %rcx"));
+                    self.pop("%rax", emitstack);
+                    self.generate_copy(24, emitstack);
+                }
+                AstNodeType::InitializerList | AstNodeType::ZeroFill | AstNodeType::Identifier => {
+                    unreachable!()
+                }
+            }
+        }
+
+        // Stores an initializer into the object of the given type at the frame
+        // offset, element by element. Elements the initializer leaves out are
+        // set to zero.
+        fn generate_initializer(
+            &mut self,
+            init: &AstNode,
+            ty: &Type,
+            offset: i32,
             emitstack: &mut Vec<String>,
         ) {
-            match node.child {
-                Some(ref valid) => {
-                    self.generate_code_recurse(valid, argstack, emitstack);
+            if init.node_type == AstNodeType::InitializerList {
+                let elements: Vec<(Type, i32)> = match *ty.unqualified() {
+                    Type::Array(ref element, length) => (0..length)
+                        .map(|i| ((**element).clone(), element.size() * i as i32))
+                        .collect(),
+                    // Only the first member of a union is initialized; the
+                    // rest of its storage is cleared first.
+                    Type::Struct(ref aggregate) if aggregate.borrow().is_union => {
+                        self.generate_zero_fill(offset, ty.size(), emitstack);
+                        aggregate.members().into_iter().take(1).collect()
+                    }
+                    Type::Struct(ref aggregate) => aggregate.members(),
+                    _ => unreachable!(),
+                };
+                for (index, (element, element_offset)) in elements.iter().enumerate() {
+                    match init.children.get(index) {
+                        Some(child) => self.generate_initializer(
+                            child,
+                            element,
+                            offset + element_offset,
+                            emitstack,
+                        ),
+                        None => self.generate_zero_fill(
+                            offset + element_offset,
+                            element.size(),
+                            emitstack,
+                        ),
+                    }
                 }
-                None => {} //break recursion by not calling recurse, continuing execution
+                return;
+            }
+            self.generate_code_recurse(init, emitstack);
+            if ty.is_struct() {
+                emitstack.push(String::from("mov %rax, %rcx"));
+                emitstack.push(format!("lea {}(%rbp), %rax", offset));
+                self.generate_copy(ty.size(), emitstack);
+            } else {
+                emitstack.push(store(ty, &format!("{}(%rbp)", offset)));
             }
+        }
 
-            //println!("CODEGEN: {:?}", &node.node_type);
-            match node.node_type {
-                AstNodeType::Expression => {
-                    argstack.push(node.data.clone());
+        // Clears the given number of bytes of the frame starting at the offset.
+        fn generate_zero_fill(&self, offset: i32, size: i32, emitstack: &mut Vec<String>) {
+            let mut filled = 0;
+            for &(width, suffix) in [(8, "q"), (4, "l"), (2, "w"), (1, "b")].iter() {
+                while size - filled >= width {
+                    emitstack.push(format!("mov{} $0, {}(%rbp)", suffix, offset + filled));
+                    filled += width;
                 }
-                AstNodeType::Statement => {
-                    let ret_arg = argstack.pop();
-                    &mut emitstack.push(String::from("ret"));
-                    &mut emitstack.push(String::from(format!("mov ${}, %eax", ret_arg.unwrap())));
+            }
+        }
+
+        fn generate_function(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let mut frame_size = 0;
+            self.stack_offsets.clear();
+            self.goto_labels.clear();
+            self.stack_depth = 0;
+
+            // Parameters passed in registers are spilled to the frame. The
+            // rest were pushed by the caller and sit above the return address
+            // and saved %rbp.
+            let parameters: Vec<&AstNode> = node
+                .children
+                .iter()
+                .filter(|c| c.node_type == AstNodeType::Parameter)
+                .collect();
+            let types: Vec<&Type> = parameters.iter().map(|p| &p.ty).collect();
+            let locations = classify_arguments(&types);
+            let mut stack_index = 0;
+            for (parameter, location) in parameters.iter().zip(locations.iter()) {
+                if *location == ArgumentLocation::Stack {
+                    let offset = 16 + 8 * stack_index;
+                    self.stack_offsets.insert(parameter.data.clone(), offset);
+                    stack_index += 1;
+                } else {
+                    self.allocate_slot(&parameter.data, &parameter.ty, &mut frame_size);
                 }
-                AstNodeType::Function => {
-                    &mut emitstack.push(String::from("_main:"));
-                    &mut emitstack.push(String::from(".globl _main"));
+            }
+            self.allocate_locals(node, &mut frame_size);
+            frame_size = align_to(frame_size, 16);
+            self.variadic_frame = None;
+            if let Type::Function(_, _, true) = node.ty {
+                frame_size += 176;
+                let count = |kind: fn(&ArgumentLocation) -> bool| {
+                    locations.iter().filter(|&l| kind(l)).count() as i32
+                };
+                self.variadic_frame = Some(VariadicFrame {
+                    save_area: -frame_size,
+                    gp_offset: 8 * count(|l| matches!(*l, ArgumentLocation::Integer(_))),
+                    fp_offset: 48 + 16 * count(|l| matches!(*l, ArgumentLocation::Sse(_))),
+                    overflow_offset: 16 + 8 * count(|l| *l == ArgumentLocation::Stack),
+                });
+            }
+
+            let name = symbol_name(&node.data);
+            emitstack.push(String::from(".text"));
+            if node.linkage == Linkage::External {
+                emitstack.push(format!(".globl {}", name));
+            }
+            emitstack.push(format!("{}:", name));
+            emitstack.push(String::from("push %rbp"));
+            emitstack.push(String::from("mov %rsp, %rbp"));
+            if frame_size > 0 {
+                emitstack.push(format!("sub ${}, %rsp", frame_size));
+            }
+            for (parameter, location) in parameters.iter().zip(locations.iter()) {
+                let slot = self.variable_location(&parameter.data);
+                match *location {
+                    ArgumentLocation::Integer(index) => {
+                        let register = argument_register(index, &parameter.ty);
+                        emitstack.push(format!("mov {}, {}", register, slot));
+                    }
+                    ArgumentLocation::Sse(index) => {
+                        let sse = sse(&parameter.ty);
+                        emitstack.push(format!("mov{} %xmm{}, {}", sse, index, slot));
+                    }
+                    ArgumentLocation::Stack => {}
+                }
+            }
+            if let Some(frame) = self.variadic_frame {
+                for (index, register) in ARGUMENT_REGISTERS.iter().enumerate() {
+                    let offset = frame.save_area + 8 * index as i32;
+                    emitstack.push(format!("mov {}, {}(%rbp)", register, offset));
+                }
+                for index in 0..8 {
+                    let offset = frame.save_area + 48 + 16 * index;
+                    emitstack.push(format!("movaps %xmm{}, {}(%rbp)", index, offset));
+                }
+            }
+
+            for child in node.children.iter() {
+                self.generate_code_recurse(child, emitstack);
+            }
+
+            // Falling off the end of main returns 0.
+            emitstack.push(String::from("mov $0, %eax"));
+            self.generate_epilogue(emitstack);
+        }
+
+        // Initialized objects go in .data and zero filled ones in .bss, where they
+        // take no space in the object file. The children of the node are the
+        // object's initial contents in memory order.
+        fn generate_static_variable(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let name = symbol_name(&node.data);
+            let zero_filled = node
+                .children
+                .iter()
+                .all(|c| c.node_type == AstNodeType::ZeroFill);
+            if zero_filled {
+                emitstack.push(String::from(".bss"));
+            } else {
+                emitstack.push(String::from(".data"));
+            }
+            if node.linkage == Linkage::External {
+                emitstack.push(format!(".globl {}", name));
+            }
+            emitstack.push(format!(".align {}", node.ty.align()));
+            emitstack.push(format!("{}:", name));
+            if zero_filled {
+                emitstack.push(format!(".zero {}", node.ty.size()));
+                return;
+            }
+            for piece in node.children.iter() {
+                match piece.node_type {
+                    AstNodeType::ZeroFill => emitstack.push(format!(".zero {}", piece.data)),
+                    AstNodeType::AddressOf => {
+                        let name = symbol_name(&piece.children[0].data);
+                        match piece.data.parse::<i64>() {
+                            Ok(offset) if offset != 0 => {
+                                emitstack.push(format!(".quad {}{:+}", name, offset))
+                            }
+                            _ => emitstack.push(format!(".quad {}", name)),
+                        }
+                    }
+                    _ => {
+                        let directive = match piece.ty.size() {
+                            1 => ".byte",
+                            2 => ".short",
+                            4 => ".long",
+                            _ => ".quad",
+                        };
+                        emitstack.push(format!("{} {}", directive, piece.data));
+                    }
+                }
+            }
+        }
+
+        fn push(&mut self, register: &str, emitstack: &mut Vec<String>) {
+            emitstack.push(format!("push {}", register));
+            self.stack_depth += 1;
+        }
+
+        fn pop(&mut self, register: &str, emitstack: &mut Vec<String>) {
+            emitstack.push(format!("pop {}", register));
+            self.stack_depth -= 1;
+        }
+
+        // Pushes the value just computed, moving a floating one out of %xmm0
+        // first.
+        fn push_value(&mut self, ty: &Type, emitstack: &mut Vec<String>) {
+            if ty.is_floating() {
+                emitstack.push(String::from("movq %xmm0, %rax"));
+            }
+            self.push("%rax", emitstack);
+        }
+
+        // Arguments are evaluated right to left and pushed, those passed on
+        // the stack first so that they end up in the order the callee
+        // expects. The register arguments are then popped into place. Padding
+        // is inserted below the stack arguments when needed so that %rsp is
+        // 16 byte aligned at the call. A pointer to the called function is
+        // evaluated last and kept in %r10, which no argument uses.
+        fn generate_call(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let indirect = node.node_type == AstNodeType::IndirectCall;
+            let arguments = if indirect {
+                &node.children[1..]
+            } else {
+                &node.children[..]
+            };
+            let types: Vec<&Type> = arguments.iter().map(|c| &c.ty).collect();
+            let locations = classify_arguments(&types);
+            let stack_arguments = locations
+                .iter()
+                .filter(|&&l| l == ArgumentLocation::Stack)
+                .count();
+
+            let padding = (self.stack_depth + stack_arguments) % 2;
+            if padding == 1 {
+                emitstack.push(String::from("sub $8, %rsp"));
+                self.stack_depth += 1;
+            }
+
+            for in_registers in [false, true].iter() {
+                for (argument, location) in arguments.iter().zip(locations.iter()).rev() {
+                    if (*location != ArgumentLocation::Stack) == *in_registers {
+                        self.generate_code_recurse(argument, emitstack);
+                        self.push_value(&argument.ty, emitstack);
+                    }
+                }
+            }
+            if indirect {
+                self.generate_code_recurse(&node.children[0], emitstack);
+                emitstack.push(String::from("mov %rax, %r10"));
+            }
+            for location in locations.iter() {
+                match *location {
+                    ArgumentLocation::Integer(index) => {
+                        self.pop(ARGUMENT_REGISTERS[index], emitstack);
+                    }
+                    ArgumentLocation::Sse(index) => {
+                        self.pop("%rax", emitstack);
+                        emitstack.push(format!("movq %rax, %xmm{}", index));
+                    }
+                    ArgumentLocation::Stack => {}
+                }
+            }
+
+            // A variadic callee needs the number of SSE registers used in %al.
+            let vectors = locations
+                .iter()
+                .filter(|l| matches!(**l, ArgumentLocation::Sse(_)))
+                .count();
+            emitstack.push(format!("mov ${}, %eax", vectors));
+            if indirect {
+                emitstack.push(String::from("call *%r10"));
+            } else {
+                emitstack.push(format!("call {}", symbol_name(&node.data)));
+            }
+            // Only the low bits of a narrow return value are defined.
+            if node.ty.is_integer() && node.ty.size() < 4 {
+                self.generate_conversion(&Type::Int, &node.ty, emitstack);
+            }
+
+            let cleanup = stack_arguments + padding;
+            if cleanup > 0 {
+                emitstack.push(format!("add ${}, %rsp", 8 * cleanup));
+                self.stack_depth -= cleanup;
+            }
+        }
+
+        // Fetches the next argument from the va_list: from the register save
+        // area while registers of its class remain, then from the stack.
+        fn generate_va_arg(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let stack = self.new_label("va_stack");
+            let done = self.new_label("va_done");
+            let (offset_field, limit, step) = if node.ty.is_floating() {
+                ("4(%rax)", 176, 16)
+            } else {
+                ("(%rax)", 48, 8)
+            };
+            self.generate_code_recurse(&node.children[0], emitstack);
+            emitstack.push(format!("movl {}, %ecx", offset_field));
+            emitstack.push(format!("cmp ${}, %ecx", limit));
+            emitstack.push(format!("jae {}", stack));
+            emitstack.push(String::from("mov 16(%rax), %rdx"));
+            emitstack.push(String::from("add %rcx, %rdx"));
+            emitstack.push(format!("addl ${}, {}", step, offset_field));
+            emitstack.push(format!("jmp {}", done));
+            emitstack.push(format!("{}:", stack));
+            emitstack.push(String::from("mov 8(%rax), %rdx"));
+            emitstack.push(String::from("lea 8(%rdx), %rcx"));
+            emitstack.push(String::from("mov %rcx, 8(%rax)"));
+            emitstack.push(format!("{}:", done));
+            emitstack.push(load(&node.ty, "(%rdx)"));
+        }
+
+        fn generate_loop_body(
+            &mut self,
+            body: &AstNode,
+            break_label: &str,
+            continue_label: &str,
+            emitstack: &mut Vec<String>,
+        ) {
+            self.break_labels.push(String::from(break_label));
+            self.continue_labels.push(String::from(continue_label));
+            self.generate_code_recurse(body, emitstack);
+            self.break_labels.pop();
+            self.continue_labels.pop();
+        }
+
+        // Every label of a switch is named after its prefix: the case labels
+        // by their index, then the default label and the end of the switch.
+        // Dense cases dispatch through a table of offsets in read only data,
+        // sparse ones through a binary search on the case values.
+        fn generate_switch(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let prefix = self.new_label("switch");
+            let end_label = format!("{}_end", prefix);
+            let mut collector = CaseCollector {
+                cases: Vec::new(),
+                has_default: false,
+            };
+            collector.visit(&node.children[1]);
+            let mut cases = collector.cases;
+            cases.sort();
+            let fallback = if collector.has_default {
+                format!("{}_default", prefix)
+            } else {
+                end_label.clone()
+            };
+
+            let ty = &node.children[0].ty;
+            self.generate_code_recurse(&node.children[0], emitstack);
+            let entries: i128 = cases.iter().map(|&(low, high, _)| high - low + 1).sum();
+            let span = match (cases.first(), cases.last()) {
+                (Some(first), Some(last)) => last.1 - first.0 + 1,
+                _ => 0,
+            };
+            if entries >= 4 && span <= 3 * entries && span <= MAX_JUMP_TABLE {
+                self.generate_jump_table(&prefix, ty, &cases, &fallback, emitstack);
+            } else {
+                self.generate_case_search(&prefix, ty, &cases, emitstack);
+                emitstack.push(format!("jmp {}", fallback));
+            }
+
+            self.break_labels.push(end_label.clone());
+            self.switch_labels.push(prefix);
+            self.generate_code_recurse(&node.children[1], emitstack);
+            self.switch_labels.pop();
+            self.break_labels.pop();
+            emitstack.push(format!("{}:", end_label));
+        }
+
+        fn generate_jump_table(
+            &mut self,
+            prefix: &str,
+            ty: &Type,
+            cases: &[(i128, i128, String)],
+            fallback: &str,
+            emitstack: &mut Vec<String>,
+        ) {
+            let table = format!("{}_table", prefix);
+            let min = cases[0].0;
+            let max = cases[cases.len() - 1].1;
+            if ty.size() == 8 && !fits_immediate(min) {
+                emitstack.push(format!("movabs ${}, %rcx", min as i64));
+                emitstack.push(String::from("sub %rcx, %rax"));
+            } else {
+                emitstack.push(format!("sub ${}, {}", min, ax(ty)));
+            }
+            emitstack.push(format!("cmp ${}, {}", max - min, ax(ty)));
+            emitstack.push(format!("ja {}", fallback));
+            emitstack.push(format!("lea {}(%rip), %rcx", table));
+            emitstack.push(String::from("movslq (%rcx,%rax,4), %rdx"));
+            emitstack.push(String::from("add %rcx, %rdx"));
+            emitstack.push(String::from("jmp *%rdx"));
+
+            emitstack.push(String::from(read_only_section()));
+            emitstack.push(String::from(".balign 4"));
+            emitstack.push(format!("{}:", table));
+            let mut value = min;
+            for &(low, high, ref index) in cases.iter() {
+                for _ in value..low {
+                    emitstack.push(format!(".long {} - {}", fallback, table));
+                }
+                for _ in low..=high {
+                    emitstack.push(format!(".long {}_case{} - {}", prefix, index, table));
+                }
+                value = high + 1;
+            }
+            emitstack.push(String::from(".text"));
+        }
+
+        // Compares the value in the accumulator against a few cases in turn,
+        // or splits larger sets in half on the middle case. Falls through when
+        // nothing matches.
+        fn generate_case_search(
+            &mut self,
+            prefix: &str,
+            ty: &Type,
+            cases: &[(i128, i128, String)],
+            emitstack: &mut Vec<String>,
+        ) {
+            let (below, at_most) = if ty.is_signed() {
+                ("jl", "jle")
+            } else {
+                ("jb", "jbe")
+            };
+            if cases.len() <= 3 {
+                for &(low, high, ref index) in cases.iter() {
+                    let target = format!("{}_case{}", prefix, index);
+                    if low == high {
+                        self.compare_case_value(ty, low, emitstack);
+                        emitstack.push(format!("je {}", target));
+                    } else {
+                        let skip = self.new_label("case_skip");
+                        self.compare_case_value(ty, low, emitstack);
+                        emitstack.push(format!("{} {}", below, skip));
+                        self.compare_case_value(ty, high, emitstack);
+                        emitstack.push(format!("{} {}", at_most, target));
+                        emitstack.push(format!("{}:", skip));
+                    }
+                }
+                return;
+            }
+
+            let middle = cases.len() / 2;
+            let left = self.new_label("case_left");
+            let done = self.new_label("case_done");
+            self.compare_case_value(ty, cases[middle].0, emitstack);
+            emitstack.push(format!("{} {}", below, left));
+            self.generate_case_search(prefix, ty, &cases[middle..], emitstack);
+            emitstack.push(format!("jmp {}", done));
+            emitstack.push(format!("{}:", left));
+            self.generate_case_search(prefix, ty, &cases[..middle], emitstack);
+            emitstack.push(format!("{}:", done));
+        }
+
+        fn compare_case_value(&self, ty: &Type, value: i128, emitstack: &mut Vec<String>) {
+            if ty.size() == 8 && !fits_immediate(value) {
+                emitstack.push(format!("movabs ${}, %rcx", value as i64));
+                emitstack.push(String::from("cmp %rcx, %rax"));
+            } else {
+                emitstack.push(format!("cmp ${}, {}", value, ax(ty)));
+            }
+        }
+
+        // Converts the value in the accumulator from one scalar type to another.
+        // Widening to 64 bits sign or zero extends according to the source
+        // type, narrowing to 8 or 16 bits extends the truncated value back to
+        // 32 bits, and anything else keeps the low bits as they are.
+        fn generate_conversion(&mut self, from: &Type, to: &Type, emitstack: &mut Vec<String>) {
+            if !to.is_scalar() {
+                return;
+            }
+            if from.is_floating() && to.is_floating() {
+                if from != to {
+                    emitstack.push(format!("cvt{}2{} %xmm0, %xmm0", sse(from), sse(to)));
+                }
+                return;
+            }
+            if to.is_floating() {
+                self.generate_integer_to_floating(from, to, emitstack);
+                return;
+            }
+            if from.is_floating() {
+                self.generate_floating_to_integer(from, to, emitstack);
+                return;
+            }
+            if to.size() == 8 && from.size() < 8 {
+                if from.is_signed() {
+                    emitstack.push(String::from("movslq %eax, %rax"));
+                } else {
+                    emitstack.push(String::from("mov %eax, %eax"));
+                }
+            } else if to.size() < 4 {
+                emitstack.push(load(to, sized_ax(to)));
+            }
+        }
+
+        // Every integer type except unsigned long fits in a signed 64-bit
+        // value. An unsigned long with the top bit set is halved first, keeping
+        // the lowest bit so that it still rounds correctly, and doubled after
+        // the conversion.
+        fn generate_integer_to_floating(
+            &mut self,
+            from: &Type,
+            to: &Type,
+            emitstack: &mut Vec<String>,
+        ) {
+            let sse = sse(to);
+            if from.size() < 4 || *from == Type::Int {
+                emitstack.push(format!("cvtsi2{}l %eax, %xmm0", sse));
+                return;
+            }
+            if *from == Type::UnsignedInt {
+                emitstack.push(String::from("mov %eax, %eax"));
+            }
+            if from.is_signed() || from.size() == 4 {
+                emitstack.push(format!("cvtsi2{}q %rax, %xmm0", sse));
+                return;
+            }
+            let large = self.new_label("unsigned_large");
+            let done = self.new_label("unsigned_done");
+            emitstack.push(String::from("test %rax, %rax"));
+            emitstack.push(format!("js {}", large));
+            emitstack.push(format!("cvtsi2{}q %rax, %xmm0", sse));
+            emitstack.push(format!("jmp {}", done));
+            emitstack.push(format!("{}:", large));
+            emitstack.push(String::from("mov %rax, %rcx"));
+            emitstack.push(String::from("shr $1, %rcx"));
+            emitstack.push(String::from("and $1, %eax"));
+            emitstack.push(String::from("or %rcx, %rax"));
+            emitstack.push(format!("cvtsi2{}q %rax, %xmm0", sse));
+            emitstack.push(format!("add{} %xmm0, %xmm0", sse));
+            emitstack.push(format!("{}:", done));
+        }
+
+        // Conversions truncate toward zero. Values of unsigned long from 2^63
+        // up do not fit the signed conversion, so 2^63 is subtracted first
+        // and its bit set again afterwards.
+        fn generate_floating_to_integer(
+            &mut self,
+            from: &Type,
+            to: &Type,
+            emitstack: &mut Vec<String>,
+        ) {
+            let sse = sse(from);
+            if to.size() < 4 || *to == Type::Int {
+                emitstack.push(format!("cvtt{}2si %xmm0, %eax", sse));
+                if to.size() < 4 {
+                    emitstack.push(load(to, sized_ax(to)));
+                }
+                return;
+            }
+            if to.is_signed() || to.size() == 4 {
+                emitstack.push(format!("cvtt{}2si %xmm0, %rax", sse));
+                return;
+            }
+            if *from == Type::Float {
+                emitstack.push(String::from("cvtss2sd %xmm0, %xmm0"));
+            }
+            let large = self.new_label("unsigned_large");
+            let done = self.new_label("unsigned_done");
+            emitstack.push(format!("movabs ${}, %rax", 2f64.powi(63).to_bits()));
+            emitstack.push(String::from("movq %rax, %xmm1"));
+            emitstack.push(String::from("comisd %xmm1, %xmm0"));
+            emitstack.push(format!("jae {}", large));
+            emitstack.push(String::from("cvttsd2si %xmm0, %rax"));
+            emitstack.push(format!("jmp {}", done));
+            emitstack.push(format!("{}:", large));
+            emitstack.push(String::from("subsd %xmm1, %xmm0"));
+            emitstack.push(String::from("cvttsd2si %xmm0, %rax"));
+            emitstack.push(String::from("btc $63, %rax"));
+            emitstack.push(format!("{}:", done));
+        }
+
+        // Floating constants can not be immediates, so each one is placed
+        // in read only data and loaded from there.
+        fn generate_floating_constant(
+            &mut self,
+            ty: &Type,
+            value: f64,
+            emitstack: &mut Vec<String>,
+        ) {
+            let label = self.new_label("float");
+            emitstack.push(String::from(read_only_section()));
+            if *ty == Type::Float {
+                emitstack.push(String::from(".balign 4"));
+                emitstack.push(format!("{}:", label));
+                emitstack.push(format!(".long {}", (value as f32).to_bits()));
+            } else {
+                emitstack.push(String::from(".balign 8"));
+                emitstack.push(format!("{}:", label));
+                emitstack.push(format!(".quad {}", value.to_bits()));
+            }
+            emitstack.push(String::from(".text"));
+            emitstack.push(format!("mov{} {}(%rip), %xmm0", sse(ty), label));
+        }
+
+        fn generate_epilogue(&self, emitstack: &mut Vec<String>) {
+            emitstack.push(String::from("mov %rbp, %rsp"));
+            emitstack.push(String::from("pop %rbp"));
+            emitstack.push(String::from("ret"));
+        }
+
+        fn generate_binary_op(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let lhs = &node.children[0];
+            let rhs = &node.children[1];
+
+            // && and || only evaluate their right operand when needed.
+            if node.data == "&&" || node.data == "||" {
+                let short_circuit = node.data == "||";
+                let end_label = self.new_label("logic_end");
+                self.generate_code_recurse(lhs, emitstack);
+                self.compare_to_zero(&lhs.ty, emitstack);
+                emitstack.push(String::from("mov $0, %eax"));
+                emitstack.push(String::from("setne %al"));
+                emitstack.push(format!(
+                    "{} {}",
+                    if short_circuit { "jne" } else { "je" },
+                    end_label
+                ));
+                self.generate_code_recurse(rhs, emitstack);
+                self.compare_to_zero(&rhs.ty, emitstack);
+                emitstack.push(String::from("mov $0, %eax"));
+                emitstack.push(String::from("setne %al"));
+                emitstack.push(format!("{}:", end_label));
+                return;
+            }
+
+            // Pointer arithmetic: the parser always puts the pointer on the left
+            // and converts the integer offset to long, which is scaled by the
+            // size of the pointee.
+            let additive = node.data == "+" || node.data == "-";
+            if additive && lhs.ty.is_pointer() && rhs.ty.is_integer() {
+                let scale = lhs.ty.pointee().unwrap().size();
+                self.generate_code_recurse(rhs, emitstack);
+                emitstack.push(format!("imul ${}, %rax", scale));
+                self.push("%rax", emitstack);
+                self.generate_code_recurse(lhs, emitstack);
+                self.pop("%rcx", emitstack);
+                match node.data.as_str() {
+                    "+" => emitstack.push(String::from("add %rcx, %rax")),
+                    "-" => emitstack.push(String::from("sub %rcx, %rax")),
+                    _ => unreachable!(),
+                }
+                return;
+            }
+
+            if lhs.ty.is_floating() {
+                self.generate_floating_binary_op(node, emitstack);
+                return;
+            }
+
+            self.generate_code_recurse(rhs, emitstack);
+            self.push("%rax", emitstack);
+            self.generate_code_recurse(lhs, emitstack);
+            self.pop("%rcx", emitstack);
+
+            if lhs.ty.is_pointer() && node.data == "-" {
+                let scale = lhs.ty.pointee().unwrap().size();
+                emitstack.push(String::from("sub %rcx, %rax"));
+                emitstack.push(String::from("cqo"));
+                emitstack.push(format!("mov ${}, %rcx", scale));
+                emitstack.push(String::from("idiv %rcx"));
+                return;
+            }
+
+            // Both operands have the same type by now. Pointers compare as
+            // unsigned values.
+            let ty = &lhs.ty;
+            let operands = format!("{}, {}", cx(ty), ax(ty));
+            let signed = ty.is_signed();
+            match node.data.as_str() {
+                "+" => emitstack.push(format!("add {}", operands)),
+                "-" => emitstack.push(format!("sub {}", operands)),
+                "*" => emitstack.push(format!("imul {}", operands)),
+                "/" | "%" => {
+                    if !signed {
+                        emitstack.push(String::from("xor %edx, %edx"));
+                        emitstack.push(format!("div {}", cx(ty)));
+                    } else if ty.size() == 8 {
+                        emitstack.push(String::from("cqo"));
+                        emitstack.push(format!("idiv {}", cx(ty)));
+                    } else {
+                        emitstack.push(String::from("cdq"));
+                        emitstack.push(format!("idiv {}", cx(ty)));
+                    }
+                    if node.data == "%" {
+                        let remainder = if ty.size() == 8 { "%rdx" } else { "%edx" };
+                        emitstack.push(format!("mov {}, {}", remainder, ax(ty)));
+                    }
+                }
+                comparison => {
+                    let set = match (comparison, signed) {
+                        ("==", _) => "sete",
+                        ("!=", _) => "setne",
+                        ("<", true) => "setl",
+                        ("<=", true) => "setle",
+                        (">", true) => "setg",
+                        (">=", true) => "setge",
+                        ("<", false) => "setb",
+                        ("<=", false) => "setbe",
+                        (">", false) => "seta",
+                        (">=", false) => "setae",
+                        _ => unreachable!(),
+                    };
+                    emitstack.push(format!("cmp {}", operands));
+                    emitstack.push(String::from("mov $0, %eax"));
+                    emitstack.push(format!("{} %al", set));
+                }
+            }
+        }
+
+        // The left operand ends up in %xmm0 and the right one in %xmm1.
+        // Comparisons are unordered when either operand is NaN, which sets the
+        // parity flag, so only != holds then.
+        fn generate_floating_binary_op(&mut self, node: &AstNode, emitstack: &mut Vec<String>) {
+            let lhs = &node.children[0];
+            let rhs = &node.children[1];
+            let ty = &lhs.ty;
+            let sse = sse(ty);
+            self.generate_code_recurse(rhs, emitstack);
+            self.push_value(ty, emitstack);
+            self.generate_code_recurse(lhs, emitstack);
+            self.pop("%rax", emitstack);
+            emitstack.push(String::from("movq %rax, %xmm1"));
+            match node.data.as_str() {
+                "+" => emitstack.push(format!("add{} %xmm1, %xmm0", sse)),
+                "-" => emitstack.push(format!("sub{} %xmm1, %xmm0", sse)),
+                "*" => emitstack.push(format!("mul{} %xmm1, %xmm0", sse)),
+                "/" => emitstack.push(format!("div{} %xmm1, %xmm0", sse)),
+                "==" | "!=" => {
+                    let (equal, ordered, combine) = if node.data == "==" {
+                        ("sete", "setnp", "and")
+                    } else {
+                        ("setne", "setp", "or")
+                    };
+                    emitstack.push(format!("ucomi{} %xmm1, %xmm0", sse));
+                    emitstack.push(format!("{} %al", equal));
+                    emitstack.push(format!("{} %cl", ordered));
+                    emitstack.push(format!("{} %cl, %al", combine));
+                    emitstack.push(String::from("movzbl %al, %eax"));
+                }
+                comparison => {
+                    // a < b is tested as b > a, since seta and setae are false
+                    // for unordered operands.
+                    let (operands, set) = match comparison {
+                        ">" => ("%xmm1, %xmm0", "seta"),
+                        ">=" => ("%xmm1, %xmm0", "setae"),
+                        "<" => ("%xmm0, %xmm1", "seta"),
+                        "<=" => ("%xmm0, %xmm1", "setae"),
+                        _ => unreachable!(),
+                    };
+                    emitstack.push(format!("ucomi{} {}", sse, operands));
+                    emitstack.push(format!("{} %al", set));
+                    emitstack.push(String::from("movzbl %al, %eax"));
                 }
-                AstNodeType::Program => {}
             }
         }
     }
 
+    #[cfg(test)]
     mod test {
         use lexer::*;
-        use std::fs;
         use ast::*;
-        use std::path::Path;
+        use diagnostics::diagnostics::{Diagnostic, Severity};
+        use std::env;
+        use std::fs;
+        use std::path::{Path, PathBuf};
+        use std::process::Command;
+        use types::types::Type;
 
         #[test]
         fn test_good_codegen() {
-            let thepath = Path::new("test\\valid\\return_2.c");
+            let thepath = Path::new("test/valid/return_2.c");
             let token_list = lexer::lex(thepath);
 
             let mut iter = token_list.iter();
-            let result = ast::parse(&mut iter, &|_s| {});
+            let result = ast::parse(&mut iter, &|_: Diagnostic| {});
 
-            let mut emit_stack: &mut Vec<String> = &mut Vec::new();
+            let emit_stack: &mut Vec<String> = &mut Vec::new();
             let mut code_gen = super::CodeGenState::new(result);
-            code_gen.generate_code(&mut emit_stack);
+            code_gen.generate_code(emit_stack);
 
+            let main = super::symbol_name("main");
             let expectedlines = vec![
-                String::from(".globl main"),
-                String::from("main:"),
+                String::from(".text"),
+                format!(".globl {}", main),
+                format!("{}:", main),
+                String::from("push %rbp"),
+                String::from("mov %rsp, %rbp"),
                 String::from("mov $2, %eax"),
+                String::from("mov %rbp, %rsp"),
+                String::from("pop %rbp"),
+                String::from("ret"),
+                String::from("mov $0, %eax"),
+                String::from("mov %rbp, %rsp"),
+                String::from("pop %rbp"),
                 String::from("ret"),
             ];
 
+            assert_eq!(emit_stack.len(), expectedlines.len());
             let mut itt = expectedlines.iter();
 
-            for i in emit_stack.into_iter().rev() {
+            for i in emit_stack.iter() {
                 let emmit_res = itt.next();
                 assert_eq!(i, emmit_res.unwrap());
             }
         }
+
+        fn run_program(executable: &Path) -> (Option<i32>, String) {
+            let output = Command::new(executable)
+                .output()
+                .expect("failed to execute test program");
+            return (
+                output.status.code(),
+                String::from_utf8_lossy(&output.stdout).into_owned(),
+            );
+        }
+
+        fn gcc(source: &Path, output: &Path) {
+            let status = Command::new("gcc")
+                .arg(source)
+                .arg("-o")
+                .arg(output)
+                .status()
+                .expect("failed to execute gcc");
+            assert!(status.success(), "gcc failed on {}", source.display());
+        }
+
+        // Compiles every program in the directory with both this compiler and gcc
+        // and checks that the resulting executables exit with the same code and
+        // print the same output.
+        fn compare_with_gcc(dir: &str) {
+            if cfg!(target_os = "windows") {
+                return;
+            }
+
+            let out_dir: PathBuf = env::temp_dir().join("simple_c_compiler_tests").join(dir);
+            fs::create_dir_all(&out_dir).unwrap();
+
+            for path in fs::read_dir(dir).unwrap() {
+                let thepath = path.unwrap().path();
+                if thepath.extension().and_then(|e| e.to_str()) != Some("c") {
+                    continue;
+                }
+                let stem = thepath.file_stem().unwrap().to_str().unwrap().to_string();
+                println!("Comparing against gcc: {}", thepath.display());
+
+                let token_list = lexer::lex(&thepath);
+                let mut iter = token_list.iter();
+                let result = ast::parse(&mut iter, &|x: Diagnostic| {
+                    if x.severity == Severity::Error {
+                        panic!("{}", x.message);
+                    }
+                });
+
+                let asm_path = out_dir.join(format!("{}.S", stem));
+                let mut code_gen = super::CodeGenState::new(result);
+                code_gen.generate_code_to_file(&asm_path);
+
+                let exe_path = out_dir.join(&stem);
+                gcc(&asm_path, &exe_path);
+                let reference_path = out_dir.join(format!("{}_gcc", stem));
+                gcc(&thepath, &reference_path);
+
+                assert_eq!(
+                    run_program(&exe_path),
+                    run_program(&reference_path),
+                    "behaviour mismatch for {}",
+                    thepath.display()
+                );
+            }
+        }
+
+        #[test]
+        fn test_valid_programs() {
+            compare_with_gcc("test/valid");
+        }
+
+        #[test]
+        fn test_blocks() {
+            compare_with_gcc("test/blocks/valid");
+        }
+
+        #[test]
+        fn test_loops() {
+            compare_with_gcc("test/loops/valid");
+        }
+
+        #[test]
+        fn test_functions() {
+            compare_with_gcc("test/functions/valid");
+        }
+
+        #[test]
+        fn test_globals() {
+            compare_with_gcc("test/globals/valid");
+        }
+
+        #[test]
+        fn test_pointers() {
+            compare_with_gcc("test/pointers/valid");
+        }
+
+        #[test]
+        fn test_arrays() {
+            compare_with_gcc("test/arrays/valid");
+        }
+
+        #[test]
+        fn test_structs() {
+            compare_with_gcc("test/structs/valid");
+        }
+
+        #[test]
+        fn test_enums() {
+            compare_with_gcc("test/enums/valid");
+        }
+
+        #[test]
+        fn test_typedefs() {
+            compare_with_gcc("test/typedefs/valid");
+        }
+
+        #[test]
+        fn test_static_assert() {
+            compare_with_gcc("test/static_assert/valid");
+        }
+
+        #[test]
+        fn test_integers() {
+            compare_with_gcc("test/integers/valid");
+        }
+
+        #[test]
+        fn test_floats() {
+            compare_with_gcc("test/floats/valid");
+        }
+
+        #[test]
+        fn test_sizeof() {
+            compare_with_gcc("test/sizeof/valid");
+        }
+
+        #[test]
+        fn test_casts() {
+            compare_with_gcc("test/casts/valid");
+        }
+
+        #[test]
+        fn test_function_pointers() {
+            compare_with_gcc("test/function_pointers/valid");
+        }
+
+        #[test]
+        fn test_goto() {
+            compare_with_gcc("test/goto/valid");
+        }
+
+        #[test]
+        fn test_switch() {
+            compare_with_gcc("test/switch/valid");
+        }
+
+        #[test]
+        fn test_variadic() {
+            compare_with_gcc("test/variadic/valid");
+        }
+
+        #[test]
+        fn test_qualifiers() {
+            compare_with_gcc("test/qualifiers/valid");
+        }
+
+        #[test]
+        fn test_sema() {
+            compare_with_gcc("test/sema/valid");
+        }
+
+        #[test]
+        fn test_warnings() {
+            compare_with_gcc("test/warnings/valid");
+        }
+
+        fn switch_code(cases: &str) -> Vec<String> {
+            let source = format!(
+                "int main() {{ int x = 3; switch (x) {{ {} }} return 0; }}",
+                cases
+            );
+            return program_code("switch_dispatch", &source);
+        }
+
+        // The assembly generated for the C program.
+        fn program_code(name: &str, source: &str) -> Vec<String> {
+            let path = env::temp_dir().join(format!("simple_c_compiler_{}.c", name));
+            fs::write(&path, source).unwrap();
+            let token_list = lexer::lex(&path);
+            let mut iter = token_list.iter();
+            let result = ast::parse(&mut iter, &|x: Diagnostic| {
+                if x.severity == Severity::Error {
+                    panic!("{}", x.message);
+                }
+            });
+            let emit_stack: &mut Vec<String> = &mut Vec::new();
+            let mut code_gen = super::CodeGenState::new(result);
+            code_gen.generate_code(emit_stack);
+            return emit_stack.clone();
+        }
+
+        #[test]
+        fn test_switch_dispatch() {
+            let dense = switch_code("case 1: case 2: case 3: case 5: case 6: break;");
+            assert!(dense.iter().any(|line| line == "jmp *%rdx"));
+
+            let sparse = switch_code("case 1: case 100: case 10000: case 1000000: break;");
+            assert!(!sparse.iter().any(|line| line.starts_with("jmp *")));
+            assert!(sparse.iter().any(|line| line.starts_with("jl ")));
+        }
+
+        // 64 bit constants that a sign extended immediate cannot hold are
+        // loaded with movabs, and converted constants are loaded converted.
+        #[test]
+        fn test_integer_immediates() {
+            let code = program_code(
+                "integer_immediates",
+                "long big(void) { return 99999999999; }\n\
+                 long small(void) { return -5; }\n\
+                 unsigned long high(void) { return 4294967295u; }\n\
+                 int narrow(void) { return 99999999999; }\n\
+                 int main(void) { return 0; }",
+            );
+            let has = |line: &str| code.iter().any(|l| l == line);
+            assert!(has("movabs $99999999999, %rax"));
+            assert!(has("mov $-5, %rax"));
+            assert!(has("movabs $4294967295, %rax"));
+            assert!(has("mov $1215752191, %eax"));
+            assert_eq!(code.iter().filter(|l| l.starts_with("movabs")).count(), 2);
+        }
+
+        #[test]
+        fn test_volatile_accesses() {
+            let code = program_code(
+                "volatile_accesses",
+                "volatile int v; int main() { v; v; v = 1; v = 1; return 0; }",
+            );
+            let v = super::symbol_name("v");
+            let count = |line: String| code.iter().filter(|l| **l == line).count();
+            assert_eq!(count(format!("movl {}(%rip), %eax", v)), 2);
+            assert_eq!(count(format!("mov %eax, {}(%rip)", v)), 2);
+        }
+
+        // Collects the size and alignment of the object named by the C
+        // expression, and the offset of each of its members, as laid out by
+        // this compiler. Each probe is the C expression that asks gcc for the
+        // same value.
+        fn layout_probes(object: &str, ty: &Type, probes: &mut Vec<(String, i32)>) {
+            // A flexible array member has only an offset.
+            if ty.is_incomplete_array() {
+                return;
+            }
+            probes.push((format!("sizeof({})", object), ty.size()));
+            probes.push((format!("_Alignof(__typeof__({}))", object), ty.align()));
+            match *ty {
+                Type::Struct(ref aggregate) => {
+                    let aggregate = aggregate.borrow();
+                    for member in aggregate.members.as_ref().unwrap().iter() {
+                        probes.push((
+                            format!("offsetof(__typeof__({}), {})", object, member.name),
+                            member.offset,
+                        ));
+                        let path = format!("{}.{}", object, member.name);
+                        layout_probes(&path, &member.ty, probes);
+                    }
+                }
+                Type::Array(..) => {
+                    layout_probes(&format!("{}[0]", object), ty.element().unwrap(), probes);
+                }
+                _ => {}
+            }
+        }
+
+        // Checks the size, alignment and member offsets of every global in the
+        // layout tests against what gcc computes for the same declarations.
+        #[test]
+        fn test_struct_layout() {
+            if cfg!(target_os = "windows") {
+                return;
+            }
+            let dir = "test/structs/layout";
+            let out_dir: PathBuf = env::temp_dir().join("simple_c_compiler_tests").join(dir);
+            fs::create_dir_all(&out_dir).unwrap();
+
+            for path in fs::read_dir(dir).unwrap() {
+                let thepath = path.unwrap().path();
+                let stem = thepath.file_stem().unwrap().to_str().unwrap().to_string();
+                println!("Comparing layout against gcc: {}", thepath.display());
+
+                let token_list = lexer::lex(&thepath);
+                let mut iter = token_list.iter();
+                let result = ast::parse(&mut iter, &|x: Diagnostic| {
+                    if x.severity == Severity::Error {
+                        panic!("{}", x.message);
+                    }
+                });
+
+                let mut probes = Vec::new();
+                for global in result.children.iter() {
+                    if global.node_type == ast::AstNodeType::StaticVariable {
+                        layout_probes(&global.data, &global.ty, &mut probes);
+                    }
+                }
+
+                let mut program = String::from("#include <stdio.h>\n#include <stddef.h>\n");
+                program.push_str(&fs::read_to_string(&thepath).unwrap());
+                program.push_str("int main() {\n");
+                let mut expected = String::new();
+                for &(ref probe, value) in probes.iter() {
+                    program.push_str(&format!(
+                        "    printf(\"%s=%d\\n\", \"{}\", (int){});\n",
+                        probe, probe
+                    ));
+                    expected.push_str(&format!("{}={}\n", probe, value));
+                }
+                program.push_str("    return 0;\n}\n");
+
+                let source_path = out_dir.join(format!("{}.c", stem));
+                fs::write(&source_path, program).unwrap();
+                let exe_path = out_dir.join(&stem);
+                gcc(&source_path, &exe_path);
+                assert_eq!(run_program(&exe_path), (Some(0), expected));
+            }
+        }
     }
 }
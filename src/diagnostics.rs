@@ -0,0 +1,994 @@
+#![allow(dead_code)]
+
+pub mod diagnostics {
+
+    use std::cell::{Cell, RefCell};
+    use std::fmt;
+
+    // A range of columns on one source line. Lines and columns count from 1
+    // and the end column is one past the last character.
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Span {
+        pub line: u32,
+        pub start: u32,
+        pub end: u32,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Severity {
+        Error,
+        Warning,
+    }
+
+    impl fmt::Display for Severity {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match *self {
+                Severity::Error => write!(f, "error"),
+                Severity::Warning => write!(f, "warning"),
+            }
+        }
+    }
+
+    // The kinds of problem the compiler reports. Each has a stable code that
+    // documentation can refer to and `--explain` describes at length, so
+    // codes are only ever added, never renumbered.
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Code {
+        Syntax,
+        InvalidLiteral,
+        Undeclared,
+        Redefinition,
+        ConflictingDeclaration,
+        InvalidSpecifiers,
+        IncompleteType,
+        InvalidDeclarator,
+        IncompatibleTypes,
+        InvalidOperands,
+        NotAssignable,
+        NotConstant,
+        InvalidCall,
+        InvalidMemberAccess,
+        InvalidInitializer,
+        MisplacedStatement,
+        ReturnMismatch,
+        InvalidVariadic,
+        Unsupported,
+        OutOfRange,
+        StaticAssertion,
+        DiscardedQualifiers,
+        UnusedVariable,
+        UnusedFunction,
+        MissingReturn,
+        ImplicitConversion,
+        SignCompare,
+        Unreachable,
+        Shadow,
+        Overflow,
+        DivByZero,
+    }
+
+    static CODES: &[Code] = &[
+        Code::Syntax,
+        Code::InvalidLiteral,
+        Code::Undeclared,
+        Code::Redefinition,
+        Code::ConflictingDeclaration,
+        Code::InvalidSpecifiers,
+        Code::IncompleteType,
+        Code::InvalidDeclarator,
+        Code::IncompatibleTypes,
+        Code::InvalidOperands,
+        Code::NotAssignable,
+        Code::NotConstant,
+        Code::InvalidCall,
+        Code::InvalidMemberAccess,
+        Code::InvalidInitializer,
+        Code::MisplacedStatement,
+        Code::ReturnMismatch,
+        Code::InvalidVariadic,
+        Code::Unsupported,
+        Code::OutOfRange,
+        Code::StaticAssertion,
+        Code::DiscardedQualifiers,
+        Code::UnusedVariable,
+        Code::UnusedFunction,
+        Code::MissingReturn,
+        Code::ImplicitConversion,
+        Code::SignCompare,
+        Code::Unreachable,
+        Code::Shadow,
+        Code::Overflow,
+        Code::DivByZero,
+    ];
+
+    impl Code {
+        pub fn id(&self) -> &'static str {
+            match *self {
+                Code::Syntax => "E0001",
+                Code::InvalidLiteral => "E0002",
+                Code::Undeclared => "E0003",
+                Code::Redefinition => "E0004",
+                Code::ConflictingDeclaration => "E0005",
+                Code::InvalidSpecifiers => "E0006",
+                Code::IncompleteType => "E0007",
+                Code::InvalidDeclarator => "E0008",
+                Code::IncompatibleTypes => "E0009",
+                Code::InvalidOperands => "E0010",
+                Code::NotAssignable => "E0011",
+                Code::NotConstant => "E0012",
+                Code::InvalidCall => "E0013",
+                Code::InvalidMemberAccess => "E0014",
+                Code::InvalidInitializer => "E0015",
+                Code::MisplacedStatement => "E0016",
+                Code::ReturnMismatch => "E0017",
+                Code::InvalidVariadic => "E0018",
+                Code::Unsupported => "E0019",
+                Code::OutOfRange => "E0020",
+                Code::StaticAssertion => "E0021",
+                Code::DiscardedQualifiers => "W0001",
+                Code::UnusedVariable => "W0002",
+                Code::UnusedFunction => "W0003",
+                Code::MissingReturn => "W0004",
+                Code::ImplicitConversion => "W0005",
+                Code::SignCompare => "W0006",
+                Code::Unreachable => "W0007",
+                Code::Shadow => "W0008",
+                Code::Overflow => "W0009",
+                Code::DivByZero => "W0010",
+            }
+        }
+
+        pub fn from_id(id: &str) -> Option<Code> {
+            return CODES.iter().find(|code| code.id() == id).cloned();
+        }
+
+        // The name of the `-W` flag that controls a warning.
+        pub fn flag(&self) -> Option<&'static str> {
+            match *self {
+                Code::DiscardedQualifiers => return Some("discarded-qualifiers"),
+                Code::UnusedVariable => return Some("unused-variable"),
+                Code::UnusedFunction => return Some("unused-function"),
+                Code::MissingReturn => return Some("missing-return"),
+                Code::ImplicitConversion => return Some("implicit-conversion"),
+                Code::SignCompare => return Some("sign-compare"),
+                Code::Unreachable => return Some("unreachable-code"),
+                Code::Shadow => return Some("shadow"),
+                Code::Overflow => return Some("overflow"),
+                Code::DivByZero => return Some("div-by-zero"),
+                _ => return None,
+            }
+        }
+
+        // The long form description printed by `--explain`.
+        pub fn explanation(&self) -> &'static str {
+            match *self {
+                Code::Syntax => {
+                    "The tokens do not form a valid C program: a token is missing, such as\n\
+                     the semi-colon at the end of a statement, or one appears where the\n\
+                     grammar does not allow it. A stray character that cannot start any\n\
+                     token, such as @ or a byte that is not valid UTF-8, is also reported.\n\
+                     \n\
+                     \x20   int x = 1 2;    // a semi-colon is expected before 2\n\
+                     \n\
+                     After a syntax error the compiler skips to the end of the statement,\n\
+                     declaration or block and carries on, so that every syntax error in the\n\
+                     file is reported in one run."
+                }
+                Code::InvalidLiteral => {
+                    "An integer, floating or character constant, or a string literal, is\n\
+                     malformed: it has invalid digits or suffixes, an unknown escape\n\
+                     sequence, no closing quote, or no characters at all.\n\
+                     \n\
+                     \x20   int x = 09;     // 9 is not an octal digit\n\
+                     \x20   char c = '';    // empty character constant"
+                }
+                Code::Undeclared => {
+                    "A name is used without a declaration in scope. Functions must be\n\
+                     declared before they are called, and goto statements must jump to a\n\
+                     label defined in the same function.\n\
+                     \n\
+                     \x20   int main(void) { return count; }    // count is not declared"
+                }
+                Code::Redefinition => {
+                    "A name is defined twice in the same scope: a variable, parameter,\n\
+                     function body, label, enumerator or struct member.\n\
+                     \n\
+                     \x20   int f(int a) { int a; return a; }    // a is already a parameter"
+                }
+                Code::ConflictingDeclaration => {
+                    "A declaration does not agree with an earlier declaration of the same\n\
+                     name: the types differ, the linkage differs, or the name was declared\n\
+                     as a different kind of symbol, such as a typedef and then a variable.\n\
+                     \n\
+                     \x20   int f(void);\n\
+                     \x20   long f(void);    // conflicting types for f"
+                }
+                Code::InvalidSpecifiers => {
+                    "The declaration specifiers cannot be combined, or are not allowed in\n\
+                     this kind of declaration: two types, two storage classes, signed with\n\
+                     unsigned, restrict on a type that is not a pointer, or a storage class\n\
+                     on a parameter or struct member.\n\
+                     \n\
+                     \x20   unsigned signed int x;\n\
+                     \x20   static extern int y;"
+                }
+                Code::IncompleteType => {
+                    "An object, member or array element needs a complete type, one whose\n\
+                     size is known, but the struct or union has only been declared, or the\n\
+                     array has no size.\n\
+                     \n\
+                     \x20   struct node;\n\
+                     \x20   struct node n;    // the size of struct node is unknown"
+                }
+                Code::InvalidDeclarator => {
+                    "The declarator describes a type C does not allow, such as a function\n\
+                     returning an array or a function, an array of functions, a parameter\n\
+                     of type void, an array of size zero or too large to lay out, a\n\
+                     struct without members, or a bit-field of non-integer type or a width\n\
+                     that does not fit its type.\n\
+                     \n\
+                     \x20   int f(void)[3];    // a function cannot return an array"
+                }
+                Code::IncompatibleTypes => {
+                    "A value cannot be converted to the type it is used as: it is assigned,\n\
+                     passed, returned or cast to a type it does not convert to, or a\n\
+                     condition does not have a scalar type.\n\
+                     \n\
+                     \x20   struct point p;\n\
+                     \x20   int x = p;    // a struct does not convert to int"
+                }
+                Code::InvalidOperands => {
+                    "An operator is applied to operands of types it does not accept, such\n\
+                     as adding two pointers, dereferencing an integer or taking the address\n\
+                     of a value that is not stored in an object.\n\
+                     \n\
+                     \x20   int *p, *q;\n\
+                     \x20   p + q;    // pointers cannot be added"
+                }
+                Code::NotAssignable => {
+                    "The left side of an assignment is not a modifiable lvalue: it is not\n\
+                     an object at all, or it is const-qualified, or it is a struct with a\n\
+                     const member.\n\
+                     \n\
+                     \x20   const int limit = 10;\n\
+                     \x20   limit = 20;    // limit is const"
+                }
+                Code::NotConstant => {
+                    "The context requires a constant expression, which the compiler\n\
+                     evaluates while compiling: array sizes, case labels, enumerator values\n\
+                     and the initializers of objects with static storage duration.\n\
+                     \n\
+                     \x20   int n = 3;\n\
+                     \x20   static int x = n;    // n is not a constant\n\
+                     \n\
+                     Only integer constants, enumerators, sizeof and the operators applied\n\
+                     to them are constant; the value of a variable is not, even if it is\n\
+                     const."
+                }
+                Code::InvalidCall => {
+                    "A call does not match what is called: the callee is not a function or\n\
+                     a pointer to one, or the number of arguments differs from the number\n\
+                     of parameters.\n\
+                     \n\
+                     \x20   int add(int a, int b);\n\
+                     \x20   add(1);    // add expects 2 arguments"
+                }
+                Code::InvalidMemberAccess => {
+                    "A member access names a member the struct or union does not have, or\n\
+                     is applied to a value that is not a struct or union. The arrow operator\n\
+                     needs a pointer to a struct or union.\n\
+                     \n\
+                     \x20   struct point { int x; } p;\n\
+                     \x20   p.z = 1;    // struct point has no member z"
+                }
+                Code::InvalidInitializer => {
+                    "An initializer does not fit the object: it has more elements than the\n\
+                     array or struct, a string is longer than the char array, or an array\n\
+                     is initialized from something other than a braced list or string.\n\
+                     \n\
+                     \x20   int a[2] = {1, 2, 3};    // excess element"
+                }
+                Code::MisplacedStatement => {
+                    "A statement appears where it has no meaning: break outside a loop or\n\
+                     switch, continue outside a loop, case or default outside a switch, or\n\
+                     a switch with two default labels or two cases with the same value.\n\
+                     \n\
+                     \x20   int main(void) { break; }"
+                }
+                Code::ReturnMismatch => {
+                    "A return statement does not match the return type of its function: a\n\
+                     void function returns a value, or a function with a return type\n\
+                     returns without one.\n\
+                     \n\
+                     \x20   void f(void) { return 1; }"
+                }
+                Code::InvalidVariadic => {
+                    "The stdarg builtins are misused: va_start in a function without a\n\
+                     `...` parameter, a va_list operand of the wrong type, or va_arg with a\n\
+                     type that is promoted when passed through `...`, which must be fetched\n\
+                     as its promoted type instead.\n\
+                     \n\
+                     \x20   char c = va_arg(ap, char);    // use va_arg(ap, int)"
+                }
+                Code::Unsupported => {
+                    "The program is valid C but uses a feature this compiler does not\n\
+                     implement, such as long double, bit-fields, multi-character constants\n\
+                     or passing structs by value."
+                }
+                Code::OutOfRange => {
+                    "A constant value does not fit the type it must have, such as an\n\
+                     enumerator whose value is outside the range of int.\n\
+                     \n\
+                     \x20   enum big { HUGE = 3000000000 };"
+                }
+                Code::StaticAssertion => {
+                    "The constant expression of a static assertion is zero, so the\n\
+                     assertion failed, and its message is reported as the error.\n\
+                     \n\
+                     \x20   _Static_assert(sizeof(int) == 8, \"int must be 8 bytes\");"
+                }
+                Code::DiscardedQualifiers => {
+                    "A pointer is converted to a pointer type that drops some of the\n\
+                     qualifiers of what it points to, so the object could be modified\n\
+                     through the new pointer although it is const, or accessed as if it\n\
+                     were not volatile. This is a warning; add the qualifiers to the target\n\
+                     type or cast explicitly if the conversion is intended.\n\
+                     \n\
+                     \x20   const char *name = \"x\";\n\
+                     \x20   char *p = name;    // discards const"
+                }
+                Code::UnusedVariable => {
+                    "A local variable is declared but never used. It may be left over from\n\
+                     an earlier version of the code, or another variable may have been used\n\
+                     by mistake where this one was meant. Enabled by -Wall.\n\
+                     \n\
+                     \x20   int count = 0;    // never read or written again"
+                }
+                Code::UnusedFunction => {
+                    "A static function is defined but never called or referred to. As it\n\
+                     cannot be used from another file either, it is dead code. Enabled by\n\
+                     -Wall.\n\
+                     \n\
+                     \x20   static int helper(void) { return 1; }"
+                }
+                Code::MissingReturn => {
+                    "Control can reach the end of a function that returns a value, and the\n\
+                     value the caller then receives is undefined. Every path through the\n\
+                     body must end in a return statement; main is exempt, as reaching its\n\
+                     end returns 0. Enabled by -Wall.\n\
+                     \n\
+                     \x20   int sign(int x) { if (x < 0) return -1; if (x > 0) return 1; }"
+                }
+                Code::ImplicitConversion => {
+                    "A value is implicitly converted to a type that cannot represent all of\n\
+                     its values: a floating value to an integer, or to a narrower floating\n\
+                     type, an integer to a narrower integer, or a wide integer to a floating\n\
+                     type with fewer bits of precision. Constants that convert exactly are\n\
+                     not reported. Add a cast to show the conversion is intended.\n\
+                     \n\
+                     \x20   double ratio = 0.75;\n\
+                     \x20   int percent = ratio * 100;"
+                }
+                Code::SignCompare => {
+                    "A signed integer is compared with an unsigned one. The usual arithmetic\n\
+                     conversions turn the signed operand into an unsigned value, so a\n\
+                     negative number compares greater than any small positive one. Enabled\n\
+                     by -Wextra.\n\
+                     \n\
+                     \x20   int i = -1;\n\
+                     \x20   unsigned n = 4;\n\
+                     \x20   if (i < n)    // false"
+                }
+                Code::Unreachable => {
+                    "A statement can never be executed: it follows a return, goto, break or\n\
+                     continue, or a loop that never ends, and has no label to jump to it.\n\
+                     Enabled by -Wextra.\n\
+                     \n\
+                     \x20   return total;\n\
+                     \x20   total = 0;    // never executed"
+                }
+                Code::Shadow => {
+                    "A declaration hides a variable of the same name declared in an enclosing\n\
+                     block or at file scope, so uses of the name in its scope refer to the\n\
+                     new variable. This is easily mistaken for a use of the outer one.\n\
+                     \n\
+                     \x20   int total;\n\
+                     \x20   void add(int x) { int total = x; }"
+                }
+                Code::Overflow => {
+                    "A signed operation on constants has a result its type cannot\n\
+                     represent, or a constant is implicitly converted to an integer type\n\
+                     that cannot represent it. Either way the compiler uses the value\n\
+                     wrapped around to the type, which is unlikely to be the one intended.\n\
+                     \n\
+                     \x20   int big = 2147483647 + 1;    // use a long operand instead\n\
+                     \x20   int f(void) { return 99999999999; }    // becomes 1215752191"
+                }
+                Code::DivByZero => {
+                    "An integer division or remainder by a constant zero, which is undefined\n\
+                     and usually ends the program when it runs. Where a constant is required\n\
+                     this is error E0012 instead.\n\
+                     \n\
+                     \x20   int ratio = total / 0;"
+                }
+            }
+        }
+    }
+
+    // Another place in the source that explains a diagnostic, such as an
+    // earlier definition.
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Label {
+        pub span: Span,
+        pub message: String,
+    }
+
+    // A message from the compiler: how serious it is, what kind of problem it
+    // is, the place in the source it refers to if it refers to one, and any
+    // other places and notes that help to understand it.
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct Diagnostic {
+        pub severity: Severity,
+        pub code: Code,
+        pub message: String,
+        pub span: Option<Span>,
+        pub labels: Vec<Label>,
+        pub notes: Vec<String>,
+    }
+
+    impl Diagnostic {
+        pub fn new(
+            severity: Severity,
+            code: Code,
+            message: String,
+            span: Option<Span>,
+        ) -> Diagnostic {
+            return Diagnostic {
+                severity,
+                code,
+                message,
+                span,
+                labels: Vec::new(),
+                notes: Vec::new(),
+            };
+        }
+
+        pub fn error(code: Code, message: String, span: Option<Span>) -> Diagnostic {
+            return Diagnostic::new(Severity::Error, code, message, span);
+        }
+
+        pub fn warning(code: Code, message: String, span: Option<Span>) -> Diagnostic {
+            return Diagnostic::new(Severity::Warning, code, message, span);
+        }
+
+        pub fn with_label(mut self, span: Span, message: String) -> Diagnostic {
+            self.labels.push(Label { span, message });
+            return self;
+        }
+
+        pub fn with_note(mut self, note: String) -> Diagnostic {
+            self.notes.push(note);
+            return self;
+        }
+    }
+
+    // Receives the diagnostics of a compilation.
+    pub trait DiagnosticSink {
+        fn report(&self, diagnostic: Diagnostic);
+    }
+
+    impl<F> DiagnosticSink for F
+    where
+        F: Fn(Diagnostic),
+    {
+        fn report(&self, diagnostic: Diagnostic) {
+            self(diagnostic);
+        }
+    }
+
+    // Warnings reported unless turned off, and the groups `-Wall` and
+    // `-Wextra` turn on.
+    static DEFAULT_WARNINGS: &[Code] =
+        &[Code::DiscardedQualifiers, Code::Overflow, Code::DivByZero];
+    static ALL_WARNINGS: &[Code] = &[
+        Code::DiscardedQualifiers,
+        Code::Overflow,
+        Code::DivByZero,
+        Code::UnusedVariable,
+        Code::UnusedFunction,
+        Code::MissingReturn,
+    ];
+    static EXTRA_WARNINGS: &[Code] = &[Code::SignCompare, Code::Unreachable];
+
+    // Which warnings are reported and how, set by the `-W` flags in the order
+    // given, so that a later flag overrides an earlier one.
+    #[derive(Clone, PartialEq, Debug)]
+    pub struct WarningOptions {
+        enabled: Vec<Code>,
+        // `-Werror` reports warnings as errors.
+        pub as_errors: bool,
+        // `-w` drops every warning.
+        pub suppressed: bool,
+    }
+
+    impl WarningOptions {
+        pub fn new() -> WarningOptions {
+            return WarningOptions {
+                enabled: DEFAULT_WARNINGS.to_vec(),
+                as_errors: false,
+                suppressed: false,
+            };
+        }
+
+        // Whether the argument is a warning flag, which apply may still
+        // reject.
+        pub fn is_flag(argument: &str) -> bool {
+            return argument == "-w" || argument.starts_with("-W");
+        }
+
+        // Applies a flag: `-w`, `-Werror`, `-Wno-error`, `-Wall`, `-Wextra`,
+        // or `-Wname` and `-Wno-name` for a single warning. Returns false for
+        // a flag that names no known warning.
+        pub fn apply(&mut self, flag: &str) -> bool {
+            match flag {
+                "-w" => self.suppressed = true,
+                "-Werror" => self.as_errors = true,
+                "-Wno-error" => self.as_errors = false,
+                "-Wall" => self.enable(ALL_WARNINGS),
+                "-Wextra" => self.enable(EXTRA_WARNINGS),
+                _ => {
+                    let (name, on) = match flag.strip_prefix("-Wno-") {
+                        Some(name) => (name, false),
+                        None => match flag.strip_prefix("-W") {
+                            Some(name) => (name, true),
+                            None => return false,
+                        },
+                    };
+                    let code = match CODES.iter().find(|code| code.flag() == Some(name)) {
+                        Some(code) => *code,
+                        None => return false,
+                    };
+                    if on {
+                        self.enable(&[code]);
+                    } else {
+                        self.enabled.retain(|enabled| *enabled != code);
+                    }
+                }
+            }
+            return true;
+        }
+
+        fn enable(&mut self, codes: &[Code]) {
+            for code in codes.iter() {
+                if !self.enabled.contains(code) {
+                    self.enabled.push(*code);
+                }
+            }
+        }
+
+        pub fn is_enabled(&self, code: Code) -> bool {
+            return !self.suppressed && self.enabled.contains(&code);
+        }
+
+        // The diagnostic as it should be reported, or None if it is a warning
+        // that is turned off.
+        pub fn filter(&self, mut diagnostic: Diagnostic) -> Option<Diagnostic> {
+            if diagnostic.severity == Severity::Error {
+                return Some(diagnostic);
+            }
+            if !self.is_enabled(diagnostic.code) {
+                return None;
+            }
+            if self.as_errors {
+                diagnostic.severity = Severity::Error;
+            }
+            return Some(diagnostic);
+        }
+    }
+
+    impl Default for WarningOptions {
+        fn default() -> WarningOptions {
+            return WarningOptions::new();
+        }
+    }
+
+    // Passes on to another sink the diagnostics the warning options let
+    // through.
+    pub struct WarningFilter<'a, S: 'a>
+    where
+        S: DiagnosticSink,
+    {
+        options: &'a WarningOptions,
+        sink: &'a S,
+    }
+
+    impl<'a, S> WarningFilter<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        pub fn new(options: &'a WarningOptions, sink: &'a S) -> WarningFilter<'a, S> {
+            return WarningFilter { options, sink };
+        }
+    }
+
+    impl<'a, S> DiagnosticSink for WarningFilter<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn report(&self, diagnostic: Diagnostic) {
+            if let Some(diagnostic) = self.options.filter(diagnostic) {
+                self.sink.report(diagnostic);
+            }
+        }
+    }
+
+    // Holds on to diagnostics until flushed, then passes them on to another
+    // sink in source order. The passes over a file each find their own
+    // problems, which would otherwise be reported one pass after another.
+    // Diagnostics at the same place keep the order they were reported in,
+    // and those without a place come last.
+    pub struct SortedSink<'a, S: 'a>
+    where
+        S: DiagnosticSink,
+    {
+        sink: &'a S,
+        pending: RefCell<Vec<Diagnostic>>,
+    }
+
+    impl<'a, S> SortedSink<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        pub fn new(sink: &'a S) -> SortedSink<'a, S> {
+            return SortedSink {
+                sink,
+                pending: RefCell::new(Vec::new()),
+            };
+        }
+
+        pub fn flush(&self) {
+            let mut pending = self.pending.replace(Vec::new());
+            pending.sort_by_key(|diagnostic| match diagnostic.span {
+                Some(span) => (span.line, span.start),
+                None => (u32::MAX, u32::MAX),
+            });
+            for diagnostic in pending {
+                self.sink.report(diagnostic);
+            }
+        }
+    }
+
+    impl<'a, S> DiagnosticSink for SortedSink<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn report(&self, diagnostic: Diagnostic) {
+            self.pending.borrow_mut().push(diagnostic);
+        }
+    }
+
+    // Prints diagnostics for a source file to stderr and counts the errors.
+    pub struct ConsoleSink<'a> {
+        file: &'a str,
+        source: &'a [String],
+        color: bool,
+        errors: Cell<usize>,
+    }
+
+    impl<'a> ConsoleSink<'a> {
+        pub fn new(file: &'a str, source: &'a [String], color: bool) -> ConsoleSink<'a> {
+            return ConsoleSink {
+                file,
+                source,
+                color,
+                errors: Cell::new(0),
+            };
+        }
+
+        pub fn error_count(&self) -> usize {
+            return self.errors.get();
+        }
+    }
+
+    impl<'a> DiagnosticSink for ConsoleSink<'a> {
+        fn report(&self, diagnostic: Diagnostic) {
+            if diagnostic.severity == Severity::Error {
+                self.errors.set(self.errors.get() + 1);
+            }
+            eprint!(
+                "{}",
+                render(self.file, self.source, &diagnostic, self.color)
+            );
+        }
+    }
+
+    const BOLD: &str = "\x1b[1m";
+    const RED: &str = "\x1b[1;31m";
+    const MAGENTA: &str = "\x1b[1;35m";
+    const CYAN: &str = "\x1b[1;36m";
+    const GREEN: &str = "\x1b[1;32m";
+    const RESET: &str = "\x1b[0m";
+
+    fn paint(color: bool, code: &str, text: &str) -> String {
+        if color {
+            return format!("{}{}{}", code, text, RESET);
+        }
+        return text.to_string();
+    }
+
+    // One `file:line:col: kind: message` line followed by the source line
+    // with the span underlined by `^~~~`.
+    fn render_message(
+        file: &str,
+        source: &[String],
+        span: Option<Span>,
+        kind: &str,
+        kind_color: &str,
+        message: &str,
+        color: bool,
+    ) -> String {
+        let span = match span {
+            Some(span) => span,
+            None => {
+                return format!(
+                    "{} {} {}\n",
+                    paint(color, BOLD, &format!("{}:", file)),
+                    paint(color, kind_color, &format!("{}:", kind)),
+                    message
+                );
+            }
+        };
+
+        let mut text = format!(
+            "{} {} {}\n",
+            paint(
+                color,
+                BOLD,
+                &format!("{}:{}:{}:", file, span.line, span.start)
+            ),
+            paint(color, kind_color, &format!("{}:", kind)),
+            message
+        );
+        let line = match source.get((span.line as usize).saturating_sub(1)) {
+            Some(line) => line,
+            None => return text,
+        };
+        // Tabs before the span are kept so that the underline lines up with
+        // the source however wide the terminal shows them.
+        let indent: String = line
+            .chars()
+            .take((span.start as usize).saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = span.end.saturating_sub(span.start).max(1) as usize;
+        let underline = format!("^{}", "~".repeat(width - 1));
+        text.push_str(&format!("{}\n", line));
+        text.push_str(&format!("{}{}\n", indent, paint(color, GREEN, &underline)));
+        return text;
+    }
+
+    // Renders a diagnostic the way gcc does, with its code after the
+    // severity: `file:line:col: error[E0003]: message`, then the source line
+    // with the span underlined. A warning names the flag that controls it
+    // after the message, `-Werror=` prefixed if it was made an error. Each
+    // label follows as a note at its own place in the source, then the notes.
+    // With color, the location is bold, the severity red for errors and
+    // magenta for warnings, notes cyan and the underline green.
+    pub fn render(file: &str, source: &[String], diagnostic: &Diagnostic, color: bool) -> String {
+        let severity_color = match diagnostic.severity {
+            Severity::Error => RED,
+            Severity::Warning => MAGENTA,
+        };
+        let kind = format!("{}[{}]", diagnostic.severity, diagnostic.code.id());
+        let message = match (diagnostic.code.flag(), diagnostic.severity) {
+            (Some(flag), Severity::Warning) => format!("{} [-W{}]", diagnostic.message, flag),
+            (Some(flag), Severity::Error) => format!("{} [-Werror={}]", diagnostic.message, flag),
+            (None, _) => diagnostic.message.clone(),
+        };
+        let mut text = render_message(
+            file,
+            source,
+            diagnostic.span,
+            &kind,
+            severity_color,
+            &message,
+            color,
+        );
+        for label in diagnostic.labels.iter() {
+            text.push_str(&render_message(
+                file,
+                source,
+                Some(label.span),
+                "note",
+                CYAN,
+                &label.message,
+                color,
+            ));
+        }
+        for note in diagnostic.notes.iter() {
+            text.push_str(&format!("{} {}\n", paint(color, CYAN, "note:"), note));
+        }
+        return text;
+    }
+
+    // The text printed by `--explain`, or None for an unknown code.
+    pub fn explain(id: &str) -> Option<String> {
+        let code = Code::from_id(id)?;
+        return Some(format!("{}\n\n{}\n", code.id(), code.explanation()));
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        fn source() -> Vec<String> {
+            return vec![
+                String::from("int main(void) {"),
+                String::from("\treturn value;"),
+                String::from("}"),
+            ];
+        }
+
+        #[test]
+        fn test_render_underlines_span() {
+            let diagnostic = Diagnostic::error(
+                Code::Undeclared,
+                String::from("Use of undeclared identifier 'value'"),
+                Some(Span {
+                    line: 2,
+                    start: 9,
+                    end: 14,
+                }),
+            );
+            assert_eq!(
+                render("main.c", &source(), &diagnostic, false),
+                "main.c:2:9: error[E0003]: Use of undeclared identifier 'value'\n\
+                 \treturn value;\n\
+                 \t       ^~~~~\n"
+            );
+        }
+
+        #[test]
+        fn test_render_without_span() {
+            let diagnostic = Diagnostic::warning(
+                Code::DiscardedQualifiers,
+                String::from("Converting 'const int *' to 'int *' discards qualifiers"),
+                None,
+            );
+            assert_eq!(
+                render("main.c", &source(), &diagnostic, false),
+                "main.c: warning[W0001]: Converting 'const int *' to 'int *' discards qualifiers \
+                 [-Wdiscarded-qualifiers]\n"
+            );
+        }
+
+        #[test]
+        fn test_render_labels_and_notes() {
+            let diagnostic = Diagnostic::error(
+                Code::Syntax,
+                String::from("Unexpected end of input: expected close brace"),
+                Some(Span {
+                    line: 3,
+                    start: 2,
+                    end: 3,
+                }),
+            )
+            .with_label(
+                Span {
+                    line: 1,
+                    start: 16,
+                    end: 17,
+                },
+                String::from("to match this open brace"),
+            )
+            .with_note(String::from("the block began here"));
+            assert_eq!(
+                render("main.c", &source(), &diagnostic, false),
+                "main.c:3:2: error[E0001]: Unexpected end of input: expected close brace\n\
+                 }\n \
+                 ^\n\
+                 main.c:1:16: note: to match this open brace\n\
+                 int main(void) {\n\
+                 \x20              ^\n\
+                 note: the block began here\n"
+            );
+        }
+
+        #[test]
+        fn test_render_with_color() {
+            let diagnostic = Diagnostic::error(
+                Code::Syntax,
+                String::from("Unexpected '}': expected semi-colon"),
+                Some(Span {
+                    line: 3,
+                    start: 1,
+                    end: 2,
+                }),
+            );
+            assert_eq!(
+                render("main.c", &source(), &diagnostic, true),
+                "\x1b[1mmain.c:3:1:\x1b[0m \x1b[1;31merror[E0001]:\x1b[0m \
+                 Unexpected '}': expected semi-colon\n\
+                 }\n\
+                 \x1b[1;32m^\x1b[0m\n"
+            );
+        }
+
+        #[test]
+        fn test_codes_are_unique() {
+            for (i, code) in CODES.iter().enumerate() {
+                assert_eq!(Code::from_id(code.id()), Some(*code));
+                assert!(CODES[i + 1..].iter().all(|other| other.id() != code.id()));
+            }
+            assert!(explain("E0012")
+                .unwrap()
+                .starts_with("E0012\n\nThe context requires"));
+            assert_eq!(explain("E9999"), None);
+        }
+
+        fn warning(code: Code) -> Diagnostic {
+            return Diagnostic::warning(code, String::from("warning"), None);
+        }
+
+        #[test]
+        fn test_warning_groups() {
+            let mut options = WarningOptions::new();
+            assert!(options.is_enabled(Code::DiscardedQualifiers));
+            assert!(!options.is_enabled(Code::UnusedVariable));
+
+            assert!(options.apply("-Wall"));
+            assert!(options.is_enabled(Code::UnusedVariable));
+            assert!(options.is_enabled(Code::MissingReturn));
+            assert!(!options.is_enabled(Code::SignCompare));
+
+            assert!(options.apply("-Wextra"));
+            assert!(options.is_enabled(Code::SignCompare));
+            assert!(options.is_enabled(Code::Unreachable));
+            assert!(!options.is_enabled(Code::Shadow));
+            assert!(!options.is_enabled(Code::ImplicitConversion));
+        }
+
+        // A later flag overrides an earlier one.
+        #[test]
+        fn test_warning_flag_order() {
+            let mut options = WarningOptions::new();
+            assert!(options.apply("-Wno-unused-variable"));
+            assert!(options.apply("-Wall"));
+            assert!(options.is_enabled(Code::UnusedVariable));
+            assert!(options.apply("-Wno-unused-variable"));
+            assert!(!options.is_enabled(Code::UnusedVariable));
+            assert!(options.is_enabled(Code::UnusedFunction));
+
+            assert!(options.apply("-Wshadow"));
+            assert!(options.is_enabled(Code::Shadow));
+            assert!(options.apply("-Wno-discarded-qualifiers"));
+            assert_eq!(options.filter(warning(Code::DiscardedQualifiers)), None);
+
+            assert!(!options.apply("-Wno-such-warning"));
+            assert!(!options.apply("-Wunused"));
+            assert!(WarningOptions::is_flag("-Wunused"));
+            assert!(!WarningOptions::is_flag("main.c"));
+        }
+
+        #[test]
+        fn test_warnings_as_errors_and_suppressed() {
+            let mut options = WarningOptions::new();
+            options.apply("-Werror");
+            let diagnostic = options.filter(warning(Code::DiscardedQualifiers)).unwrap();
+            assert_eq!(diagnostic.severity, Severity::Error);
+            assert_eq!(
+                render("main.c", &source(), &diagnostic, false),
+                "main.c: error[W0001]: warning [-Werror=discarded-qualifiers]\n"
+            );
+            options.apply("-Wno-error");
+            let diagnostic = options.filter(warning(Code::DiscardedQualifiers)).unwrap();
+            assert_eq!(diagnostic.severity, Severity::Warning);
+
+            // -w drops every warning but no error.
+            options.apply("-w");
+            assert_eq!(options.filter(warning(Code::DiscardedQualifiers)), None);
+            let error = Diagnostic::error(Code::Syntax, String::from("error"), None);
+            assert_eq!(options.filter(error.clone()), Some(error));
+        }
+    }
+}
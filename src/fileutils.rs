@@ -1,4 +1,4 @@
-use std::io::{BufRead, BufReader};
+use std::fs;
 use std::path::Path;
 use std::fs::File;
 use std::io::Write;
@@ -7,29 +7,30 @@ pub fn lines_from_file<P>(filename: P) -> Vec<String>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename).expect("no such file");
-    let buf = BufReader::new(file);
-    buf.lines()
-        .map(|l| l.expect("Could not parse line"))
+    // Bytes that are not valid UTF-8 are replaced with U+FFFD rather than
+    // rejected here; the lexer makes a stray token of the replacement and the
+    // parser reports it.
+    let bytes = fs::read(filename).expect("no such file");
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .map(String::from)
         .collect()
 }
 
-pub fn write_lines<P>(filename: P, lines: &Vec<String>)
+pub fn write_lines<P>(filename: P, lines: &[String])
 where
     P: AsRef<Path>,
 {
     let mut file = File::create(filename).expect("error creating file");
-    for i in lines.iter().rev() {
+    for i in lines.iter() {
         let result = file.write_all(i.as_bytes());
-        match result.err() {
-            Some(_l) => panic!("error writing file"),
-            _ => {}
+        if result.is_err() {
+            panic!("error writing file");
         }
 
-        let res2 = file.write(String::from("\r\n").as_bytes());
-        match res2.err() {
-            Some(_l) => panic!("error writing to file"),
-            _ => {}
+        let res2 = file.write_all(b"\n");
+        if res2.is_err() {
+            panic!("error writing to file");
         }
     }
 }
@@ -3,6 +3,7 @@
 pub mod lexer {
     use std::fmt::*;
     use std::path::Path;
+    use diagnostics::diagnostics::Span;
     use fileutils::lines_from_file;
     use std::iter::FromIterator;
     use std::*;
@@ -13,19 +14,107 @@ pub mod lexer {
         CloseBrace,
         OpenParen,
         CloseParen,
+        OpenBracket,
+        CloseBracket,
         SemiColon,
         KeywordInt,
+        KeywordChar,
+        KeywordShort,
+        KeywordLong,
+        KeywordSigned,
+        KeywordUnsigned,
+        KeywordFloat,
+        KeywordDouble,
+        KeywordVoid,
+        KeywordStatic,
+        KeywordExtern,
+        KeywordStruct,
+        KeywordUnion,
+        KeywordEnum,
+        KeywordTypedef,
+        KeywordConst,
+        KeywordVolatile,
+        KeywordRestrict,
         KeywordRet,
+        KeywordIf,
+        KeywordElse,
+        KeywordFor,
+        KeywordWhile,
+        KeywordDo,
+        KeywordBreak,
+        KeywordContinue,
+        KeywordSwitch,
+        KeywordCase,
+        KeywordDefault,
+        KeywordGoto,
+        KeywordSizeof,
+        KeywordAlignof,
+        KeywordStaticAssert,
+        // The builtins <stdarg.h> is made of.
+        KeywordVaList,
+        KeywordVaStart,
+        KeywordVaArg,
+        KeywordVaEnd,
+        KeywordVaCopy,
         Identifier,
         Intliteral,
+        Floatliteral,
+        // The data of string and character literals is their spelling,
+        // including the quotes.
+        Stringliteral,
+        Charliteral,
+        Plus,
+        Minus,
+        Star,
+        Slash,
+        Percent,
+        Tilde,
+        Bang,
+        Assign,
+        Equal,
+        NotEqual,
+        Less,
+        LessEqual,
+        Greater,
+        GreaterEqual,
+        LogicalAnd,
+        LogicalOr,
+        Ampersand,
+        Comma,
+        Dot,
+        Arrow,
+        Colon,
+        Ellipsis,
+        // A character that cannot start a token, such as `@` or a byte that
+        // is not valid UTF-8; the data is the character.
+        Stray,
     }
 
+    // How diagnostics name a token type: its spelling in quotes, or what
+    // kind of token it is when that varies.
     impl Display for TokenType {
         fn fmt(&self, f: &mut Formatter) -> Result {
-            write!(f, "{:?}", self)
+            let spelling = PUNCTUATORS
+                .iter()
+                .chain(KEYWORDS.iter())
+                .find(|p| p.1 == *self)
+                .map(|p| p.0);
+            if let Some(spelling) = spelling {
+                return write!(f, "'{}'", spelling);
+            }
+            let kind = match *self {
+                TokenType::Identifier => "identifier",
+                TokenType::Intliteral => "integer constant",
+                TokenType::Floatliteral => "floating constant",
+                TokenType::Stringliteral => "string literal",
+                TokenType::Charliteral => "character constant",
+                _ => "stray character",
+            };
+            write!(f, "{}", kind)
         }
     }
 
+    #[derive(Clone)]
     pub struct TokenInfo {
         pub token_type: TokenType,
         source_line: u32,
@@ -34,6 +123,106 @@ pub mod lexer {
         pub data: String,
     }
 
+    // A token as diagnostics name it, with the text of an identifier or
+    // literal.
+    impl Display for TokenInfo {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            match self.token_type {
+                TokenType::Stringliteral | TokenType::Charliteral => {
+                    write!(f, "{} {}", self.token_type, self.data)
+                }
+                _ if self.data.is_empty() => write!(f, "{}", self.token_type),
+                _ => write!(f, "{} '{}'", self.token_type, self.data),
+            }
+        }
+    }
+
+    impl TokenInfo {
+        // Where the token is in the source.
+        pub fn span(&self) -> Span {
+            return Span {
+                line: self.source_line,
+                start: self.start_char,
+                end: self.end_char,
+            };
+        }
+    }
+
+    // Longer punctuators must come before their prefixes so that "<=" is not
+    // lexed as "<" followed by "=".
+    static PUNCTUATORS: &[(&str, TokenType)] = &[
+        ("==", TokenType::Equal),
+        ("!=", TokenType::NotEqual),
+        ("<=", TokenType::LessEqual),
+        (">=", TokenType::GreaterEqual),
+        ("&&", TokenType::LogicalAnd),
+        ("||", TokenType::LogicalOr),
+        ("->", TokenType::Arrow),
+        ("...", TokenType::Ellipsis),
+        ("(", TokenType::OpenParen),
+        (")", TokenType::CloseParen),
+        ("{", TokenType::OpenBrace),
+        ("}", TokenType::CloseBrace),
+        ("[", TokenType::OpenBracket),
+        ("]", TokenType::CloseBracket),
+        (";", TokenType::SemiColon),
+        ("+", TokenType::Plus),
+        ("-", TokenType::Minus),
+        ("*", TokenType::Star),
+        ("/", TokenType::Slash),
+        ("%", TokenType::Percent),
+        ("~", TokenType::Tilde),
+        ("&", TokenType::Ampersand),
+        ("!", TokenType::Bang),
+        ("=", TokenType::Assign),
+        ("<", TokenType::Less),
+        (">", TokenType::Greater),
+        (",", TokenType::Comma),
+        (".", TokenType::Dot),
+        (":", TokenType::Colon),
+    ];
+
+    static KEYWORDS: &[(&str, TokenType)] = &[
+        ("int", TokenType::KeywordInt),
+        ("char", TokenType::KeywordChar),
+        ("short", TokenType::KeywordShort),
+        ("long", TokenType::KeywordLong),
+        ("signed", TokenType::KeywordSigned),
+        ("unsigned", TokenType::KeywordUnsigned),
+        ("float", TokenType::KeywordFloat),
+        ("double", TokenType::KeywordDouble),
+        ("void", TokenType::KeywordVoid),
+        ("static", TokenType::KeywordStatic),
+        ("extern", TokenType::KeywordExtern),
+        ("struct", TokenType::KeywordStruct),
+        ("union", TokenType::KeywordUnion),
+        ("enum", TokenType::KeywordEnum),
+        ("typedef", TokenType::KeywordTypedef),
+        ("const", TokenType::KeywordConst),
+        ("volatile", TokenType::KeywordVolatile),
+        ("restrict", TokenType::KeywordRestrict),
+        ("return", TokenType::KeywordRet),
+        ("if", TokenType::KeywordIf),
+        ("else", TokenType::KeywordElse),
+        ("for", TokenType::KeywordFor),
+        ("while", TokenType::KeywordWhile),
+        ("do", TokenType::KeywordDo),
+        ("break", TokenType::KeywordBreak),
+        ("continue", TokenType::KeywordContinue),
+        ("switch", TokenType::KeywordSwitch),
+        ("case", TokenType::KeywordCase),
+        ("default", TokenType::KeywordDefault),
+        ("goto", TokenType::KeywordGoto),
+        ("sizeof", TokenType::KeywordSizeof),
+        ("_Alignof", TokenType::KeywordAlignof),
+        ("_Static_assert", TokenType::KeywordStaticAssert),
+        ("__builtin_va_list", TokenType::KeywordVaList),
+        ("__builtin_va_start", TokenType::KeywordVaStart),
+        ("__builtin_va_arg", TokenType::KeywordVaArg),
+        ("__builtin_va_end", TokenType::KeywordVaEnd),
+        ("__builtin_va_copy", TokenType::KeywordVaCopy),
+    ];
+
     pub fn lex<P>(filename: P) -> Vec<TokenInfo>
     where
         P: AsRef<Path>,
@@ -45,14 +234,34 @@ pub mod lexer {
     }
 
     fn lexinternal(lines: Vec<String>, collector: &mut Vec<TokenInfo>) {
-        let mut line_num: u32 = 1;
-        for line in lines {
-            get_tokens(&line, line_num, collector);
-            line_num = line_num + 1;
+        for (line_num, line) in (1..).zip(lines.iter()) {
+            get_tokens(line, line_num, collector);
         }
     }
 
-    fn get_tokens(line: &String, line_num: u32, collector: &mut Vec<TokenInfo>) {
+    fn push_token(
+        collector: &mut Vec<TokenInfo>,
+        token_type: TokenType,
+        line_num: u32,
+        start_char: u32,
+        end_char: u32,
+        data: String,
+    ) {
+        let new_token_info = TokenInfo {
+            token_type,
+            source_line: line_num,
+            start_char,
+            end_char,
+            data,
+        };
+        collector.push(new_token_info);
+    }
+
+    fn keyword_type(word: &str) -> Option<TokenType> {
+        return KEYWORDS.iter().find(|k| k.0 == word).map(|k| k.1.clone());
+    }
+
+    fn get_tokens(line: &str, line_num: u32, collector: &mut Vec<TokenInfo>) {
         let mut bb = LexerState::from(line.chars());
         let mut slast_char;
 
@@ -63,126 +272,132 @@ pub mod lexer {
         loop {
             //TODO: lastchar in LexerState
             //TODO: improve EOD detection using LexerState
-            //TODO: remove repeated token init code
 
-            let classified = classify(&slast_char);
+            // A period followed by a digit starts a floating literal like .5.
+            let starts_fraction =
+                slast_char == '.' && bb.char_iter.clone().next().is_some_and(|c| is_number(&c));
+            let classified = if starts_fraction {
+                CharClassified::Number(slast_char)
+            } else {
+                classify(&slast_char)
+            };
             let mut advanced: bool = false;
 
             match classified {
                 CharClassified::Alpha(_al) => {
                     //TODO: use AL rather than lastchar
                     let result = readallconf(&mut bb, &mut slast_char, |x: &char| {
-                        return is_word_char(x);
+                        return is_word_char(x) || is_number(x);
                     });
 
-                    match result.0.as_str() {
-                        "int" => {
-                            //println!("kw: int");
-                            let new_token_info = TokenInfo {
-                                token_type: TokenType::KeywordInt,
-                                source_line: line_num,
-                                start_char: result.2,
-                                end_char: result.3,
-                                data: String::new(),
-                            };
-                            collector.push(new_token_info);
+                    match keyword_type(result.0.as_str()) {
+                        Some(keyword) => {
+                            push_token(
+                                collector,
+                                keyword,
+                                line_num,
+                                result.2,
+                                result.3,
+                                String::new(),
+                            );
                         }
-                        "return" => {
-                            //println!("kw: return");
-                            let new_token_info = TokenInfo {
-                                token_type: TokenType::KeywordRet,
-                                source_line: line_num,
-                                start_char: result.2,
-                                end_char: result.3,
-                                data: String::new(),
-                            };
-                            collector.push(new_token_info);
-                        }
-                        _ => {
-                            //println!("Found Char Literal:{}", result.0);
-                            let new_token_info = TokenInfo {
-                                token_type: TokenType::Identifier,
-                                source_line: line_num,
-                                start_char: result.2,
-                                end_char: result.3,
-                                data: result.0,
-                            };
-                            collector.push(new_token_info);
+                        None => {
+                            push_token(
+                                collector,
+                                TokenType::Identifier,
+                                line_num,
+                                result.2,
+                                result.3,
+                                result.0,
+                            );
                         }
                     }
 
                     advanced = result.1;
                 }
-                CharClassified::Number(_al) => {
-                    let result =
-                        readallconf(&mut bb, &mut slast_char, |x: &char| return is_number(x));
-                    //println!("Found number:{}", result.0);
-                    let new_token_info = TokenInfo {
-                        token_type: TokenType::Intliteral,
-                        source_line: line_num,
-                        start_char: result.2,
-                        end_char: result.3,
-                        data: result.0,
-                    };
-                    collector.push(new_token_info);
+                CharClassified::Number(al) => {
+                    // Letters are kept for hexadecimal digits and suffixes;
+                    // the parser decides what the literal means. A decimal
+                    // literal with a period or an exponent, whose sign is part
+                    // of the literal, is floating.
+                    let mut text = al.to_string();
+                    let result = readallconf(&mut bb, &mut slast_char, |x: &char| {
+                        let hex = text.starts_with("0x") || text.starts_with("0X");
+                        let exponent_sign = (*x == '+' || *x == '-')
+                            && !hex
+                            && (text.ends_with('e') || text.ends_with('E'));
+                        let accepted =
+                            is_word_char(x) || is_number(x) || *x == '.' || exponent_sign;
+                        if accepted {
+                            text.push(*x);
+                        }
+                        return accepted;
+                    });
+                    let hex = result.0.starts_with("0x") || result.0.starts_with("0X");
+                    let floating = !hex && result.0.contains(['.', 'e', 'E']);
+                    push_token(
+                        collector,
+                        if floating {
+                            TokenType::Floatliteral
+                        } else {
+                            TokenType::Intliteral
+                        },
+                        line_num,
+                        result.2,
+                        result.3,
+                        result.0,
+                    );
                     advanced = result.1;
                 }
-                CharClassified::Symbol('(') => {
-                    //println!("Found ( {}", bb.cnt);
-                    let new_token_info = TokenInfo {
-                        token_type: TokenType::OpenParen,
-                        source_line: line_num,
-                        start_char: bb.cnt,
-                        end_char: bb.cnt + 1,
-                        data: String::new(),
-                    };
-                    collector.push(new_token_info);
-                }
-                CharClassified::Symbol(')') => {
-                    //println!("Found )");
-                    let new_token_info = TokenInfo {
-                        token_type: TokenType::CloseParen,
-                        source_line: line_num,
-                        start_char: bb.cnt,
-                        end_char: bb.cnt + 1,
-                        data: String::new(),
-                    };
-                    collector.push(new_token_info);
-                }
-                CharClassified::Symbol('{') => {
-                    //println!("Found {{");
-                    let new_token_info = TokenInfo {
-                        token_type: TokenType::OpenBrace,
-                        source_line: line_num,
-                        start_char: bb.cnt,
-                        end_char: bb.cnt + 1,
-                        data: String::new(),
-                    };
-                    collector.push(new_token_info);
-                }
-                CharClassified::Symbol('}') => {
-                    //println!("Found }}");
-                    let new_token_info = TokenInfo {
-                        token_type: TokenType::CloseBrace,
-                        source_line: line_num,
-                        start_char: bb.cnt,
-                        end_char: bb.cnt + 1,
-                        data: String::new(),
+                // A literal runs to the matching quote on the same line; an
+                // escaped quote does not end it. The parser decodes the escape
+                // sequences and reports a missing closing quote.
+                CharClassified::Symbol(quote) if quote == '"' || quote == '\'' => {
+                    let start = bb.cnt;
+                    let mut text = quote.to_string();
+                    let mut escaped = false;
+                    while let Some(c) = bb.next() {
+                        text.push(c);
+                        if c == quote && !escaped {
+                            break;
+                        }
+                        escaped = c == '\\' && !escaped;
+                    }
+                    let token_type = if quote == '"' {
+                        TokenType::Stringliteral
+                    } else {
+                        TokenType::Charliteral
                     };
-                    collector.push(new_token_info);
+                    push_token(collector, token_type, line_num, start, bb.cnt + 1, text);
                 }
-                CharClassified::Symbol(';') => {
-                    //println!("Found ;");
-                    let new_token_info = TokenInfo {
-                        token_type: TokenType::SemiColon,
-                        source_line: line_num,
-                        start_char: bb.cnt,
-                        end_char: bb.cnt + 1,
-                        data: String::new(),
-                    };
-                    collector.push(new_token_info);
+                CharClassified::Symbol(al) => {
+                    let ahead: String = iter::once(al).chain(bb.char_iter.clone()).collect();
+                    let start = bb.cnt;
+                    match PUNCTUATORS.iter().find(|p| ahead.starts_with(p.0)) {
+                        Some(&(text, ref token_type)) => {
+                            for _ in 1..text.len() {
+                                bb.next();
+                            }
+                            push_token(
+                                collector,
+                                token_type.clone(),
+                                line_num,
+                                start,
+                                bb.cnt + 1,
+                                String::new(),
+                            );
+                        }
+                        None if is_space(&al) => {}
+                        None => push_token(
+                            collector,
+                            TokenType::Stray,
+                            line_num,
+                            start,
+                            start + 1,
+                            al.to_string(),
+                        ),
+                    }
                 }
-                CharClassified::Symbol(_al) => {} //println!("Found other symbol {}", al),
             }
 
             if !advanced {
@@ -194,9 +409,9 @@ pub mod lexer {
         }
     }
 
-    fn readallconf<F>(iter: &mut LexerState, last: &mut char, f: F) -> (String, bool, u32, u32)
+    fn readallconf<F>(iter: &mut LexerState, last: &mut char, mut f: F) -> (String, bool, u32, u32)
     where
-        F: Fn(&char) -> bool,
+        F: FnMut(&char) -> bool,
     {
         let mut literal: Vec<char> = Vec::new();
         let start = iter.cnt;
@@ -210,13 +425,15 @@ pub mod lexer {
                 }
                 Some(i) => *last = i,
             }
-            if f(&last) {
+            if f(last) {
                 literal.push(*last);
             } else {
                 break;
             }
         }
-        let end = iter.cnt;
+        // The character that ended the token is one past it, unless the line
+        // ended first.
+        let end = if advanced { iter.cnt } else { iter.cnt + 1 };
         let maa = String::from_iter(literal);
         return (maa, advanced, start, end);
     }
@@ -245,7 +462,7 @@ pub mod lexer {
                     return None;
                 }
                 Some(t) => {
-                    self.cnt = self.cnt + 1;
+                    self.cnt += 1;
                     return Some(t);
                 }
             }
@@ -258,8 +475,8 @@ pub mod lexer {
         Symbol(char),
     }
 
-    static ALPHA: &'static str = "abcdefghijklmnopqrstuvwzyzABCDEFGHIJKLMNOPQRSTUVWZYZ";
-    static NUMERIC: &'static str = "0123456789";
+    static ALPHA: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_";
+    static NUMERIC: &str = "0123456789";
 
     fn classify(input: &char) -> CharClassified {
         if is_word_char(input) {
@@ -272,15 +489,20 @@ pub mod lexer {
     }
 
     fn is_word_char(input: &char) -> bool {
-        let foo = ALPHA.find(*input);
-        return foo != None;
+        return ALPHA.contains(*input);
     }
 
     fn is_number(input: &char) -> bool {
-        let foo = NUMERIC.find(*input);
-        return foo != None;
+        return NUMERIC.contains(*input);
     }
 
+    // The white space characters of C, other than the newlines that end the
+    // lines.
+    fn is_space(input: &char) -> bool {
+        return " \t\r\x0b\x0c".contains(*input);
+    }
+
+    #[cfg(test)]
     mod tests {
         use std::fs;
         //#[ignore]
@@ -304,20 +526,236 @@ pub mod lexer {
             }
         }
 
+        #[test]
+        fn test_operators() {
+            let lines = vec![String::from(r"a_1=b<=c==!d&&e||-f[2].g->h;")];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let expected = vec![
+                super::TokenType::Identifier,
+                super::TokenType::Assign,
+                super::TokenType::Identifier,
+                super::TokenType::LessEqual,
+                super::TokenType::Identifier,
+                super::TokenType::Equal,
+                super::TokenType::Bang,
+                super::TokenType::Identifier,
+                super::TokenType::LogicalAnd,
+                super::TokenType::Identifier,
+                super::TokenType::LogicalOr,
+                super::TokenType::Minus,
+                super::TokenType::Identifier,
+                super::TokenType::OpenBracket,
+                super::TokenType::Intliteral,
+                super::TokenType::CloseBracket,
+                super::TokenType::Dot,
+                super::TokenType::Identifier,
+                super::TokenType::Arrow,
+                super::TokenType::Identifier,
+                super::TokenType::SemiColon,
+            ];
+
+            assert_eq!(collector.len(), expected.len());
+            for (token, expected_type) in collector.iter().zip(expected.iter()) {
+                assert_eq!(&token.token_type, expected_type);
+            }
+            assert_eq!(collector[0].data, "a_1");
+        }
+
+        #[test]
+        fn test_stray_characters() {
+            let lines = vec![String::from("a\t@ b\x0c$;")];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let expected = [
+                (super::TokenType::Identifier, "a"),
+                (super::TokenType::Stray, "@"),
+                (super::TokenType::Identifier, "b"),
+                (super::TokenType::Stray, "$"),
+                (super::TokenType::SemiColon, ""),
+            ];
+
+            assert_eq!(collector.len(), expected.len());
+            for (token, &(ref expected_type, data)) in collector.iter().zip(expected.iter()) {
+                assert_eq!(&token.token_type, expected_type);
+                assert_eq!(token.data, data);
+            }
+            assert_eq!(collector[1].span().start, 3);
+        }
+
+        // Diagnostics name tokens as they are spelled in the source.
+        #[test]
+        fn test_token_names() {
+            let lines = vec![String::from(r#"return ( x 12 "s" 'c' ->;"#)];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let names: Vec<String> = collector.iter().map(|t| t.to_string()).collect();
+            assert_eq!(
+                names,
+                vec![
+                    "'return'",
+                    "'('",
+                    "identifier 'x'",
+                    "integer constant '12'",
+                    "string literal \"s\"",
+                    "character constant 'c'",
+                    "'->'",
+                    "';'",
+                ]
+            );
+            assert_eq!(super::TokenType::CloseBrace.to_string(), "'}'");
+        }
+
+        #[test]
+        fn test_integer_literals() {
+            let lines = vec![String::from(r"unsigned long x=0x1fUL+10ll;")];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let expected = [
+                super::TokenType::KeywordUnsigned,
+                super::TokenType::KeywordLong,
+                super::TokenType::Identifier,
+                super::TokenType::Assign,
+                super::TokenType::Intliteral,
+                super::TokenType::Plus,
+                super::TokenType::Intliteral,
+                super::TokenType::SemiColon,
+            ];
+
+            assert_eq!(collector.len(), expected.len());
+            for (token, expected_type) in collector.iter().zip(expected.iter()) {
+                assert_eq!(&token.token_type, expected_type);
+            }
+            assert_eq!(collector[4].data, "0x1fUL");
+            assert_eq!(collector[6].data, "10ll");
+        }
+
+        #[test]
+        fn test_floating_literals() {
+            let lines = vec![String::from(r"x=1.5+.25f-1e-3*2.E+2-0x1e-a.b;")];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let expected = [
+                (super::TokenType::Identifier, "x"),
+                (super::TokenType::Assign, ""),
+                (super::TokenType::Floatliteral, "1.5"),
+                (super::TokenType::Plus, ""),
+                (super::TokenType::Floatliteral, ".25f"),
+                (super::TokenType::Minus, ""),
+                (super::TokenType::Floatliteral, "1e-3"),
+                (super::TokenType::Star, ""),
+                (super::TokenType::Floatliteral, "2.E+2"),
+                (super::TokenType::Minus, ""),
+                (super::TokenType::Intliteral, "0x1e"),
+                (super::TokenType::Minus, ""),
+                (super::TokenType::Identifier, "a"),
+                (super::TokenType::Dot, ""),
+                (super::TokenType::Identifier, "b"),
+                (super::TokenType::SemiColon, ""),
+            ];
+
+            assert_eq!(collector.len(), expected.len());
+            for (token, &(ref expected_type, data)) in collector.iter().zip(expected.iter()) {
+                assert_eq!(&token.token_type, expected_type);
+                assert_eq!(token.data, data);
+            }
+        }
+
+        #[test]
+        fn test_string_literals() {
+            let lines = vec![String::from(
+                r#"f("a \"b\" \\", 'c', '\'', "x;y" "z", __builtin_va_arg(ap, int), "open"#,
+            )];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let expected = [
+                (super::TokenType::Identifier, "f"),
+                (super::TokenType::OpenParen, ""),
+                (super::TokenType::Stringliteral, r#""a \"b\" \\""#),
+                (super::TokenType::Comma, ""),
+                (super::TokenType::Charliteral, "'c'"),
+                (super::TokenType::Comma, ""),
+                (super::TokenType::Charliteral, r"'\''"),
+                (super::TokenType::Comma, ""),
+                (super::TokenType::Stringliteral, r#""x;y""#),
+                (super::TokenType::Stringliteral, r#""z""#),
+                (super::TokenType::Comma, ""),
+                (super::TokenType::KeywordVaArg, ""),
+                (super::TokenType::OpenParen, ""),
+                (super::TokenType::Identifier, "ap"),
+                (super::TokenType::Comma, ""),
+                (super::TokenType::KeywordInt, ""),
+                (super::TokenType::CloseParen, ""),
+                (super::TokenType::Comma, ""),
+                (super::TokenType::Stringliteral, r#""open"#),
+            ];
+
+            assert_eq!(collector.len(), expected.len());
+            for (token, &(ref expected_type, data)) in collector.iter().zip(expected.iter()) {
+                assert_eq!(&token.token_type, expected_type);
+                assert_eq!(token.data, data);
+            }
+        }
+
+        // Columns count from 1 and each span ends one past its last
+        // character, also for a token that ends the line.
+        #[test]
+        fn test_token_spans() {
+            let lines = vec![String::from("int main"), String::from("\tx <= 'c' + 42")];
+
+            let mut collector: Vec<super::TokenInfo> = Vec::new();
+            super::lexinternal(lines, &mut collector);
+
+            let spans: Vec<(u32, u32, u32)> = collector
+                .iter()
+                .map(|token| {
+                    let span = token.span();
+                    (span.line, span.start, span.end)
+                })
+                .collect();
+            assert_eq!(
+                spans,
+                vec![
+                    (1, 1, 4),
+                    (1, 5, 9),
+                    (2, 2, 3),
+                    (2, 4, 6),
+                    (2, 7, 10),
+                    (2, 11, 12),
+                    (2, 13, 15),
+                ]
+            );
+        }
+
         //#[ignore]
         #[test]
         fn test_succeeding() {
-            let paths = fs::read_dir("test\\valid").unwrap();
+            let paths = fs::read_dir("test/valid").unwrap();
 
             for path in paths {
-                let thepath = path.unwrap();
-                println!("Name: {}", thepath.path().display());
-                let token_list = super::lex(thepath.path());
+                let thepath = path.unwrap().path();
+                if thepath.extension().and_then(|e| e.to_str()) != Some("c") {
+                    continue;
+                }
+                println!("Name: {}", thepath.display());
+                let token_list = super::lex(&thepath);
                 verify_stage_one_tokens(&token_list);
             }
         }
 
-        fn verify_stage_one_tokens(tokenlist: &Vec<super::TokenInfo>) -> bool {
+        fn verify_stage_one_tokens(tokenlist: &[super::TokenInfo]) -> bool {
             let success_token_types = vec![
                 super::TokenType::KeywordInt,
                 super::TokenType::Identifier,
@@ -330,7 +768,7 @@ pub mod lexer {
                 super::TokenType::CloseBrace,
             ];
 
-            for i in tokenlist.into_iter() {
+            for i in tokenlist.iter() {
                 println!("{}:{}", &i.token_type, &i.data)
             }
 
@@ -0,0 +1,642 @@
+#![allow(dead_code)]
+
+pub mod lint {
+
+    use std::collections::HashSet;
+    use ast::ast::{
+        constant_value, evaluate_constant, find_all, floating_value, walk, AstNode, AstNodeType,
+        overflow_warning, Visitor,
+    };
+    use diagnostics::diagnostics::{Code, Diagnostic, DiagnosticSink, Span};
+    use symtab::symtab::{Linkage, SymbolTable};
+    use types::types::Type;
+
+    // Reports the warnings about code that is valid but probably not what
+    // was meant: unused variables and functions, functions that can end
+    // without returning a value, code that can never run, implicit
+    // conversions that may change a value, comparisons between signed
+    // and unsigned integers, and constant operations that overflow or divide
+    // by zero. Every warning is reported; the sink decides
+    // which of them the user asked for.
+    pub fn check<S>(program: &AstNode, symbols: &SymbolTable, sink: &S)
+    where
+        S: DiagnosticSink,
+    {
+        let mut lint = Lint {
+            symbols,
+            sink,
+            context: None,
+        };
+        for item in program.children.iter() {
+            if item.node_type == AstNodeType::Function {
+                lint.check_function(item);
+            }
+        }
+        lint.check_unused_functions(program);
+        lint.visit(program);
+    }
+
+    // Checks the implicit conversions in the initializer of a static object,
+    // which the parser folds into the object's initial contents before the
+    // program is checked. Overflow in the initializer is reported as it is
+    // folded.
+    pub fn check_static_initializer<S>(
+        initializer: &AstNode,
+        symbols: &SymbolTable,
+        span: Option<Span>,
+        sink: &S,
+    ) where
+        S: DiagnosticSink,
+    {
+        let lint = Lint {
+            symbols,
+            sink,
+            context: span,
+        };
+        for cast in find_all(initializer, AstNodeType::Cast) {
+            if cast.data == "implicit" {
+                lint.check_conversion(cast);
+            }
+        }
+    }
+
+    // The expression with any implicit conversion the language applied to it
+    // undone.
+    fn unconverted(node: &AstNode) -> &AstNode {
+        if node.node_type == AstNodeType::Cast && node.data == "implicit" {
+            return unconverted(&node.children[0]);
+        }
+        return node;
+    }
+
+    fn is_always_true(condition: &AstNode) -> bool {
+        return constant_value(condition).is_some_and(|value| value != 0);
+    }
+
+    // Whether a node of the type is in the statement, not counting those
+    // inside any statement of the types that capture it, such as a break
+    // inside an inner loop.
+    fn contains(node: &AstNode, node_type: AstNodeType, captured_by: &[AstNodeType]) -> bool {
+        return node.children.iter().any(|child| {
+            if child.node_type == node_type {
+                return true;
+            }
+            if captured_by.contains(&child.node_type) {
+                return false;
+            }
+            return contains(child, node_type.clone(), captured_by);
+        });
+    }
+
+    static LOOPS: &[AstNodeType] = &[AstNodeType::While, AstNodeType::DoWhile, AstNodeType::For];
+    static BREAKABLE: &[AstNodeType] = &[
+        AstNodeType::While,
+        AstNodeType::DoWhile,
+        AstNodeType::For,
+        AstNodeType::Switch,
+    ];
+
+    fn breaks_out(body: &AstNode) -> bool {
+        return contains(body, AstNodeType::Break, BREAKABLE);
+    }
+
+    fn is_labeled(statement: &AstNode) -> bool {
+        return statement.node_type == AstNodeType::Label
+            || statement.node_type == AstNodeType::Case
+            || statement.node_type == AstNodeType::Default;
+    }
+
+    // Whether control can reach the end of the statement: it does not leave
+    // by return, goto, break or continue on every path, and is not a loop
+    // that never ends.
+    fn completes(statement: &AstNode) -> bool {
+        let children = &statement.children;
+        match statement.node_type {
+            AstNodeType::Return
+            | AstNodeType::Goto
+            | AstNodeType::Break
+            | AstNodeType::Continue => return false,
+            AstNodeType::Block => {
+                // A labeled statement can be jumped to even after one that
+                // does not complete.
+                let mut reachable = true;
+                for child in children.iter() {
+                    if is_labeled(child) {
+                        reachable = true;
+                    }
+                    if reachable {
+                        reachable = completes(child);
+                    }
+                }
+                return reachable;
+            }
+            AstNodeType::If => {
+                return children.len() < 3 || completes(&children[1]) || completes(&children[2]);
+            }
+            AstNodeType::While => {
+                return !is_always_true(&children[0]) || breaks_out(&children[1]);
+            }
+            AstNodeType::For => {
+                return !is_always_true(&children[1]) || breaks_out(&children[3]);
+            }
+            AstNodeType::DoWhile => {
+                let body = &children[0];
+                let repeats = completes(body) || contains(body, AstNodeType::Continue, LOOPS);
+                return (repeats && !is_always_true(&children[1])) || breaks_out(body);
+            }
+            AstNodeType::Switch => {
+                let body = &children[1];
+                return !contains(body, AstNodeType::Default, &[AstNodeType::Switch])
+                    || completes(body)
+                    || breaks_out(body);
+            }
+            AstNodeType::Label | AstNodeType::Case | AstNodeType::Default => {
+                return completes(children.last().unwrap());
+            }
+            _ => return true,
+        }
+    }
+
+    // Whether converting the expression to the type may change its value.
+    // Constants are only reported if they do not convert exactly.
+    fn may_change_value(value: &AstNode, to: &Type) -> bool {
+        let from = &value.ty;
+        if from.is_floating() && to.is_integer() {
+            return match floating_value(value) {
+                Some(constant) => {
+                    constant.fract() != 0.0 || to.truncate(constant as i64) as f64 != constant
+                }
+                None => true,
+            };
+        }
+        if from.is_integer() && to.is_integer() {
+            return match constant_value(value) {
+                Some(constant) => to.truncate(constant) != constant,
+                None => to.size() < from.size(),
+            };
+        }
+        if *from == Type::Double && *to == Type::Float {
+            return match floating_value(value) {
+                Some(constant) => f64::from(constant as f32) != constant,
+                None => true,
+            };
+        }
+        if from.is_integer() && to.is_floating() {
+            // float holds 24 bits of precision and double 53.
+            let precision = if *to == Type::Float { 24 } else { 53 };
+            return match constant_value(value) {
+                Some(constant) => constant.unsigned_abs() >= 1 << precision,
+                None => from.size() * 8 > precision,
+            };
+        }
+        return false;
+    }
+
+    // Whether the floating value, truncated toward zero, is in the range of
+    // the integer type.
+    fn fits_integer(value: f64, ty: &Type) -> bool {
+        let whole = value.trunc();
+        if ty.size() == 8 && !ty.is_signed() {
+            return whole >= 0.0 && whole < 2f64.powi(64);
+        }
+        return whole >= -(2f64.powi(63))
+            && whole < 2f64.powi(63)
+            && ty.truncate(whole as i64) as f64 == whole;
+    }
+
+    struct Lint<'a, S: 'a>
+    where
+        S: DiagnosticSink,
+    {
+        symbols: &'a SymbolTable,
+        sink: &'a S,
+        // The span of the innermost node being visited that has one, for
+        // warnings about nodes that have none.
+        context: Option<Span>,
+    }
+
+    impl<'a, 'n, S> Visitor<'n> for Lint<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn visit(&mut self, node: &'n AstNode) {
+            if node.recovered {
+                return;
+            }
+            let outer = self.context;
+            if node.span.is_some() {
+                self.context = node.span;
+            }
+            match node.node_type {
+                AstNodeType::Block => self.check_reachable(node, true),
+                AstNodeType::Cast if node.data == "implicit" => self.check_conversion(node),
+                AstNodeType::BinaryOp => {
+                    self.check_comparison(node);
+                    self.check_constant(node);
+                }
+                AstNodeType::UnaryOp => self.check_constant(node),
+                _ => {}
+            }
+            // The statements of a switch body before its first label can
+            // never run.
+            if node.node_type == AstNodeType::Switch
+                && node.children[1].node_type == AstNodeType::Block
+            {
+                self.visit(&node.children[0]);
+                self.check_reachable(&node.children[1], false);
+                walk(self, &node.children[1]);
+            } else {
+                walk(self, node);
+            }
+            self.context = outer;
+        }
+    }
+
+    impl<'a, S> Lint<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn warning(&self, code: Code, message: String, span: Option<Span>) {
+            self.sink.report(Diagnostic::warning(code, message, span));
+        }
+
+        // The name a local variable was declared with.
+        fn source_name(&self, unique_name: &str) -> String {
+            return match self.symbols.lookup_unique(unique_name) {
+                Some(symbol) => symbol.name.clone(),
+                None => String::from(unique_name),
+            };
+        }
+
+        fn check_function(&self, function: &AstNode) {
+            let used: HashSet<&str> = find_all(function, AstNodeType::Variable)
+                .iter()
+                .map(|variable| variable.data.as_str())
+                .collect();
+            for declaration in find_all(function, AstNodeType::Declaration) {
+                if !declaration.recovered && !used.contains(declaration.data.as_str()) {
+                    self.warning(
+                        Code::UnusedVariable,
+                        format!("Unused variable '{}'", self.source_name(&declaration.data)),
+                        declaration.span,
+                    );
+                }
+            }
+
+            // Reaching the end of main returns 0.
+            let returns_value = match function.ty {
+                Type::Function(ref return_type, ..) => **return_type != Type::Void,
+                _ => false,
+            };
+            let body = function.children.last().unwrap();
+            if returns_value && function.data != "main" && completes(body) {
+                self.warning(
+                    Code::MissingReturn,
+                    format!(
+                        "Non-void function '{}' does not return a value in all control paths",
+                        function.data
+                    ),
+                    function.span,
+                );
+            }
+        }
+
+        // A static function can only be used in this file, so one that is
+        // not called or referred to outside its own body is dead code.
+        fn check_unused_functions(&self, program: &AstNode) {
+            let mut used = HashSet::new();
+            for item in program.children.iter() {
+                let references = find_all(item, AstNodeType::Call)
+                    .into_iter()
+                    .chain(find_all(item, AstNodeType::Variable));
+                for reference in references {
+                    if reference.data != item.data {
+                        used.insert(reference.data.as_str());
+                    }
+                }
+            }
+            for function in program.children.iter() {
+                let unused = function.node_type == AstNodeType::Function
+                    && function.linkage == Linkage::Internal
+                    && !used.contains(function.data.as_str());
+                if unused {
+                    self.warning(
+                        Code::UnusedFunction,
+                        format!("Unused function '{}'", function.data),
+                        function.span,
+                    );
+                }
+            }
+        }
+
+        // Warns about the first statement of each run in the block that
+        // control cannot reach. A break after a statement that does not
+        // complete is a common precaution and not reported.
+        fn check_reachable(&self, block: &AstNode, reachable_at_start: bool) {
+            let mut reachable = reachable_at_start;
+            let mut reported = false;
+            for statement in block.children.iter() {
+                if is_labeled(statement) {
+                    reachable = true;
+                    reported = false;
+                }
+                let has_effect = match statement.node_type {
+                    AstNodeType::NullStatement | AstNodeType::Break => false,
+                    AstNodeType::Declaration => !statement.children.is_empty(),
+                    _ => true,
+                };
+                if !reachable && !reported && has_effect {
+                    self.warning(
+                        Code::Unreachable,
+                        String::from("Code will never be executed"),
+                        statement.span,
+                    );
+                    reported = true;
+                }
+                if reachable {
+                    reachable = completes(statement);
+                }
+            }
+        }
+
+        // Reports an operation on constants that overflows, and an integer
+        // division by a constant zero. Only the operation where an overflow
+        // happens is reported, not those containing it.
+        fn check_constant(&self, node: &AstNode) {
+            let mut overflows = Vec::new();
+            let _ = evaluate_constant(node, &mut overflows);
+            if let Some(overflow) = overflows.last().filter(|o| o.span == node.span) {
+                self.sink.report(overflow_warning(overflow, None));
+            }
+            let divides =
+                node.node_type == AstNodeType::BinaryOp && (node.data == "/" || node.data == "%");
+            if divides && node.ty.is_integer() && constant_value(&node.children[1]) == Some(0) {
+                self.warning(Code::DivByZero, String::from("Division by zero"), node.span);
+            }
+        }
+
+        // A constant converted to an integer type that cannot represent it is
+        // reported even without -Wimplicit-conversion, as gcc does. Making a
+        // value unsigned without narrowing it is left alone, since
+        // `unsigned x = -1` is a common idiom.
+        fn check_conversion(&self, node: &AstNode) {
+            let value = &node.children[0];
+            let (from, to) = (&value.ty, &node.ty);
+            let span = value.span.or(self.context);
+            if let (Some(constant), Some(converted)) = (constant_value(value), constant_value(node))
+            {
+                let (before, after) = (from.order_key(constant), to.order_key(converted));
+                let reinterpreted = !to.is_signed() && from.size() <= to.size();
+                if before != after && !reinterpreted {
+                    self.warning(
+                        Code::Overflow,
+                        format!(
+                            "Implicit conversion from '{}' to '{}' changes value from {} to {}",
+                            from, to, before, after
+                        ),
+                        span,
+                    );
+                    return;
+                }
+            }
+            if let Some(constant) = floating_value(value) {
+                if to.is_integer() && !fits_integer(constant, to) {
+                    self.warning(
+                        Code::Overflow,
+                        format!(
+                            "Implicit conversion of out of range value {} from '{}' to '{}' is undefined",
+                            constant, from, to
+                        ),
+                        span,
+                    );
+                    return;
+                }
+            }
+            if may_change_value(value, to) {
+                self.warning(
+                    Code::ImplicitConversion,
+                    format!(
+                        "Implicit conversion from '{}' to '{}' may change the value",
+                        from, to
+                    ),
+                    span,
+                );
+            }
+        }
+
+        // The usual arithmetic conversions make a signed operand compared
+        // with an unsigned one unsigned too, unless the signed one is a
+        // constant that is not negative.
+        fn check_comparison(&self, node: &AstNode) {
+            let is_comparison = matches!(node.data.as_str(), "==" | "!=" | "<" | "<=" | ">" | ">=");
+            let common = &node.children[0].ty;
+            if !is_comparison || !common.is_integer() || common.is_signed() {
+                return;
+            }
+            let lhs = unconverted(&node.children[0]);
+            let rhs = unconverted(&node.children[1]);
+            let (lhs_type, rhs_type) = (lhs.ty.promote(), rhs.ty.promote());
+            if !lhs_type.is_integer() || !rhs_type.is_integer() {
+                return;
+            }
+            let signed = match (lhs_type.is_signed(), rhs_type.is_signed()) {
+                (true, false) => lhs,
+                (false, true) => rhs,
+                _ => return,
+            };
+            if constant_value(signed).is_some_and(|value| value >= 0) {
+                return;
+            }
+            self.warning(
+                Code::SignCompare,
+                format!(
+                    "Comparison of integers of different signs: '{}' and '{}'",
+                    lhs.ty, rhs.ty
+                ),
+                node.span,
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use ast::ast::parse;
+        use diagnostics::diagnostics::{Code, Diagnostic, Severity};
+        use lexer::*;
+        use std::cell::RefCell;
+        use std::path::Path;
+
+        fn warnings(path: &str) -> Vec<(Code, u32, String)> {
+            let token_list = lexer::lex(Path::new(path));
+            let diagnostics = RefCell::new(Vec::new());
+            parse(&mut token_list.iter(), &|x: Diagnostic| {
+                assert_eq!(x.severity, Severity::Warning, "{}", x.message);
+                diagnostics
+                    .borrow_mut()
+                    .push((x.code, x.span.unwrap().line, x.message));
+            });
+            return diagnostics.into_inner();
+        }
+
+        // Static functions referred to through a pointer count as used, and
+        // a function calling itself does not.
+        #[test]
+        fn test_unused() {
+            assert_eq!(
+                warnings("test/warnings/valid/unused.c"),
+                vec![
+                    (
+                        Code::UnusedFunction,
+                        5,
+                        String::from("Unused function 'unused_helper'")
+                    ),
+                    (
+                        Code::UnusedVariable,
+                        14,
+                        String::from("Unused variable 'unused'")
+                    ),
+                ]
+            );
+        }
+
+        // Loops that never end, switches whose every case returns and the
+        // end of main need no return statement.
+        #[test]
+        fn test_control_flow() {
+            let never_executed = String::from("Code will never be executed");
+            assert_eq!(
+                warnings("test/warnings/valid/control_flow.c"),
+                vec![
+                    (
+                        Code::MissingReturn,
+                        1,
+                        String::from(
+                            "Non-void function 'sign' does not return a value in all control paths"
+                        )
+                    ),
+                    (Code::Unreachable, 27, never_executed.clone()),
+                    (Code::Unreachable, 33, never_executed.clone()),
+                    (Code::Unreachable, 41, never_executed),
+                ]
+            );
+        }
+
+        // Constants that convert exactly and comparisons with a constant that
+        // is not negative are not reported.
+        #[test]
+        fn test_conversions() {
+            let codes: Vec<(Code, u32)> = warnings("test/warnings/valid/conversions.c")
+                .into_iter()
+                .map(|(code, line, _)| (code, line))
+                .collect();
+            assert_eq!(
+                codes,
+                vec![
+                    (Code::ImplicitConversion, 6),
+                    (Code::ImplicitConversion, 9),
+                    (Code::ImplicitConversion, 11),
+                    (Code::ImplicitConversion, 13),
+                    (Code::Shadow, 15),
+                    (Code::SignCompare, 17),
+                ]
+            );
+        }
+
+        // Constants that change value when converted are reported at the
+        // constant without -Wimplicit-conversion. Making a value unsigned is
+        // only reported by -Wimplicit-conversion, and an explicit cast not at
+        // all. Static initializers are checked like any other.
+        #[test]
+        fn test_narrowing() {
+            let changes = |from: &str, to: &str, before: &str, after: &str| {
+                format!(
+                    "Implicit conversion from '{}' to '{}' changes value from {} to {}",
+                    from, to, before, after
+                )
+            };
+            assert_eq!(
+                warnings("test/warnings/valid/narrowing.c"),
+                vec![
+                    (
+                        Code::Overflow,
+                        2,
+                        changes("long", "int", "99999999999", "1215752191")
+                    ),
+                    (
+                        Code::Overflow,
+                        6,
+                        changes("int", "unsigned char", "300", "44")
+                    ),
+                    (
+                        Code::ImplicitConversion,
+                        14,
+                        String::from(
+                            "Implicit conversion from 'int' to 'unsigned int' may change the value"
+                        )
+                    ),
+                    (
+                        Code::Overflow,
+                        15,
+                        changes("int", "short", "40000", "-25536")
+                    ),
+                    (
+                        Code::Overflow,
+                        16,
+                        changes("unsigned int", "int", "4294967295", "-1")
+                    ),
+                    (
+                        Code::Overflow,
+                        23,
+                        changes("int", "unsigned char", "257", "1")
+                    ),
+                    (
+                        Code::ImplicitConversion,
+                        24,
+                        String::from(
+                            "Implicit conversion from 'double' to 'int' may change the value"
+                        )
+                    ),
+                    (Code::Overflow, 27, changes("int", "char", "1000", "-24")),
+                ]
+            );
+        }
+
+        // Overflow wraps around in enumerators as elsewhere, and a remainder
+        // overflows with its division; unsigned arithmetic wraps silently,
+        // even at 64 bits. The right operand of && is not evaluated when the
+        // left is zero.
+        #[test]
+        fn test_constants() {
+            assert_eq!(
+                warnings("test/warnings/valid/constants.c"),
+                vec![
+                    (
+                        Code::Overflow,
+                        1,
+                        String::from(
+                            "Integer overflow in expression of type 'int' results in '-2147483648'"
+                        )
+                    ),
+                    (
+                        Code::Overflow,
+                        7,
+                        String::from("Integer overflow in expression of type 'int' results in '0'")
+                    ),
+                    (
+                        Code::Overflow,
+                        9,
+                        String::from(
+                            "Integer overflow in expression of type 'long' results in '-2'"
+                        )
+                    ),
+                    (
+                        Code::Overflow,
+                        14,
+                        String::from("Integer overflow in expression of type 'int' results in '0'")
+                    ),
+                    (Code::DivByZero, 16, String::from("Division by zero")),
+                ]
+            );
+        }
+    }
+}
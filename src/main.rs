@@ -1,15 +1,21 @@
-mod test;
-mod fileutils;
-mod lexer;
+#![allow(clippy::needless_return, clippy::module_inception)]
+
 mod ast;
 mod codegen;
+mod diagnostics;
+mod fileutils;
+mod lexer;
+mod lint;
+mod sema;
+mod symtab;
+mod test;
+mod types;
 
-use std::ops::Index;
 use ast::ast::parse;
+use diagnostics::diagnostics::{explain, ConsoleSink, WarningFilter, WarningOptions};
+use fileutils::lines_from_file;
 use lexer::lexer::lex;
 use std::path::Path;
-use std::path::PathBuf;
-use std::fs;
 use codegen::codegen::CodeGenState;
 use std::process::Command;
 
@@ -18,22 +24,65 @@ use std::env;
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if args.len() != 2 {
+    // `--explain E0012` describes an error code instead of compiling.
+    if args.len() == 3 && args[1] == "--explain" {
+        match explain(&args[2]) {
+            Some(text) => print!("{}", text),
+            None => {
+                println!("no such error code: {}", args[2]);
+                std::process::exit(-1);
+            }
+        }
+        return;
+    }
+
+    // `--color` highlights the parts of each diagnostic.
+    let color = args.iter().skip(1).any(|arg| arg == "--color");
+
+    // Warning flags apply in order, so a later one overrides an earlier one.
+    let mut options = WarningOptions::new();
+    for flag in args
+        .iter()
+        .skip(1)
+        .filter(|arg| WarningOptions::is_flag(arg))
+    {
+        if !options.apply(flag) {
+            println!("unrecognized warning option: {}", flag);
+            std::process::exit(-1);
+        }
+    }
+
+    let files: Vec<&String> = args
+        .iter()
+        .skip(1)
+        .filter(|arg| *arg != "--color" && !WarningOptions::is_flag(arg))
+        .collect();
+    if files.len() != 1 {
         println!("missing source file argument");
         std::process::exit(-1);
     }
 
-    let thepath = Path::new(args.index(1));
+    let thepath = Path::new(files[0]);
 
     let output_path = thepath.with_extension("S");
 
+    if !thepath.is_file() {
+        println!("no such file: {}", thepath.display());
+        std::process::exit(-1);
+    }
+
     let token_list = lex(thepath);
+    let source = lines_from_file(thepath);
+    let file_name = thepath.display().to_string();
 
+    // Every error is reported before giving up on the file.
+    let sink = ConsoleSink::new(&file_name, &source, color);
+    let filter = WarningFilter::new(&options, &sink);
     let mut iter = token_list.iter();
-    let result = parse(&mut iter, &|x: String| {
-        println!("Error: {}", x);
+    let result = parse(&mut iter, &filter);
+    if sink.error_count() > 0 {
         std::process::exit(-1);
-    });
+    }
 
     let mut code_gen = CodeGenState::new(result);
     code_gen.generate_code_to_file(&output_path);
@@ -41,8 +90,8 @@ fn main() {
     gcc_compile(&output_path);
 }
 
-fn gcc_compile(file_path: &PathBuf) {
-    let mut naked = file_path.clone();
+fn gcc_compile(file_path: &Path) {
+    let mut naked = file_path.to_path_buf();
     naked.set_extension("");
     let clean_str = naked.to_str().expect("broken");
 
@@ -55,7 +104,7 @@ fn gcc_compile(file_path: &PathBuf) {
 
     let output = if cfg!(target_os = "windows") {
         Command::new("cmd")
-            .args(&["/C", "gcc file"])
+            .args(["/C", "gcc file"])
             .output()
             .expect("failed to execute process")
     } else {
@@ -0,0 +1,1296 @@
+#![allow(dead_code)]
+
+pub mod sema {
+
+    use std::collections::HashMap;
+    use ast::ast::{
+        convert, decay, element_type, has_const_member, is_lvalue, is_null_pointer_constant,
+        lvalue_qualifiers, same_pointee, void_pointer_pair, find_all, walk_mut, AstNode,
+        AstNodeType, MutVisitor,
+    };
+    use diagnostics::diagnostics::{Code, Diagnostic, DiagnosticSink, Severity, Span};
+    use symtab::symtab::{SymbolKind, SymbolTable};
+    use types::types::Type;
+
+    // Resolves the names in the parsed program and types its expressions,
+    // converting values to the types their context requires, and checks the
+    // rules that depend on that context: assignments need a modifiable lvalue
+    // and a value of a compatible type, calls the right arguments, returns a
+    // value of the function's type, and break, continue, case, default and
+    // goto need an enclosing statement or label to refer to.
+    //
+    // The parser only binds each name to the declaration in scope, as it
+    // must to tell typedef names from other identifiers, and leaves
+    // expressions untyped. Where it needs a value before the program is
+    // complete, such as an array length, it types that expression by the same
+    // rules through the functions below. Declarations are checked by the
+    // parser as they are entered in the symbol table.
+    pub fn analyze<S>(program: &mut AstNode, symbols: &SymbolTable, sink: &S)
+    where
+        S: DiagnosticSink,
+    {
+        Analyzer::new(symbols, sink).visit_mut(program);
+    }
+
+    // Types an expression whose value the parser needs while parsing.
+    pub fn value<S>(expression: AstNode, symbols: &SymbolTable, sink: &S) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        return Analyzer::new(symbols, sink).value(expression);
+    }
+
+    // The type of the operand of sizeof, which is not converted to a value.
+    pub fn type_of<S>(expression: AstNode, symbols: &SymbolTable, sink: &S) -> Type
+    where
+        S: DiagnosticSink,
+    {
+        return Analyzer::new(symbols, sink).expression(expression).ty;
+    }
+
+    // Types the initializer of an object with static storage duration, which
+    // the parser folds into the object's initial contents. The span is where
+    // to report an element that has none of its own.
+    pub fn initializer<S>(
+        initializer: AstNode,
+        ty: &Type,
+        symbols: &SymbolTable,
+        span: Option<Span>,
+        sink: &S,
+    ) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        return Analyzer::new(symbols, sink).initializer(initializer, ty, span);
+    }
+
+    // Why a value cannot be stored in an object of the target type, if it
+    // cannot. Besides identical types, arithmetic types convert to each other,
+    // a null pointer constant converts to any pointer and `void *` to and from
+    // any object pointer. A pointer may gain qualifiers on the type it points
+    // to; losing them only warns.
+    fn assignment_diagnostic(
+        target: &Type,
+        value: &AstNode,
+        span: Option<Span>,
+    ) -> Option<Diagnostic> {
+        let target = target.unqualified();
+        let compatible = *target == value.ty
+            || (target.is_arithmetic() && value.ty.is_arithmetic())
+            || (target.is_pointer() && is_null_pointer_constant(value))
+            || same_pointee(target, &value.ty)
+            || void_pointer_pair(target, &value.ty);
+        if !compatible {
+            return Some(Diagnostic::error(
+                Code::IncompatibleTypes,
+                format!(
+                    "Incompatible types: cannot convert '{}' to '{}'",
+                    value.ty, target
+                ),
+                span,
+            ));
+        }
+        if let (Some(to), Some(from)) = (target.pointee(), value.ty.pointee()) {
+            if !from.qualifiers().is_subset(to.qualifiers()) {
+                return Some(Diagnostic::warning(
+                    Code::DiscardedQualifiers,
+                    format!(
+                        "Converting '{}' to '{}' discards qualifiers",
+                        value.ty, target
+                    ),
+                    span,
+                ));
+            }
+        }
+        return None;
+    }
+
+    // Types a call through a function pointer.
+    fn indirect_call<S>(
+        callee: AstNode,
+        arguments: Vec<AstNode>,
+        span: Option<Span>,
+        sink: &S,
+    ) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let return_type = match callee.ty.pointee() {
+            Some(Type::Function(return_type, ..)) => (**return_type).clone(),
+            _ => {
+                sink.report(Diagnostic::error(
+                    Code::InvalidCall,
+                    format!(
+                        "Called object type '{}' is not a function or function pointer",
+                        callee.ty
+                    ),
+                    span,
+                ));
+                return callee;
+            }
+        };
+        let mut call = AstNode::new_typed(AstNodeType::IndirectCall, String::new(), return_type);
+        call.span = span;
+        call.add_child(callee);
+        for argument in arguments {
+            call.add_child(argument);
+        }
+        return call;
+    }
+
+    // Types a binary operator after checking its operand types. Operands of
+    // arithmetic and comparisons undergo the usual arithmetic conversions.
+    // An integer added to a pointer is always made the right operand,
+    // converted to long, so that codegen only needs to scale one side.
+    fn binary<S>(
+        operator: &str,
+        lhs: AstNode,
+        rhs: AstNode,
+        span: Option<Span>,
+        sink: &S,
+    ) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let (mut lhs, mut rhs) = (lhs, rhs);
+        if operator == "+" && lhs.ty.is_integer() && rhs.ty.is_pointer() {
+            ::std::mem::swap(&mut lhs, &mut rhs);
+        }
+        let both_integers = lhs.ty.is_integer() && rhs.ty.is_integer();
+        let both_arithmetic = lhs.ty.is_arithmetic() && rhs.ty.is_arithmetic();
+        let common = lhs.ty.common_type(&rhs.ty);
+        // Pointer arithmetic needs the size of the pointee.
+        let sized_pointer = match lhs.ty.pointee() {
+            Some(&Type::Function(..)) => false,
+            Some(pointee) => pointee.is_complete(),
+            None => false,
+        };
+        let (ty, operands) = match operator {
+            "+" | "-" if sized_pointer && rhs.ty.is_integer() => (Some(lhs.ty.clone()), None),
+            "-" if sized_pointer && same_pointee(&lhs.ty, &rhs.ty) => (Some(Type::Long), None),
+            "+" | "-" | "*" | "/" if both_arithmetic => (Some(common.clone()), Some(common)),
+            "%" if both_integers => (Some(common.clone()), Some(common)),
+            "&&" | "||" if lhs.ty.is_scalar() && rhs.ty.is_scalar() => (Some(Type::Int), None),
+            "==" | "!=" | "<" | "<=" | ">" | ">=" => {
+                // Pointers compare with pointers to the same type, however
+                // qualified, or with a null pointer constant converted to
+                // their type. Equality also compares an object pointer with
+                // `void *`.
+                let equality = operator == "==" || operator == "!=";
+                if both_arithmetic {
+                    (Some(Type::Int), Some(common))
+                } else if same_pointee(&lhs.ty, &rhs.ty)
+                    || (equality && void_pointer_pair(&lhs.ty, &rhs.ty))
+                {
+                    (Some(Type::Int), None)
+                } else if lhs.ty.is_pointer() && is_null_pointer_constant(&rhs) {
+                    (Some(Type::Int), Some(lhs.ty.clone()))
+                } else if rhs.ty.is_pointer() && is_null_pointer_constant(&lhs) {
+                    (Some(Type::Int), Some(rhs.ty.clone()))
+                } else {
+                    (None, None)
+                }
+            }
+            _ => (None, None),
+        };
+
+        let ty = match ty {
+            Some(ty) => ty,
+            None => {
+                sink.report(Diagnostic::error(
+                    Code::InvalidOperands,
+                    format!(
+                        "Invalid operands to binary {} ('{}' and '{}')",
+                        operator, lhs.ty, rhs.ty
+                    ),
+                    span,
+                ));
+                Type::Int
+            }
+        };
+        match operands {
+            Some(operands) => {
+                lhs = convert(lhs, &operands);
+                rhs = convert(rhs, &operands);
+            }
+            None if ty.is_pointer() => {
+                rhs = convert(rhs, &Type::Long);
+            }
+            None => {}
+        }
+        let mut binary = AstNode::new_typed(AstNodeType::BinaryOp, String::from(operator), ty);
+        binary.add_child(lhs);
+        binary.add_child(rhs);
+        return binary;
+    }
+
+    // Types `-`, `+`, `~` or `!` applied to an operand, given the span of the
+    // operator. The arithmetic operators promote it; `+` leaves only the
+    // promoted operand.
+    fn unary<S>(operator: &str, operand: AstNode, span: Option<Span>, sink: &S) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        if operator == "!" {
+            let mut unary =
+                AstNode::new_typed(AstNodeType::UnaryOp, String::from(operator), Type::Int);
+            unary.span = span;
+            unary.add_child(operand);
+            return unary;
+        }
+        let valid = if operator == "~" {
+            operand.ty.is_integer()
+        } else {
+            operand.ty.is_arithmetic()
+        };
+        if !valid {
+            sink.report(Diagnostic::error(
+                Code::InvalidOperands,
+                format!("Invalid argument type '{}' to unary expression", operand.ty),
+                span,
+            ));
+            return operand;
+        }
+        let ty = operand.ty.promote();
+        let operand = convert(operand, &ty);
+        if operator == "+" {
+            return operand;
+        }
+        let mut unary = AstNode::new_typed(AstNodeType::UnaryOp, String::from(operator), ty);
+        unary.span = span;
+        unary.add_child(operand);
+        return unary;
+    }
+
+    // Types `&operand`, a pointer to the operand's type qualified like the
+    // object it designates.
+    fn address_of(symbols: &SymbolTable, operand: AstNode, span: Option<Span>) -> AstNode {
+        let qualifiers = lvalue_qualifiers(symbols, &operand);
+        let ty = Type::pointer_to(operand.ty.clone().qualified(qualifiers));
+        let mut address = AstNode::new_typed(AstNodeType::AddressOf, String::new(), ty);
+        address.span = span;
+        address.add_child(operand);
+        return address;
+    }
+
+    // Types `*operand`, which needs a pointer operand.
+    fn dereference<S>(operand: AstNode, span: Option<Span>, sink: &S) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let ty = match operand.ty.pointee() {
+            Some(pointee) => pointee.unqualified().clone(),
+            None => {
+                sink.report(Diagnostic::error(
+                    Code::InvalidOperands,
+                    format!(
+                        "Indirection requires pointer operand ('{}' invalid)",
+                        operand.ty
+                    ),
+                    span,
+                ));
+                Type::Int
+            }
+        };
+        let mut dereference = AstNode::new_typed(AstNodeType::Dereference, String::new(), ty);
+        dereference.span = span;
+        dereference.add_child(operand);
+        return dereference;
+    }
+
+    // Types `base[index]`, which means `*(base + index)`, so either operand
+    // may be the pointer.
+    fn subscript<S>(base: AstNode, index: AstNode, span: Option<Span>, sink: &S) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let valid = (base.ty.is_pointer() && index.ty.is_integer())
+            || (base.ty.is_integer() && index.ty.is_pointer());
+        if !valid {
+            sink.report(Diagnostic::error(
+                Code::InvalidOperands,
+                String::from("Subscripted value is not an array or pointer"),
+                span,
+            ));
+            return base;
+        }
+        let address = binary("+", base, index, span, sink);
+        // The addition fails for a pointer to an incomplete type.
+        let ty = match address.ty.pointee() {
+            Some(pointee) => pointee.unqualified().clone(),
+            None => return address,
+        };
+        let mut element = AstNode::new_typed(AstNodeType::Dereference, String::new(), ty);
+        element.add_child(address);
+        return element;
+    }
+
+    // The struct or union a `->` operand points to, or the operand itself
+    // when it does not point to one.
+    fn arrow<S>(pointer: AstNode, span: Option<Span>, sink: &S) -> Result<AstNode, AstNode>
+    where
+        S: DiagnosticSink,
+    {
+        let ty = match pointer.ty.pointee() {
+            Some(pointee) if pointee.is_struct() => pointee.unqualified().clone(),
+            _ => {
+                sink.report(Diagnostic::error(
+                    Code::InvalidMemberAccess,
+                    format!(
+                        "Member reference type '{}' is not a pointer to a structure or union",
+                        pointer.ty
+                    ),
+                    span,
+                ));
+                return Err(pointer);
+            }
+        };
+        let mut object = AstNode::new_typed(AstNodeType::Dereference, String::new(), ty);
+        object.add_child(pointer);
+        return Ok(object);
+    }
+
+    // Types the member of a struct or union expression. An array member of a
+    // qualified struct has qualified elements.
+    fn member<S>(
+        symbols: &SymbolTable,
+        object: AstNode,
+        name: String,
+        span: Option<Span>,
+        sink: &S,
+    ) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let ty = match object.ty {
+            Type::Struct(ref aggregate) => {
+                if !aggregate.is_complete() {
+                    sink.report(Diagnostic::error(
+                        Code::IncompleteType,
+                        format!("Incomplete definition of type '{}'", object.ty),
+                        span,
+                    ));
+                    return object;
+                }
+                match aggregate.member(&name) {
+                    Some((ty, _)) => ty,
+                    None => {
+                        sink.report(Diagnostic::error(
+                            Code::InvalidMemberAccess,
+                            format!("No member named '{}' in '{}'", name, object.ty),
+                            span,
+                        ));
+                        return object;
+                    }
+                }
+            }
+            _ => {
+                sink.report(Diagnostic::error(
+                    Code::InvalidMemberAccess,
+                    format!(
+                        "Member reference base type '{}' is not a structure or union",
+                        object.ty
+                    ),
+                    span,
+                ));
+                return object;
+            }
+        };
+        let ty = if ty.is_array() {
+            ty.qualified(lvalue_qualifiers(symbols, &object))
+        } else {
+            ty.unqualified().clone()
+        };
+        let mut member = AstNode::new_typed(AstNodeType::Member, name, ty);
+        member.add_child(object);
+        return member;
+    }
+
+    // Types `(ty) operand`. Scalar values convert to any scalar type, except
+    // between pointers and floating types, or to void, which discards the
+    // value. The cast always gets its own node, even to the operand's own
+    // type, since its result is not an lvalue.
+    fn cast<S>(ty: Type, operand: AstNode, span: Option<Span>, sink: &S) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let pointer_and_floating = (ty.is_pointer() && operand.ty.is_floating())
+            || (ty.is_floating() && operand.ty.is_pointer());
+        let valid =
+            ty == Type::Void || (ty.is_scalar() && operand.ty.is_scalar() && !pointer_and_floating);
+        if !valid {
+            sink.report(Diagnostic::error(
+                Code::IncompatibleTypes,
+                format!("Invalid cast from '{}' to '{}'", operand.ty, ty),
+                span,
+            ));
+            return operand;
+        }
+        let mut cast = AstNode::new_typed(AstNodeType::Cast, String::new(), ty);
+        cast.add_child(operand);
+        return cast;
+    }
+
+    // The value of `sizeof` or `_Alignof` applied to a type, a constant of
+    // type unsigned long taken from the same layout the code generator
+    // allocates objects with.
+    pub fn size_of<S>(keyword: &str, ty: &Type, span: Option<Span>, sink: &S) -> AstNode
+    where
+        S: DiagnosticSink,
+    {
+        let value = match *ty {
+            Type::Function(..) => {
+                sink.report(Diagnostic::error(
+                    Code::InvalidOperands,
+                    format!("Invalid application of '{}' to a function type", keyword),
+                    span,
+                ));
+                1
+            }
+            _ if !ty.is_complete() => {
+                sink.report(Diagnostic::error(
+                    Code::IncompleteType,
+                    format!(
+                        "Invalid application of '{}' to an incomplete type '{}'",
+                        keyword, ty
+                    ),
+                    span,
+                ));
+                1
+            }
+            _ if keyword == "sizeof" => ty.size(),
+            _ => ty.align(),
+        };
+        return AstNode::new_typed(AstNodeType::Constant, value.to_string(), Type::UnsignedLong);
+    }
+
+    struct SwitchCases {
+        // The promoted type of the controlling expression.
+        ty: Type,
+        ranges: Vec<(i64, i64)>,
+        has_default: bool,
+    }
+
+    struct Analyzer<'a, S: 'a>
+    where
+        S: DiagnosticSink,
+    {
+        symbols: &'a SymbolTable,
+        sink: &'a S,
+        // Return type of the function being analyzed.
+        return_type: Type,
+        loop_depth: usize,
+        // The case values seen so far in each enclosing switch statement.
+        switches: Vec<SwitchCases>,
+    }
+
+    impl<'a, S> MutVisitor for Analyzer<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn visit_mut(&mut self, node: &mut AstNode) {
+            if node.recovered {
+                self.check_undeclared(node);
+                return;
+            }
+            match node.node_type {
+                AstNodeType::Function => {
+                    if let Type::Function(ref return_type, ..) = node.ty {
+                        self.return_type = (**return_type).clone();
+                    }
+                    self.check_labels(node);
+                    self.check_frame(node);
+                    self.check_va_start(node);
+                    walk_mut(self, node);
+                }
+                AstNodeType::If => {
+                    self.check_condition(node, 0);
+                    walk_mut(self, node);
+                }
+                AstNodeType::While | AstNodeType::DoWhile | AstNodeType::For => {
+                    let condition = if node.node_type == AstNodeType::While {
+                        0
+                    } else {
+                        1
+                    };
+                    self.check_condition(node, condition);
+                    self.loop_depth += 1;
+                    walk_mut(self, node);
+                    self.loop_depth -= 1;
+                }
+                AstNodeType::Switch => {
+                    let ty = self.check_switch(node);
+                    self.switches.push(SwitchCases {
+                        ty,
+                        ranges: Vec::new(),
+                        has_default: false,
+                    });
+                    self.visit_mut(&mut node.children[1]);
+                    self.switches.pop();
+                }
+                AstNodeType::Case => {
+                    self.check_case(node);
+                    self.visit_mut(&mut node.children[2]);
+                }
+                AstNodeType::Default => {
+                    let duplicate = self
+                        .switches
+                        .last_mut()
+                        .map(|switch| ::std::mem::replace(&mut switch.has_default, true));
+                    match duplicate {
+                        Some(true) => self.error(
+                            Code::MisplacedStatement,
+                            String::from("Multiple default labels in one switch"),
+                            node.span,
+                        ),
+                        Some(false) => {}
+                        None => self.error(
+                            Code::MisplacedStatement,
+                            String::from("'default' statement not in switch statement"),
+                            node.span,
+                        ),
+                    }
+                    walk_mut(self, node);
+                }
+                AstNodeType::Break => {
+                    if self.loop_depth == 0 && self.switches.is_empty() {
+                        self.error(
+                            Code::MisplacedStatement,
+                            String::from("break statement not within loop or switch"),
+                            node.span,
+                        );
+                    }
+                }
+                AstNodeType::Continue => {
+                    if self.loop_depth == 0 {
+                        self.error(
+                            Code::MisplacedStatement,
+                            String::from("continue statement not within a loop"),
+                            node.span,
+                        );
+                    }
+                }
+                AstNodeType::Return => {
+                    if !node.children.is_empty() {
+                        self.value_at(node, 0);
+                    }
+                    self.check_return(node);
+                }
+                AstNodeType::ExpressionStatement => self.value_at(node, 0),
+                AstNodeType::Declaration => {
+                    if let Some(initializer) = node.children.pop() {
+                        let initializer = self.initializer(initializer, &node.ty, node.span);
+                        node.add_child(initializer);
+                    }
+                }
+                _ => walk_mut(self, node),
+            }
+        }
+    }
+
+    impl<'a, S> Analyzer<'a, S>
+    where
+        S: DiagnosticSink,
+    {
+        fn new(symbols: &'a SymbolTable, sink: &'a S) -> Analyzer<'a, S> {
+            return Analyzer {
+                symbols,
+                sink,
+                return_type: Type::Void,
+                loop_depth: 0,
+                switches: Vec::new(),
+            };
+        }
+
+        fn error(&self, code: Code, message: String, span: Option<Span>) {
+            self.sink.report(Diagnostic::error(code, message, span));
+        }
+
+        // An expression used for its value, with an array or function decayed
+        // to a pointer.
+        fn value(&self, expression: AstNode) -> AstNode {
+            return decay(self.expression(expression));
+        }
+
+        // Types the child of the node at the index as a value.
+        fn value_at(&self, node: &mut AstNode, index: usize) {
+            let expression = node.children.remove(index);
+            let value = self.value(expression);
+            node.children.insert(index, value);
+        }
+
+        // Types an expression from the bottom up, checking the operands of
+        // each operator. The result may have an array or function type, which
+        // only `&` and sizeof keep. Literals were typed by the parser.
+        fn expression(&self, node: AstNode) -> AstNode {
+            let mut node = node;
+            let span = node.span;
+            match node.node_type {
+                AstNodeType::Identifier => {
+                    // Like any other name in error, it is taken to be an int
+                    // so that its uses are not reported as well.
+                    self.error(
+                        Code::Undeclared,
+                        format!("Use of undeclared identifier '{}'", node.data),
+                        span,
+                    );
+                    node.node_type = AstNodeType::Variable;
+                    node.ty = Type::Int;
+                    return node;
+                }
+                AstNodeType::Variable => return self.variable(node),
+                AstNodeType::Assign => {
+                    let rhs = self.value(node.children.pop().unwrap());
+                    let lhs = self.value(node.children.pop().unwrap());
+                    node.ty = lhs.ty.clone();
+                    node.add_child(lhs);
+                    node.add_child(rhs);
+                    self.check_assign(&mut node);
+                    return node;
+                }
+                AstNodeType::BinaryOp => {
+                    let rhs = self.value(node.children.pop().unwrap());
+                    let lhs = self.value(node.children.pop().unwrap());
+                    let mut binary = binary(&node.data, lhs, rhs, span, self.sink);
+                    binary.span = span;
+                    return binary;
+                }
+                AstNodeType::UnaryOp => {
+                    let operand = self.value(node.children.pop().unwrap());
+                    return unary(&node.data, operand, span, self.sink);
+                }
+                AstNodeType::AddressOf => {
+                    let operand = self.expression(node.children.pop().unwrap());
+                    if !is_lvalue(&operand) {
+                        self.error(
+                            Code::InvalidOperands,
+                            String::from("Cannot take the address of an rvalue"),
+                            span,
+                        );
+                    }
+                    return address_of(self.symbols, operand, span);
+                }
+                AstNodeType::Dereference if node.data == "subscript" => {
+                    let index = self.value(node.children.pop().unwrap());
+                    let base = self.value(node.children.pop().unwrap());
+                    return subscript(base, index, span, self.sink);
+                }
+                AstNodeType::Dereference => {
+                    let operand = self.value(node.children.pop().unwrap());
+                    return dereference(operand, span, self.sink);
+                }
+                AstNodeType::Member => {
+                    let mut object = node.children.pop().unwrap();
+                    let object =
+                        if object.node_type == AstNodeType::Dereference && object.data == "arrow" {
+                            let pointer = self.value(object.children.pop().unwrap());
+                            match arrow(pointer, object.span, self.sink) {
+                                Ok(object) => object,
+                                Err(pointer) => return pointer,
+                            }
+                        } else {
+                            self.expression(object)
+                        };
+                    return member(self.symbols, object, node.data, span, self.sink);
+                }
+                AstNodeType::Cast => {
+                    let operand = self.value(node.children.pop().unwrap());
+                    return cast(node.ty, operand, span, self.sink);
+                }
+                AstNodeType::IndirectCall => {
+                    let mut arguments: Vec<AstNode> = node.children.drain(..).collect();
+                    let callee = arguments.remove(0);
+                    let arguments = arguments.into_iter().map(|a| self.value(a)).collect();
+                    return self.call(callee, arguments, span);
+                }
+                AstNodeType::VaStart
+                | AstNodeType::VaArg
+                | AstNodeType::VaEnd
+                | AstNodeType::VaCopy => {
+                    let operands: Vec<AstNode> = node.children.drain(..).collect();
+                    for operand in operands {
+                        let operand = self.va_list_operand(operand);
+                        node.add_child(operand);
+                    }
+                    if node.node_type == AstNodeType::VaArg {
+                        self.check_va_arg(&node);
+                    }
+                    return node;
+                }
+                _ => return node,
+            }
+        }
+
+        // Types a name by the declaration the parser bound it to. An
+        // enumerator is replaced by its value, and a typedef name where a
+        // value is expected is taken to be an int. A Variable without a
+        // declaration is a string literal, which the parser has typed.
+        fn variable(&self, node: AstNode) -> AstNode {
+            let mut node = node;
+            let symbol = match self.symbols.lookup_unique(&node.data) {
+                Some(symbol) => symbol,
+                None => return node,
+            };
+            match symbol.kind {
+                SymbolKind::Constant(value) => {
+                    let mut constant =
+                        AstNode::new_typed(AstNodeType::Constant, value.to_string(), Type::Int);
+                    constant.span = node.span;
+                    return constant;
+                }
+                SymbolKind::Typedef => {
+                    self.error(
+                        Code::Syntax,
+                        format!(
+                            "Unexpected type name '{}': expected expression",
+                            symbol.name
+                        ),
+                        node.span,
+                    );
+                    node.ty = Type::Int;
+                }
+                _ => node.ty = symbol.ty.unqualified().clone(),
+            }
+            return node;
+        }
+
+        // Types a call. A function named by the callee is called directly and
+        // anything else through the pointer the callee decays to; either way
+        // the arguments are checked against the parameters. A call of an
+        // undeclared function is taken to return int.
+        fn call(&self, callee: AstNode, arguments: Vec<AstNode>, span: Option<Span>) -> AstNode {
+            if callee.node_type == AstNodeType::Identifier {
+                self.error(
+                    Code::Undeclared,
+                    format!("Call to undeclared function '{}'", callee.data),
+                    span,
+                );
+                let mut call = AstNode::new_typed(AstNodeType::Call, callee.data, Type::Int);
+                call.span = span;
+                call.children = arguments;
+                return call;
+            }
+            let function = match self.symbols.lookup_unique(&callee.data) {
+                Some(symbol) if callee.node_type == AstNodeType::Variable => match symbol.kind {
+                    SymbolKind::Function { .. } => Some(symbol.ty.clone()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(Type::Function(return_type, params, variadic)) = function {
+                let mut call = AstNode::new_typed(AstNodeType::Call, callee.data, *return_type);
+                call.span = span;
+                call.children = arguments;
+                let name = format!("Function '{}'", call.data);
+                self.convert_arguments(&mut call, 0, &name, &params, variadic);
+                return call;
+            }
+
+            let callee = self.value(callee);
+            let function = callee.ty.pointee().cloned();
+            let mut call = indirect_call(callee, arguments, span, self.sink);
+            if let Some(Type::Function(_, params, variadic)) = function {
+                self.convert_arguments(&mut call, 1, "Function pointer", &params, variadic);
+            }
+            return call;
+        }
+
+        // Types an initializer for an object of the type. Each element of a
+        // list initializes the member or element in its position and is
+        // converted to its type. The span is where to report a value that has
+        // no span of its own.
+        fn initializer(&self, initializer: AstNode, ty: &Type, span: Option<Span>) -> AstNode {
+            let mut initializer = initializer;
+            if initializer.node_type != AstNodeType::InitializerList {
+                let span = initializer.span.or(span);
+                let value = self.value(initializer);
+                return self.convert_assignment(ty, value, span);
+            }
+            let elements: Vec<AstNode> = initializer.children.drain(..).collect();
+            for (index, element) in elements.into_iter().enumerate() {
+                let element = match element_type(&initializer.ty, index) {
+                    Some(element_ty) => self.initializer(element, &element_ty, span),
+                    None => self.value(element),
+                };
+                initializer.add_child(element);
+            }
+            return initializer;
+        }
+
+        // An operand of the stdarg builtins, which must be a va_list.
+        fn va_list_operand(&self, operand: AstNode) -> AstNode {
+            let operand = self.value(operand);
+            let expected = match *self.symbols.va_list() {
+                Type::Array(ref tag, _) => Type::Pointer(tag.clone()),
+                _ => unreachable!(),
+            };
+            if operand.ty != expected {
+                self.error(
+                    Code::InvalidVariadic,
+                    format!(
+                        "Expected 'va_list' but argument is of type '{}'",
+                        operand.ty
+                    ),
+                    operand.span,
+                );
+            }
+            return operand;
+        }
+
+        // Arguments arrive promoted, so va_arg cannot fetch the narrow types.
+        fn check_va_arg(&self, node: &AstNode) {
+            let ty = &node.ty;
+            if ty.is_struct() {
+                self.error(
+                    Code::Unsupported,
+                    String::from("Fetching a struct with 'va_arg' is not supported"),
+                    node.span,
+                );
+            } else if !ty.is_scalar() {
+                self.error(
+                    Code::InvalidVariadic,
+                    format!("Invalid type '{}' for 'va_arg'", ty),
+                    node.span,
+                );
+            } else if ty.promote() != *ty || *ty == Type::Float {
+                let promoted = if *ty == Type::Float {
+                    Type::Double
+                } else {
+                    ty.promote()
+                };
+                self.sink.report(
+                    Diagnostic::error(
+                        Code::InvalidVariadic,
+                        format!("'{}' is promoted when passed through '...'", ty),
+                        node.span,
+                    )
+                    .with_note(format!("fetch it with va_arg(ap, {}) instead", promoted)),
+                );
+            }
+        }
+
+        // Only a function taking variable arguments has any for va_start to
+        // set up.
+        fn check_va_start(&self, function: &AstNode) {
+            if let Type::Function(_, _, true) = function.ty {
+                return;
+            }
+            for va_start in find_all(function, AstNodeType::VaStart) {
+                self.error(
+                    Code::InvalidVariadic,
+                    String::from("'va_start' used in function with fixed arguments"),
+                    va_start.span,
+                );
+            }
+        }
+
+        // A node cut short by a syntax error is not otherwise analyzed, since
+        // most of what is wrong with it follows from the tokens that were
+        // skipped. A name that is not declared is wrong whatever follows it.
+        fn check_undeclared(&self, node: &AstNode) {
+            for (index, child) in node.children.iter().enumerate() {
+                if child.node_type != AstNodeType::Identifier {
+                    self.check_undeclared(child);
+                } else if index == 0 && node.node_type == AstNodeType::IndirectCall {
+                    self.error(
+                        Code::Undeclared,
+                        format!("Call to undeclared function '{}'", child.data),
+                        child.span,
+                    );
+                } else {
+                    self.error(
+                        Code::Undeclared,
+                        format!("Use of undeclared identifier '{}'", child.data),
+                        child.span,
+                    );
+                }
+            }
+        }
+
+        // Types the controlling expression of a statement, the child at the
+        // index, which must have scalar type.
+        fn check_condition(&self, node: &mut AstNode, index: usize) {
+            self.value_at(node, index);
+            let condition = &node.children[index];
+            if !condition.ty.is_scalar() {
+                self.error(
+                    Code::IncompatibleTypes,
+                    format!(
+                        "Statement requires expression of scalar type ('{}' invalid)",
+                        condition.ty
+                    ),
+                    condition.span.or(node.span),
+                );
+            }
+        }
+
+        // Types the controlling expression of a switch, which must have
+        // integer type, and promotes it. Returns the promoted type, which the
+        // case values are converted to.
+        fn check_switch(&self, node: &mut AstNode) -> Type {
+            let condition = self.value(node.children.remove(0));
+            if !condition.ty.is_integer() {
+                self.error(
+                    Code::IncompatibleTypes,
+                    format!(
+                        "Statement requires expression of integer type ('{}' invalid)",
+                        condition.ty
+                    ),
+                    condition.span.or(node.span),
+                );
+            }
+            let ty = condition.ty.promote();
+            node.children.insert(0, convert(condition, &ty));
+            return ty;
+        }
+
+        // Every parameter and local variable of a function gets its own slot
+        // in the stack frame, whose offsets must fit in an int. Each slot is
+        // counted with its alignment as padding, as codegen may add that much.
+        fn check_frame(&self, function: &AstNode) {
+            let mut slots = find_all(function, AstNodeType::Declaration);
+            slots.extend(find_all(function, AstNodeType::Parameter));
+            let size: i64 = slots
+                .iter()
+                .map(|slot| i64::from(slot.ty.size()) + i64::from(slot.ty.align()))
+                .sum();
+            // The register save area of a variadic function and the final
+            // alignment need room as well.
+            if size + 192 > i64::from(i32::MAX) {
+                self.error(
+                    Code::InvalidDeclarator,
+                    format!(
+                        "Local variables of '{}' are too large for a stack frame",
+                        function.data
+                    ),
+                    function.span,
+                );
+            }
+        }
+
+        // Labels belong to the whole function, so a goto may jump to one that
+        // comes later in the body, but no further.
+        fn check_labels(&self, function: &AstNode) {
+            let labels = find_all(function, AstNodeType::Label);
+            let mut defined: HashMap<&str, Option<Span>> = HashMap::new();
+            for label in labels {
+                if let Some(previous) = defined.insert(&label.data, label.span) {
+                    let mut diagnostic = Diagnostic::error(
+                        Code::Redefinition,
+                        format!("Redefinition of label '{}'", label.data),
+                        label.span,
+                    );
+                    if let Some(previous) = previous {
+                        diagnostic = diagnostic
+                            .with_label(previous, String::from("previous definition is here"));
+                    }
+                    self.sink.report(diagnostic);
+                }
+            }
+
+            for goto in find_all(function, AstNodeType::Goto) {
+                if !goto.data.is_empty() && !defined.contains_key(goto.data.as_str()) {
+                    self.error(
+                        Code::Undeclared,
+                        format!("Use of undeclared label '{}'", goto.data),
+                        goto.span,
+                    );
+                }
+            }
+        }
+
+        // Converts the values of a case label to the type of the switch and
+        // numbers it among the switch's labels. No value may be matched by
+        // two labels.
+        fn check_case(&mut self, node: &mut AstNode) {
+            let ty = match self.switches.last() {
+                Some(switch) => switch.ty.clone(),
+                None => {
+                    self.error(
+                        Code::MisplacedStatement,
+                        String::from("'case' statement not in switch statement"),
+                        node.span,
+                    );
+                    return;
+                }
+            };
+            for value in node.children[..2].iter_mut() {
+                value.data = ty
+                    .truncate(value.data.parse::<i64>().unwrap_or(0))
+                    .to_string();
+                value.ty = ty.clone();
+            }
+            let low = node.children[0].data.parse::<i64>().unwrap_or(0);
+            let high = node.children[1].data.parse::<i64>().unwrap_or(0);
+            let key = |value: i64| ty.order_key(value);
+            if key(low) > key(high) {
+                self.error(
+                    Code::MisplacedStatement,
+                    String::from("Empty case range specified"),
+                    node.span,
+                );
+            }
+
+            let switch = self.switches.last_mut().unwrap();
+            node.data = switch.ranges.len().to_string();
+            let duplicate = switch
+                .ranges
+                .iter()
+                .find(|&&(l, h)| key(l) <= key(high) && key(low) <= key(h))
+                .map(|&(l, _)| if key(l) > key(low) { l } else { low });
+            switch.ranges.push((low, high));
+            if let Some(value) = duplicate {
+                self.error(
+                    Code::MisplacedStatement,
+                    format!("Duplicate case value '{}'", key(value)),
+                    node.span,
+                );
+            }
+        }
+
+        fn check_return(&self, node: &mut AstNode) {
+            match node.children.pop() {
+                None => {
+                    if self.return_type != Type::Void {
+                        self.error(
+                            Code::ReturnMismatch,
+                            String::from("Non-void function should return a value"),
+                            node.span,
+                        );
+                    }
+                }
+                Some(value) => {
+                    if self.return_type == Type::Void {
+                        self.error(
+                            Code::ReturnMismatch,
+                            String::from("Void function should not return a value"),
+                            node.span,
+                        );
+                        node.children.push(value);
+                    } else {
+                        let value = self.convert_assignment(&self.return_type, value, node.span);
+                        node.children.push(value);
+                    }
+                }
+            }
+        }
+
+        fn check_assign(&self, node: &mut AstNode) {
+            let lhs = &node.children[0];
+            let qualifiers = lvalue_qualifiers(self.symbols, lhs);
+            if !is_lvalue(lhs) {
+                self.error(
+                    Code::NotAssignable,
+                    String::from("Expression is not assignable"),
+                    node.span,
+                );
+            } else if qualifiers.is_const {
+                self.error(
+                    Code::NotAssignable,
+                    format!(
+                        "Cannot assign to an object of const-qualified type '{}'",
+                        lhs.ty.clone().qualified(qualifiers)
+                    ),
+                    node.span,
+                );
+            } else if has_const_member(&lhs.ty) {
+                self.error(
+                    Code::NotAssignable,
+                    format!(
+                        "Cannot assign to '{}' because it has a const-qualified member",
+                        lhs.ty
+                    ),
+                    node.span,
+                );
+            }
+            let target = lhs.ty.clone();
+            let rhs = node.children.pop().unwrap();
+            let rhs = self.convert_assignment(&target, rhs, node.span);
+            node.children.push(rhs);
+        }
+
+        // Checks the arguments of a call, which follow the first children of
+        // the node, against the callee's parameters. Each argument is
+        // converted to the type of its parameter; those passed for the `...`
+        // of a variadic function get the default argument promotions instead.
+        fn convert_arguments(
+            &self,
+            call: &mut AstNode,
+            first: usize,
+            callee: &str,
+            params: &[Type],
+            variadic: bool,
+        ) {
+            let count = call.children.len() - first;
+            if variadic && count < params.len() {
+                self.error(
+                    Code::InvalidCall,
+                    format!(
+                        "{} expects at least {} arguments but {} were given",
+                        callee,
+                        params.len(),
+                        count
+                    ),
+                    call.span,
+                );
+            } else if !variadic && params.len() != count {
+                self.error(
+                    Code::InvalidCall,
+                    format!(
+                        "{} expects {} arguments but {} were given",
+                        callee,
+                        params.len(),
+                        count
+                    ),
+                    call.span,
+                );
+            }
+            let arguments = call.children.split_off(first);
+            for (index, argument) in arguments.into_iter().enumerate() {
+                let argument = match params.get(index) {
+                    Some(param) => self.convert_assignment(param, argument, call.span),
+                    None if variadic => self.promote_argument(argument, call.span),
+                    None => argument,
+                };
+                call.children.push(argument);
+            }
+        }
+
+        // The default argument promotions: float becomes double and the
+        // integer promotions apply.
+        fn promote_argument(&self, argument: AstNode, span: Option<Span>) -> AstNode {
+            let argument = decay(argument);
+            if argument.ty.is_struct() {
+                self.error(
+                    Code::Unsupported,
+                    String::from("Passing a struct as a variadic argument is not supported"),
+                    span,
+                );
+                return argument;
+            }
+            if argument.ty == Type::Void {
+                self.error(
+                    Code::IncompatibleTypes,
+                    String::from("Argument has type 'void'"),
+                    span,
+                );
+                return argument;
+            }
+            if argument.ty == Type::Float {
+                return convert(argument, &Type::Double);
+            }
+            let promoted = argument.ty.promote();
+            return convert(argument, &promoted);
+        }
+
+        // Converts a value for storing in an object of the target type,
+        // reporting an error unless that is allowed.
+        fn convert_assignment(&self, target: &Type, value: AstNode, span: Option<Span>) -> AstNode {
+            if let Some(diagnostic) = assignment_diagnostic(target, &value, span) {
+                let incompatible = diagnostic.severity == Severity::Error;
+                self.sink.report(diagnostic);
+                if incompatible {
+                    return value;
+                }
+            }
+            return convert(value, target.unqualified());
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use ast::ast::{find_all, parse, AstNode, AstNodeType};
+        use diagnostics::diagnostics::{Diagnostic, Severity};
+        use lexer::*;
+        use std::cell::RefCell;
+        use std::path::Path;
+        use types::types::Type;
+
+        fn analyze_file(path: &str) -> (Box<AstNode>, Vec<Diagnostic>) {
+            let token_list = lexer::lex(Path::new(path));
+            let diagnostics = RefCell::new(Vec::new());
+            let program = parse(&mut token_list.iter(), &|x: Diagnostic| {
+                diagnostics.borrow_mut().push(x)
+            });
+            return (program, diagnostics.into_inner());
+        }
+
+        // Each error is reported at the operator or keyword whose context is
+        // wrong, after the parser has finished with the whole file.
+        #[test]
+        fn test_context_errors() {
+            let (_, diagnostics) = analyze_file("test/sema/invalid/context_errors.c");
+            let errors: Vec<(u32, u32, &str)> = diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .map(|d| {
+                    let span = d.span.unwrap();
+                    (span.line, span.start, d.message.as_str())
+                })
+                .collect();
+            assert_eq!(
+                errors,
+                vec![
+                    (6, 5, "Void function should not return a value"),
+                    (
+                        12,
+                        11,
+                        "Cannot assign to an object of const-qualified type 'const int'"
+                    ),
+                    (13, 15, "Expression is not assignable"),
+                    (14, 14, "Cannot take the address of an rvalue"),
+                    (
+                        15,
+                        15,
+                        "Function 'twice' expects 1 arguments but 2 were given"
+                    ),
+                    (16, 5, "break statement not within loop or switch"),
+                    (19, 5, "Duplicate case value '1'"),
+                    (20, 9, "continue statement not within a loop"),
+                    (22, 10, "Use of undeclared label 'missing'"),
+                    (23, 5, "Non-void function should return a value"),
+                ]
+            );
+        }
+
+        // Values are converted to the type their context needs: assigned
+        // values to the object's type, arguments to the parameter's type or
+        // promoted, and case values to the promoted type of the switch.
+        #[test]
+        fn test_conversions() {
+            let (program, diagnostics) = analyze_file("test/sema/valid/conversions.c");
+            assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+
+            let assignments = find_all(&program, AstNodeType::Assign);
+            assert_eq!(assignments[0].children[1].node_type, AstNodeType::Cast);
+            assert_eq!(assignments[0].children[1].ty, Type::Long);
+            assert_eq!(assignments[1].children[1].ty, Type::UnsignedChar);
+
+            let calls = find_all(&program, AstNodeType::Call);
+            let printf = calls.iter().find(|c| c.data == "printf").unwrap();
+            let promoted: Vec<Type> = printf.children[1..].iter().map(|a| a.ty.clone()).collect();
+            assert_eq!(
+                promoted,
+                vec![Type::Long, Type::Int, Type::Long, Type::Int, Type::Double]
+            );
+            let widen = calls.iter().find(|c| c.data == "widen").unwrap();
+            assert_eq!(widen.children[0].ty, Type::Long);
+
+            let cases = find_all(&program, AstNodeType::Case);
+            let numbered: Vec<(&str, &str, Type)> = cases
+                .iter()
+                .map(|c| {
+                    (
+                        c.data.as_str(),
+                        c.children[0].data.as_str(),
+                        c.children[0].ty.clone(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                numbered,
+                vec![("0", "44", Type::Int), ("1", "97", Type::Int)]
+            );
+        }
+    }
+}
@@ -0,0 +1,898 @@
+#![allow(dead_code)]
+
+pub mod symtab {
+
+    use std::collections::HashMap;
+    use diagnostics::diagnostics::{Code, Span};
+    use types::types::{AggregateRef, Type};
+
+    // Why a declaration was rejected: the kind of problem, a message and
+    // where the declaration it conflicts with is, if it has one.
+    pub type DeclarationError = (Code, String, Option<Span>);
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Linkage {
+        None,
+        Internal,
+        External,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum StorageClass {
+        Auto,
+        Static,
+        Extern,
+        // Not a storage class as such, but written where one would be.
+        Typedef,
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum SymbolKind {
+        Variable,
+        // An object with static storage duration: a file scope variable or a
+        // block scope static. Its unique name is the assembler symbol.
+        Static,
+        Function { defined: bool },
+        // An enumerator, which names an int constant.
+        Constant(i64),
+        // A typedef name, an alias for the symbol's type.
+        Typedef,
+    }
+
+    pub struct Symbol {
+        pub name: String,
+        pub unique_name: String,
+        pub kind: SymbolKind,
+        pub linkage: Linkage,
+        pub ty: Type,
+        // Where the symbol was declared, or defined for a function that has
+        // been, for diagnostics that point back to it.
+        pub span: Option<Span>,
+    }
+
+    // One piece of the initial contents of a static object, in memory order.
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum StaticInit {
+        // A scalar of the given type. Floating values are stored as their bit
+        // patterns.
+        Value(Type, i64),
+        // A run of zero bytes.
+        Zero(i32),
+        // The address of the function or static object of the given type and
+        // name, plus a byte offset.
+        Address(Type, String, i64),
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Definition {
+        // Only declared with extern; the object lives in another file.
+        None,
+        // Declared without an initializer; zero filled unless defined later.
+        Tentative,
+        Initialized(Vec<StaticInit>),
+    }
+
+    pub struct StaticObject {
+        pub name: String,
+        pub linkage: Linkage,
+        pub definition: Definition,
+        pub ty: Type,
+    }
+
+    // A stack of lexical scopes. Every declaration is given a unique name so that
+    // shadowed variables can be told apart once the scopes have been discarded.
+    pub struct SymbolTable {
+        scopes: Vec<HashMap<String, Symbol>>,
+        // Struct, union and enum tags live in their own namespace but are
+        // scoped like ordinary identifiers; there is one entry per scope. An
+        // enum tag maps to int, its underlying type.
+        tags: Vec<HashMap<String, Type>>,
+        counter: usize,
+        // Objects with static storage duration in declaration order.
+        statics: Vec<StaticObject>,
+        // The symbols of scopes that have been exited, by unique name, for the
+        // passes that run after parsing.
+        retired: HashMap<String, Symbol>,
+        // The type named by __builtin_va_list, which every va_list shares.
+        va_list: Type,
+    }
+
+    // The System V va_list: an array of one structure tracking how many of
+    // the argument registers have been consumed and where the remaining
+    // arguments were saved.
+    fn va_list_type() -> Type {
+        let tag = AggregateRef::new("__va_list_tag", false);
+        tag.define(vec![
+            (String::from("gp_offset"), Type::UnsignedInt),
+            (String::from("fp_offset"), Type::UnsignedInt),
+            (
+                String::from("overflow_arg_area"),
+                Type::pointer_to(Type::Void),
+            ),
+            (String::from("reg_save_area"), Type::pointer_to(Type::Void)),
+        ]);
+        return Type::Array(Box::new(Type::Struct(tag)), 1);
+    }
+
+    impl SymbolTable {
+        pub fn new() -> SymbolTable {
+            let table = SymbolTable {
+                scopes: vec![HashMap::new()],
+                tags: vec![HashMap::new()],
+                counter: 0,
+                statics: Vec::new(),
+                retired: HashMap::new(),
+                va_list: va_list_type(),
+            };
+            return table;
+        }
+
+        pub fn enter_scope(&mut self) {
+            self.scopes.push(HashMap::new());
+            self.tags.push(HashMap::new());
+        }
+
+        pub fn exit_scope(&mut self) {
+            if let Some(scope) = self.scopes.pop() {
+                for (_, symbol) in scope {
+                    self.retired.insert(symbol.unique_name.clone(), symbol);
+                }
+            }
+            self.tags.pop();
+        }
+
+        pub fn statics(&self) -> &[StaticObject] {
+            return &self.statics;
+        }
+
+        pub fn va_list(&self) -> &Type {
+            return &self.va_list;
+        }
+
+        fn next_unique_name(&mut self, name: &str) -> String {
+            let unique_name = format!("{}.{}", name, self.counter);
+            self.counter += 1;
+            return unique_name;
+        }
+
+        fn insert(
+            &mut self,
+            name: &str,
+            unique_name: &str,
+            kind: SymbolKind,
+            linkage: Linkage,
+            ty: Type,
+            span: Option<Span>,
+        ) {
+            let scope = self.scopes.last_mut().expect("no open scope");
+            scope.insert(
+                String::from(name),
+                Symbol {
+                    name: String::from(name),
+                    unique_name: String::from(unique_name),
+                    kind,
+                    linkage,
+                    ty,
+                    span,
+                },
+            );
+        }
+
+        fn insert_global(
+            &mut self,
+            name: &str,
+            kind: SymbolKind,
+            linkage: Linkage,
+            ty: Type,
+            span: Option<Span>,
+        ) {
+            self.scopes[0].insert(
+                String::from(name),
+                Symbol {
+                    name: String::from(name),
+                    unique_name: String::from(name),
+                    kind,
+                    linkage,
+                    ty,
+                    span,
+                },
+            );
+        }
+
+        pub fn declared_in_current_scope(&self, name: &str) -> bool {
+            let scope = self.scopes.last().expect("no open scope");
+            return scope.contains_key(name);
+        }
+
+        // Where the declaration of `name` in the innermost scope is, which is
+        // what the declare functions returning None or false conflicted with.
+        pub fn current_scope_span(&self, name: &str) -> Option<Span> {
+            let scope = self.scopes.last().expect("no open scope");
+            return scope.get(name).and_then(|symbol| symbol.span);
+        }
+
+        // Returns the unique name for the new symbol, or None if the name is
+        // already declared in the innermost scope. Each of the declare
+        // functions is given the span of the name being declared.
+        pub fn declare(&mut self, name: &str, ty: Type, span: Option<Span>) -> Option<String> {
+            if self.declared_in_current_scope(name) {
+                return None;
+            }
+            let unique_name = self.next_unique_name(name);
+            self.insert(
+                name,
+                &unique_name,
+                SymbolKind::Variable,
+                Linkage::None,
+                ty,
+                span,
+            );
+            return Some(unique_name);
+        }
+
+        // A block scope static has no linkage, so it gets a unique name just like
+        // an automatic variable, but it is stored with the file's other statics.
+        pub fn declare_local_static(
+            &mut self,
+            name: &str,
+            ty: Type,
+            init: Vec<StaticInit>,
+            span: Option<Span>,
+        ) -> Option<String> {
+            if self.declared_in_current_scope(name) {
+                return None;
+            }
+            let unique_name = self.next_unique_name(name);
+            self.insert(
+                name,
+                &unique_name,
+                SymbolKind::Static,
+                Linkage::None,
+                ty.clone(),
+                span,
+            );
+            self.statics.push(StaticObject {
+                name: unique_name.clone(),
+                linkage: Linkage::None,
+                definition: Definition::Initialized(init),
+                ty,
+            });
+            return Some(unique_name);
+        }
+
+        // A string literal is an unnamed static array of char. Returns the name
+        // of the object holding its characters.
+        pub fn declare_string(&mut self, ty: Type, init: Vec<StaticInit>) -> String {
+            let unique_name = self.next_unique_name("string");
+            self.statics.push(StaticObject {
+                name: unique_name.clone(),
+                linkage: Linkage::None,
+                definition: Definition::Initialized(init),
+                ty,
+            });
+            return unique_name;
+        }
+
+        // A block scope extern refers to the file scope object of the same name,
+        // declaring it there if this is the first time it has been seen.
+        pub fn declare_local_extern(
+            &mut self,
+            name: &str,
+            ty: Type,
+            span: Option<Span>,
+        ) -> Result<String, DeclarationError> {
+            if let Some(symbol) = self.scopes.last().expect("no open scope").get(name) {
+                if symbol.kind != SymbolKind::Static || symbol.linkage == Linkage::None {
+                    return Err((
+                        Code::ConflictingDeclaration,
+                        format!("Conflicting declarations of '{}'", name),
+                        symbol.span,
+                    ));
+                }
+            }
+
+            let mut linkage = Linkage::External;
+            match self.scopes[0].get(name) {
+                Some(symbol) => {
+                    if symbol.kind != SymbolKind::Static {
+                        return Err((
+                            Code::ConflictingDeclaration,
+                            format!("'{}' redeclared as a different kind of symbol", name),
+                            symbol.span,
+                        ));
+                    }
+                    if symbol.ty != ty {
+                        return Err((
+                            Code::ConflictingDeclaration,
+                            format!("Conflicting types for '{}'", name),
+                            symbol.span,
+                        ));
+                    }
+                    linkage = symbol.linkage;
+                }
+                None => {
+                    self.statics.push(StaticObject {
+                        name: String::from(name),
+                        linkage,
+                        definition: Definition::None,
+                        ty: ty.clone(),
+                    });
+                    self.insert_global(name, SymbolKind::Static, linkage, ty.clone(), span);
+                }
+            }
+            self.insert(name, name, SymbolKind::Static, linkage, ty, span);
+            return Ok(String::from(name));
+        }
+
+        // Declares a file scope variable, merging it with earlier declarations
+        // of the same name. Any number of tentative definitions are allowed, but
+        // at most one with an initializer, and all of them must agree on
+        // linkage.
+        pub fn declare_global(
+            &mut self,
+            name: &str,
+            mut ty: Type,
+            storage: StorageClass,
+            definition: Definition,
+            span: Option<Span>,
+        ) -> Result<(), DeclarationError> {
+            let previous = self.scopes[0]
+                .get(name)
+                .map(|s| (s.kind.clone(), s.linkage, s.ty.clone()));
+            let previous_span = self.scopes[0].get(name).and_then(|s| s.span);
+            let linkage = match previous {
+                Some((SymbolKind::Static, previous_linkage, previous_type)) => {
+                    ty = match previous_type.composite(&ty) {
+                        Some(composite) => composite,
+                        None => {
+                            return Err((
+                                Code::ConflictingDeclaration,
+                                format!("Conflicting types for '{}'", name),
+                                previous_span,
+                            ))
+                        }
+                    };
+                    let linkage = match storage {
+                        StorageClass::Static => Linkage::Internal,
+                        StorageClass::Extern => previous_linkage,
+                        _ => Linkage::External,
+                    };
+                    if linkage != previous_linkage {
+                        return Err((
+                            Code::ConflictingDeclaration,
+                            format!("Conflicting linkage for '{}'", name),
+                            previous_span,
+                        ));
+                    }
+                    linkage
+                }
+                Some(_) => {
+                    return Err((
+                        Code::ConflictingDeclaration,
+                        format!("'{}' redeclared as a different kind of symbol", name),
+                        previous_span,
+                    ));
+                }
+                None => match storage {
+                    StorageClass::Static => Linkage::Internal,
+                    _ => Linkage::External,
+                },
+            };
+
+            match self.statics.iter_mut().find(|o| o.name == name) {
+                Some(object) => {
+                    match (&object.definition, &definition) {
+                        (&Definition::Initialized(_), &Definition::Initialized(_)) => {
+                            return Err((
+                                Code::Redefinition,
+                                format!("Redefinition of '{}'", name),
+                                previous_span,
+                            ));
+                        }
+                        (_, &Definition::Initialized(_)) => object.definition = definition,
+                        (&Definition::None, &Definition::Tentative) => {
+                            object.definition = definition
+                        }
+                        _ => {}
+                    }
+                    object.ty = ty.clone();
+                }
+                None => self.statics.push(StaticObject {
+                    name: String::from(name),
+                    linkage,
+                    definition,
+                    ty: ty.clone(),
+                }),
+            }
+
+            self.insert_global(name, SymbolKind::Static, linkage, ty, span);
+            return Ok(());
+        }
+
+        // Functions always live in the file scope and keep their own name so
+        // that they can be linked against. A function may be declared any number
+        // of times as long as every declaration agrees, but defined only once.
+        // Returns the function's linkage.
+        pub fn declare_function(
+            &mut self,
+            name: &str,
+            ty: Type,
+            is_definition: bool,
+            storage: StorageClass,
+            span: Option<Span>,
+        ) -> Result<Linkage, DeclarationError> {
+            let scope = self.scopes.first_mut().expect("no file scope");
+            match scope.get_mut(name) {
+                Some(symbol) => match symbol.kind {
+                    SymbolKind::Function { defined } => {
+                        if symbol.ty != ty {
+                            return Err((
+                                Code::ConflictingDeclaration,
+                                format!("Conflicting types for '{}'", name),
+                                symbol.span,
+                            ));
+                        }
+                        if defined && is_definition {
+                            return Err((
+                                Code::Redefinition,
+                                format!("Redefinition of '{}'", name),
+                                symbol.span,
+                            ));
+                        }
+                        if storage == StorageClass::Static && symbol.linkage != Linkage::Internal {
+                            return Err((
+                                Code::ConflictingDeclaration,
+                                format!(
+                                    "Static declaration of '{}' follows non-static declaration",
+                                    name
+                                ),
+                                symbol.span,
+                            ));
+                        }
+                        symbol.kind = SymbolKind::Function {
+                            defined: defined || is_definition,
+                        };
+                        if is_definition {
+                            symbol.span = span;
+                        }
+                        return Ok(symbol.linkage);
+                    }
+                    _ => {
+                        return Err((
+                            Code::ConflictingDeclaration,
+                            format!("'{}' redeclared as a different kind of symbol", name),
+                            symbol.span,
+                        ));
+                    }
+                },
+                None => {
+                    let linkage = if storage == StorageClass::Static {
+                        Linkage::Internal
+                    } else {
+                        Linkage::External
+                    };
+                    let kind = SymbolKind::Function {
+                        defined: is_definition,
+                    };
+                    self.insert_global(name, kind, linkage, ty, span);
+                    return Ok(linkage);
+                }
+            }
+        }
+
+        // Finds the type named by `struct tag` or `union tag`. A definition, or
+        // a declaration that is just `struct tag;`, always refers to the
+        // current scope; any other use refers to the innermost visible
+        // declaration. If there is none, a new incomplete type is declared in
+        // the current scope. An empty tag is an anonymous struct, which is
+        // never found again.
+        pub fn declare_tag(
+            &mut self,
+            tag: &str,
+            is_union: bool,
+            current_scope_only: bool,
+        ) -> Result<Type, DeclarationError> {
+            let existing = if current_scope_only {
+                self.tags.last().expect("no open scope").get(tag).cloned()
+            } else {
+                self.tags
+                    .iter()
+                    .rev()
+                    .filter_map(|t| t.get(tag))
+                    .next()
+                    .cloned()
+            };
+            if tag.is_empty() {
+                return Ok(Type::Struct(AggregateRef::new(tag, is_union)));
+            }
+            match existing {
+                Some(ty) => {
+                    let matches = match ty {
+                        Type::Struct(ref aggregate) => aggregate.borrow().is_union == is_union,
+                        _ => false,
+                    };
+                    if !matches {
+                        return Err((
+                            Code::ConflictingDeclaration,
+                            format!(
+                            "Use of '{}' with tag type that does not match previous declaration",
+                            tag
+                        ),
+                            None,
+                        ));
+                    }
+                    return Ok(ty);
+                }
+                None => {
+                    let ty = Type::Struct(AggregateRef::new(tag, is_union));
+                    let tags = self.tags.last_mut().expect("no open scope");
+                    tags.insert(String::from(tag), ty.clone());
+                    return Ok(ty);
+                }
+            }
+        }
+
+        // Declares an enumerator in the current scope. Returns false if the
+        // name is already declared there.
+        pub fn declare_constant(&mut self, name: &str, value: i64, span: Option<Span>) -> bool {
+            if self.declared_in_current_scope(name) {
+                return false;
+            }
+            let unique_name = self.next_unique_name(name);
+            self.insert(
+                name,
+                &unique_name,
+                SymbolKind::Constant(value),
+                Linkage::None,
+                Type::Int,
+                span,
+            );
+            return true;
+        }
+
+        // Declares a typedef name in the current scope. The same typedef may be
+        // repeated as long as it names the same type.
+        pub fn declare_typedef(
+            &mut self,
+            name: &str,
+            ty: Type,
+            span: Option<Span>,
+        ) -> Result<(), DeclarationError> {
+            if let Some(symbol) = self.scopes.last().expect("no open scope").get(name) {
+                if symbol.kind != SymbolKind::Typedef {
+                    return Err((
+                        Code::ConflictingDeclaration,
+                        format!("'{}' redeclared as a different kind of symbol", name),
+                        symbol.span,
+                    ));
+                }
+                if symbol.ty != ty {
+                    return Err((
+                        Code::ConflictingDeclaration,
+                        format!("Conflicting types for typedef '{}'", name),
+                        symbol.span,
+                    ));
+                }
+                return Ok(());
+            }
+            let unique_name = self.next_unique_name(name);
+            self.insert(
+                name,
+                &unique_name,
+                SymbolKind::Typedef,
+                Linkage::None,
+                ty,
+                span,
+            );
+            return Ok(());
+        }
+
+        // Declares the tag of an enum definition in the current scope.
+        pub fn declare_enum(&mut self, tag: &str) -> Result<(), DeclarationError> {
+            let tags = self.tags.last_mut().expect("no open scope");
+            match tags.get(tag) {
+                Some(&Type::Int) => {
+                    return Err((
+                        Code::Redefinition,
+                        format!("Redefinition of 'enum {}'", tag),
+                        None,
+                    ))
+                }
+                Some(_) => {
+                    return Err((
+                        Code::ConflictingDeclaration,
+                        format!(
+                            "Use of '{}' with tag type that does not match previous declaration",
+                            tag
+                        ),
+                        None,
+                    ))
+                }
+                None => {
+                    tags.insert(String::from(tag), Type::Int);
+                    return Ok(());
+                }
+            }
+        }
+
+        // Checks that `enum tag` refers to a visible enum definition.
+        pub fn lookup_enum(&self, tag: &str) -> Result<(), DeclarationError> {
+            match self.tags.iter().rev().filter_map(|t| t.get(tag)).next() {
+                Some(&Type::Int) => return Ok(()),
+                Some(_) => {
+                    return Err((
+                        Code::ConflictingDeclaration,
+                        format!(
+                            "Use of '{}' with tag type that does not match previous declaration",
+                            tag
+                        ),
+                        None,
+                    ))
+                }
+                None => {
+                    return Err((
+                        Code::Undeclared,
+                        format!("Use of undeclared enum '{}'", tag),
+                        None,
+                    ))
+                }
+            }
+        }
+
+        pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+            for scope in self.scopes.iter().rev() {
+                if let Some(symbol) = scope.get(name) {
+                    return Some(symbol);
+                }
+            }
+            return None;
+        }
+
+        // Finds the symbol a unique name was given to, even once its scope
+        // has been exited.
+        pub fn lookup_unique(&self, unique_name: &str) -> Option<&Symbol> {
+            return self
+                .scopes
+                .iter()
+                .rev()
+                .flat_map(|scope| scope.values())
+                .find(|symbol| symbol.unique_name == unique_name)
+                .or_else(|| self.retired.get(unique_name));
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::{Definition, Linkage, StaticInit, StorageClass, SymbolKind, SymbolTable};
+        use types::types::Type;
+
+        fn function_type(param_count: usize) -> Type {
+            return Type::Function(Box::new(Type::Int), vec![Type::Int; param_count], false);
+        }
+
+        #[test]
+        fn test_shadowing() {
+            let mut table = SymbolTable::new();
+            let outer = table.declare("a", Type::Int, None).unwrap();
+            assert!(table.declare("a", Type::Int, None).is_none());
+
+            table.enter_scope();
+            let inner = table
+                .declare("a", Type::pointer_to(Type::Int), None)
+                .unwrap();
+            assert_ne!(outer, inner);
+            assert_eq!(table.lookup("a").unwrap().unique_name, inner);
+            assert!(table.lookup("a").unwrap().ty.is_pointer());
+
+            table.exit_scope();
+            assert_eq!(table.lookup("a").unwrap().unique_name, outer);
+            assert!(table.lookup("b").is_none());
+            assert!(table.lookup_unique(&inner).unwrap().ty.is_pointer());
+        }
+
+        #[test]
+        fn test_function_declarations() {
+            let mut table = SymbolTable::new();
+            let auto = StorageClass::Auto;
+            assert!(table
+                .declare_function("f", function_type(2), false, auto, None)
+                .is_ok());
+            assert!(table
+                .declare_function("f", function_type(2), true, auto, None)
+                .is_ok());
+            assert!(table
+                .declare_function("f", function_type(2), false, auto, None)
+                .is_ok());
+            assert!(table
+                .declare_function("f", function_type(2), true, auto, None)
+                .is_err());
+            assert!(table
+                .declare_function("f", function_type(3), false, auto, None)
+                .is_err());
+            assert!(table
+                .declare_function("f", function_type(2), false, StorageClass::Static, None)
+                .is_err());
+
+            table.enter_scope();
+            assert!(table
+                .declare_function("g", function_type(0), false, auto, None)
+                .is_ok());
+            table.exit_scope();
+            assert_eq!(table.lookup("g").unwrap().unique_name, "g");
+        }
+
+        #[test]
+        fn test_tentative_definitions() {
+            let mut table = SymbolTable::new();
+            let auto = StorageClass::Auto;
+            let int = Type::Int;
+            let three = vec![StaticInit::Value(Type::Int, 3)];
+            assert!(table
+                .declare_global("x", int.clone(), auto, Definition::Tentative, None)
+                .is_ok());
+            assert!(table
+                .declare_global(
+                    "x",
+                    int.clone(),
+                    StorageClass::Extern,
+                    Definition::None,
+                    None
+                )
+                .is_ok());
+            assert!(table
+                .declare_global(
+                    "x",
+                    int.clone(),
+                    auto,
+                    Definition::Initialized(three.clone()),
+                    None
+                )
+                .is_ok());
+            assert!(table
+                .declare_global("x", int.clone(), auto, Definition::Tentative, None)
+                .is_ok());
+            assert!(table
+                .declare_global(
+                    "x",
+                    int.clone(),
+                    auto,
+                    Definition::Initialized(three.clone()),
+                    None
+                )
+                .is_err());
+            assert!(table
+                .declare_global(
+                    "x",
+                    int.clone(),
+                    StorageClass::Static,
+                    Definition::Tentative,
+                    None
+                )
+                .is_err());
+            assert!(table
+                .declare_global(
+                    "x",
+                    Type::pointer_to(int),
+                    auto,
+                    Definition::Tentative,
+                    None
+                )
+                .is_err());
+
+            let object = &table.statics()[0];
+            assert_eq!(object.linkage, Linkage::External);
+            assert_eq!(object.definition, Definition::Initialized(three));
+        }
+
+        #[test]
+        fn test_tags() {
+            let mut table = SymbolTable::new();
+            let outer = table.declare_tag("s", false, false).unwrap();
+            assert_eq!(table.declare_tag("s", false, false).unwrap(), outer);
+            assert!(table.declare_tag("s", true, false).is_err());
+
+            table.enter_scope();
+            assert_eq!(table.declare_tag("s", false, false).unwrap(), outer);
+            let inner = table.declare_tag("s", false, true).unwrap();
+            assert_ne!(inner, outer);
+            assert_eq!(table.declare_tag("s", false, false).unwrap(), inner);
+            table.exit_scope();
+
+            assert_eq!(table.declare_tag("s", false, false).unwrap(), outer);
+            assert_ne!(
+                table.declare_tag("", false, false).unwrap(),
+                table.declare_tag("", false, false).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_enumerators() {
+            let mut table = SymbolTable::new();
+            assert!(table.declare_constant("A", 3, None));
+            assert!(!table.declare_constant("A", 4, None));
+            assert_eq!(table.lookup("A").unwrap().kind, SymbolKind::Constant(3));
+            assert!(table
+                .declare_global(
+                    "A",
+                    Type::Int,
+                    StorageClass::Auto,
+                    Definition::Tentative,
+                    None
+                )
+                .is_err());
+
+            assert!(table.declare_enum("e").is_ok());
+            assert!(table.declare_enum("e").is_err());
+            assert!(table.lookup_enum("e").is_ok());
+            assert!(table.lookup_enum("f").is_err());
+            table.declare_tag("s", false, false).unwrap();
+            assert!(table.lookup_enum("s").is_err());
+            assert!(table.declare_tag("e", false, false).is_err());
+        }
+
+        #[test]
+        fn test_typedefs() {
+            let mut table = SymbolTable::new();
+            let pointer = Type::pointer_to(Type::Int);
+            assert!(table.declare_typedef("T", pointer.clone(), None).is_ok());
+            assert!(table.declare_typedef("T", pointer.clone(), None).is_ok());
+            assert!(table.declare_typedef("T", Type::Int, None).is_err());
+            assert!(table.declare("T", Type::Int, None).is_none());
+
+            table.enter_scope();
+            assert!(table.declare("T", Type::Int, None).is_some());
+            assert_eq!(table.lookup("T").unwrap().kind, SymbolKind::Variable);
+            table.exit_scope();
+
+            let symbol = table.lookup("T").unwrap();
+            assert_eq!(symbol.kind, SymbolKind::Typedef);
+            assert_eq!(symbol.ty, pointer);
+        }
+
+        #[test]
+        fn test_incomplete_arrays() {
+            let mut table = SymbolTable::new();
+            let element = Box::new(Type::Int);
+            let incomplete = Type::Array(element.clone(), 0);
+            let complete = Type::Array(element.clone(), 4);
+            assert!(table
+                .declare_global(
+                    "a",
+                    incomplete.clone(),
+                    StorageClass::Extern,
+                    Definition::None,
+                    None
+                )
+                .is_ok());
+            assert!(table
+                .declare_global(
+                    "a",
+                    complete.clone(),
+                    StorageClass::Auto,
+                    Definition::Tentative,
+                    None
+                )
+                .is_ok());
+            assert!(table
+                .declare_global(
+                    "a",
+                    incomplete,
+                    StorageClass::Extern,
+                    Definition::None,
+                    None
+                )
+                .is_ok());
+            assert_eq!(table.lookup("a").unwrap().ty, complete);
+            assert_eq!(table.statics()[0].ty, complete);
+            assert!(table
+                .declare_global(
+                    "a",
+                    Type::Array(element, 5),
+                    StorageClass::Auto,
+                    Definition::None,
+                    None
+                )
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,687 @@
+#![allow(dead_code)]
+
+pub mod types {
+    use std::cell::{Ref, RefCell};
+    use std::convert::TryFrom;
+    use std::fmt::*;
+    use std::rc::Rc;
+
+    pub struct Member {
+        pub name: String,
+        pub ty: Type,
+        pub offset: i32,
+    }
+
+    // A struct or union. Its members are only known once the closing brace of
+    // its definition has been seen; until then it is an incomplete type.
+    pub struct Aggregate {
+        pub tag: String,
+        pub is_union: bool,
+        pub members: Option<Vec<Member>>,
+        pub size: i32,
+        pub align: i32,
+    }
+
+    // Every use of a struct refers to the same shared definition, so a struct
+    // can contain pointers to itself. Two struct types are the same type only
+    // if they come from the same declaration.
+    #[derive(Clone)]
+    pub struct AggregateRef(Rc<RefCell<Aggregate>>);
+
+    impl PartialEq for AggregateRef {
+        fn eq(&self, other: &AggregateRef) -> bool {
+            return Rc::ptr_eq(&self.0, &other.0);
+        }
+    }
+
+    impl Debug for AggregateRef {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}", Type::Struct(self.clone()))
+        }
+    }
+
+    fn align_to(n: i64, align: i64) -> i64 {
+        return (n + align - 1) / align * align;
+    }
+
+    impl AggregateRef {
+        pub fn new(tag: &str, is_union: bool) -> AggregateRef {
+            let aggregate = Aggregate {
+                tag: String::from(tag),
+                is_union,
+                members: None,
+                size: 0,
+                align: 1,
+            };
+            return AggregateRef(Rc::new(RefCell::new(aggregate)));
+        }
+
+        pub fn borrow(&self) -> Ref<'_, Aggregate> {
+            return self.0.borrow();
+        }
+
+        pub fn is_complete(&self) -> bool {
+            return self.0.borrow().members.is_some();
+        }
+
+        // Completes the type, laying the members out as the System V ABI does:
+        // each struct member is placed at the next offset that is a multiple
+        // of its alignment, all union members start at offset zero, and the
+        // size is rounded up to the strictest member alignment so that arrays
+        // of the type keep every element aligned. Returns false if the size
+        // does not fit in an int, in which case the offsets and size saturate.
+        pub fn define(&self, fields: Vec<(String, Type)>) -> bool {
+            // The members are laid out before the type is borrowed, as one
+            // of them may refer to it.
+            let is_union = self.0.borrow().is_union;
+            let mut members = Vec::new();
+            let mut size: i64 = 0;
+            let mut align: i64 = 1;
+            for (name, ty) in fields {
+                let (member_size, member_align) = (i64::from(ty.size()), i64::from(ty.align()));
+                let offset = if is_union {
+                    0
+                } else {
+                    align_to(size, member_align)
+                };
+                size = ::std::cmp::max(size, offset + member_size);
+                align = ::std::cmp::max(align, member_align);
+                members.push(Member {
+                    name,
+                    ty,
+                    offset: ::std::cmp::min(offset, i64::from(i32::MAX)) as i32,
+                });
+            }
+            let size = align_to(size, align);
+            let mut aggregate = self.0.borrow_mut();
+            aggregate.members = Some(members);
+            aggregate.size = ::std::cmp::min(size, i64::from(i32::MAX)) as i32;
+            aggregate.align = align as i32;
+            return size <= i64::from(i32::MAX);
+        }
+
+        // The type and offset of the named member.
+        pub fn member(&self, name: &str) -> Option<(Type, i32)> {
+            let aggregate = self.0.borrow();
+            let members = match aggregate.members {
+                Some(ref members) => members,
+                None => return None,
+            };
+            return members
+                .iter()
+                .find(|m| m.name == name)
+                .map(|m| (m.ty.clone(), m.offset));
+        }
+
+        // The types and offsets of the members in declaration order.
+        pub fn members(&self) -> Vec<(Type, i32)> {
+            let aggregate = self.0.borrow();
+            match aggregate.members {
+                Some(ref members) => {
+                    return members.iter().map(|m| (m.ty.clone(), m.offset)).collect()
+                }
+                None => return Vec::new(),
+            }
+        }
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Type {
+        Void,
+        // Plain char is a distinct type from both signed and unsigned char,
+        // though it is signed on x86-64.
+        Char,
+        SignedChar,
+        UnsignedChar,
+        Short,
+        UnsignedShort,
+        Int,
+        UnsignedInt,
+        Long,
+        UnsignedLong,
+        LongLong,
+        UnsignedLongLong,
+        Float,
+        Double,
+        Pointer(Box<Type>),
+        // Element type and length. A length of zero marks an array whose size
+        // has not been given yet, as in `int a[] = {1, 2};`.
+        Array(Box<Type>, usize),
+        // Return type, parameter types and whether further arguments may
+        // follow them, as declared by `...`.
+        Function(Box<Type>, Vec<Type>, bool),
+        Struct(AggregateRef),
+        // A qualified version of the type, which is never itself qualified.
+        // Arrays are never qualified; their elements are.
+        Qualified(Box<Type>, Qualifiers),
+    }
+
+    // The type qualifiers applied to a type.
+    #[derive(Clone, Copy, PartialEq, Debug, Default)]
+    pub struct Qualifiers {
+        pub is_const: bool,
+        pub is_volatile: bool,
+        pub is_restrict: bool,
+    }
+
+    impl Qualifiers {
+        pub fn is_empty(&self) -> bool {
+            return !self.is_const && !self.is_volatile && !self.is_restrict;
+        }
+
+        pub fn union(&self, other: Qualifiers) -> Qualifiers {
+            return Qualifiers {
+                is_const: self.is_const || other.is_const,
+                is_volatile: self.is_volatile || other.is_volatile,
+                is_restrict: self.is_restrict || other.is_restrict,
+            };
+        }
+
+        // Whether every qualifier in this set is also in the other.
+        pub fn is_subset(&self, other: Qualifiers) -> bool {
+            return self.union(other) == other;
+        }
+    }
+
+    impl Display for Qualifiers {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            let names = [
+                (self.is_const, "const"),
+                (self.is_volatile, "volatile"),
+                (self.is_restrict, "restrict"),
+            ];
+            let present: Vec<&str> = names.iter().filter(|n| n.0).map(|n| n.1).collect();
+            write!(f, "{}", present.join(" "))
+        }
+    }
+
+    impl Type {
+        pub fn pointer_to(base: Type) -> Type {
+            return Type::Pointer(Box::new(base));
+        }
+
+        // The type without its qualifiers. The qualifiers of an object only
+        // restrict how it is accessed, so most properties of a type are
+        // those of its unqualified version.
+        pub fn unqualified(&self) -> &Type {
+            match *self {
+                Type::Qualified(ref base, _) => return base,
+                _ => return self,
+            }
+        }
+
+        // The qualifiers of the type, which for an array are those of its
+        // elements.
+        pub fn qualifiers(&self) -> Qualifiers {
+            match *self {
+                Type::Qualified(_, qualifiers) => return qualifiers,
+                Type::Array(ref element, _) => return element.qualifiers(),
+                _ => return Qualifiers::default(),
+            }
+        }
+
+        // Adds the qualifiers to the type. Qualifying an array qualifies its
+        // elements instead.
+        pub fn qualified(self, qualifiers: Qualifiers) -> Type {
+            if qualifiers.is_empty() {
+                return self;
+            }
+            match self {
+                Type::Array(element, length) => {
+                    return Type::Array(Box::new(element.qualified(qualifiers)), length)
+                }
+                Type::Qualified(base, existing) => {
+                    return Type::Qualified(base, existing.union(qualifiers))
+                }
+                base => return Type::Qualified(Box::new(base), qualifiers),
+            }
+        }
+
+        pub fn size(&self) -> i32 {
+            match *self {
+                Type::Qualified(ref base, _) => return base.size(),
+                Type::Void | Type::Char | Type::SignedChar | Type::UnsignedChar => return 1,
+                Type::Short | Type::UnsignedShort => return 2,
+                Type::Int | Type::UnsignedInt | Type::Float => return 4,
+                Type::Long
+                | Type::UnsignedLong
+                | Type::LongLong
+                | Type::UnsignedLongLong
+                | Type::Double => return 8,
+                Type::Pointer(_) => return 8,
+                // The parser rejects arrays too large to lay out, so the size
+                // only saturates for types it has already reported.
+                Type::Array(ref element, length) => {
+                    return i32::try_from(length)
+                        .ok()
+                        .and_then(|length| element.size().checked_mul(length))
+                        .unwrap_or(i32::MAX);
+                }
+                Type::Function(..) => return 1,
+                Type::Struct(ref aggregate) => return aggregate.borrow().size,
+            }
+        }
+
+        pub fn align(&self) -> i32 {
+            match *self.unqualified() {
+                Type::Array(ref element, _) => return element.align(),
+                Type::Struct(ref aggregate) => return aggregate.borrow().align,
+                _ => return self.size(),
+            }
+        }
+
+        pub fn is_integer(&self) -> bool {
+            match *self.unqualified() {
+                Type::Char
+                | Type::SignedChar
+                | Type::UnsignedChar
+                | Type::Short
+                | Type::UnsignedShort
+                | Type::Int
+                | Type::UnsignedInt
+                | Type::Long
+                | Type::UnsignedLong
+                | Type::LongLong
+                | Type::UnsignedLongLong => return true,
+                _ => return false,
+            }
+        }
+
+        pub fn is_character(&self) -> bool {
+            let ty = self.unqualified();
+            return *ty == Type::Char || *ty == Type::SignedChar || *ty == Type::UnsignedChar;
+        }
+
+        pub fn is_floating(&self) -> bool {
+            let ty = self.unqualified();
+            return *ty == Type::Float || *ty == Type::Double;
+        }
+
+        pub fn is_arithmetic(&self) -> bool {
+            return self.is_integer() || self.is_floating();
+        }
+
+        pub fn is_signed(&self) -> bool {
+            match *self.unqualified() {
+                Type::Char
+                | Type::SignedChar
+                | Type::Short
+                | Type::Int
+                | Type::Long
+                | Type::LongLong => return true,
+                _ => return false,
+            }
+        }
+
+        // The integer conversion rank, which orders the integer types by size
+        // with long long above long even though both are 64 bits.
+        fn rank(&self) -> i32 {
+            match *self.unqualified() {
+                Type::Short | Type::UnsignedShort => return 2,
+                Type::Int | Type::UnsignedInt => return 3,
+                Type::Long | Type::UnsignedLong => return 4,
+                Type::LongLong | Type::UnsignedLongLong => return 5,
+                _ => return 1,
+            }
+        }
+
+        // The integer promotions: every type narrower than int is converted to
+        // int, which can represent all of its values.
+        pub fn promote(&self) -> Type {
+            if self.is_integer() && self.rank() < Type::Int.rank() {
+                return Type::Int;
+            }
+            return self.unqualified().clone();
+        }
+
+        fn to_unsigned(&self) -> Type {
+            match *self {
+                Type::Int => return Type::UnsignedInt,
+                Type::Long => return Type::UnsignedLong,
+                Type::LongLong => return Type::UnsignedLongLong,
+                _ => return self.clone(),
+            }
+        }
+
+        // The usual arithmetic conversions: if either operand is floating the
+        // other is converted to the wider floating type of the two. Otherwise
+        // both operands are promoted, then the one of lower rank is converted
+        // to the type of the other. When the signedness differs the unsigned
+        // type wins unless the signed one is wider and so can hold all of its
+        // values.
+        pub fn common_type(&self, other: &Type) -> Type {
+            let (this, other) = (self.unqualified(), other.unqualified());
+            if *this == Type::Double || *other == Type::Double {
+                return Type::Double;
+            }
+            if *this == Type::Float || *other == Type::Float {
+                return Type::Float;
+            }
+            let (a, b) = (this.promote(), other.promote());
+            if a == b {
+                return a;
+            }
+            if a.is_signed() == b.is_signed() {
+                return if a.rank() >= b.rank() { a } else { b };
+            }
+            let (unsigned, signed) = if a.is_signed() { (b, a) } else { (a, b) };
+            if unsigned.rank() >= signed.rank() {
+                return unsigned;
+            }
+            if signed.size() > unsigned.size() {
+                return signed;
+            }
+            return signed.to_unsigned();
+        }
+
+        // Maps a value of this integer type to a key that sorts the values in
+        // the type's order, since unsigned 64 bit values may appear negative.
+        pub fn order_key(&self, value: i64) -> i128 {
+            if self.size() == 8 && !self.is_signed() {
+                return i128::from(value as u64);
+            }
+            return i128::from(value);
+        }
+
+        // Converts a value to this integer type, truncating it to the type's
+        // size. Values are kept sign or zero extended to 64 bits, so those of
+        // the unsigned 64 bit types may appear negative.
+        pub fn truncate(&self, value: i64) -> i64 {
+            match (self.size(), self.is_signed()) {
+                (1, true) => return i64::from(value as i8),
+                (1, false) => return i64::from(value as u8),
+                (2, true) => return i64::from(value as i16),
+                (2, false) => return i64::from(value as u16),
+                (4, true) => return i64::from(value as i32),
+                (4, false) => return i64::from(value as u32),
+                _ => return value,
+            }
+        }
+
+        pub fn is_function(&self) -> bool {
+            if let Type::Function(..) = *self.unqualified() {
+                return true;
+            }
+            return false;
+        }
+
+        pub fn is_pointer(&self) -> bool {
+            if let Type::Pointer(_) = *self.unqualified() {
+                return true;
+            }
+            return false;
+        }
+
+        pub fn is_scalar(&self) -> bool {
+            return self.is_arithmetic() || self.is_pointer();
+        }
+
+        pub fn is_array(&self) -> bool {
+            if let Type::Array(..) = *self.unqualified() {
+                return true;
+            }
+            return false;
+        }
+
+        pub fn is_struct(&self) -> bool {
+            if let Type::Struct(_) = *self.unqualified() {
+                return true;
+            }
+            return false;
+        }
+
+        // Arrays and structs are handled by address rather than by value.
+        pub fn is_aggregate(&self) -> bool {
+            return self.is_array() || self.is_struct();
+        }
+
+        // Objects can only be defined with a complete type, one whose size is
+        // known.
+        pub fn is_complete(&self) -> bool {
+            match *self.unqualified() {
+                Type::Void => return false,
+                Type::Array(ref element, length) => return length != 0 && element.is_complete(),
+                Type::Struct(ref aggregate) => return aggregate.is_complete(),
+                _ => return true,
+            }
+        }
+
+        pub fn is_incomplete_array(&self) -> bool {
+            if let Type::Array(_, 0) = *self.unqualified() {
+                return true;
+            }
+            return false;
+        }
+
+        pub fn element(&self) -> Option<&Type> {
+            match *self.unqualified() {
+                Type::Array(ref element, _) => return Some(element),
+                _ => return None,
+            }
+        }
+
+        // Two declarations of the same object must have compatible types, which
+        // only differ in whether an array's length is known. Returns the type
+        // that combines what both of them say.
+        pub fn composite(&self, other: &Type) -> Option<Type> {
+            if self == other {
+                return Some(self.clone());
+            }
+            match (self, other) {
+                (Type::Array(a, 0), Type::Array(b, _)) if a == b => return Some(other.clone()),
+                (Type::Array(a, _), Type::Array(b, 0)) if a == b => return Some(self.clone()),
+                _ => return None,
+            }
+        }
+
+        pub fn pointee(&self) -> Option<&Type> {
+            match *self.unqualified() {
+                Type::Pointer(ref base) => return Some(base),
+                _ => return None,
+            }
+        }
+    }
+
+    impl Type {
+        // Writes the type the way C spells it, wrapping the declarator for the
+        // derived types so far around the base type: `int *[3]` is an array of
+        // pointers while `int (*)[3]` is a pointer to an array.
+        fn declarator(&self, inner: &str) -> String {
+            match *self {
+                Type::Struct(ref aggregate) => {
+                    let aggregate = aggregate.borrow();
+                    let kind = if aggregate.is_union {
+                        "union"
+                    } else {
+                        "struct"
+                    };
+                    let tag = if aggregate.tag.is_empty() {
+                        "(anonymous)"
+                    } else {
+                        aggregate.tag.as_str()
+                    };
+                    if inner.is_empty() {
+                        return format!("{} {}", kind, tag);
+                    }
+                    if inner.starts_with('[') {
+                        return format!("{} {}{}", kind, tag, inner);
+                    }
+                    return format!("{} {} {}", kind, tag, inner);
+                }
+                Type::Pointer(ref base) => return base.declarator(&format!("*{}", inner)),
+                Type::Array(ref element, length) => {
+                    let suffix = if length == 0 {
+                        String::from("[]")
+                    } else {
+                        format!("[{}]", length)
+                    };
+                    return element.declarator(&format!("{}{}", parenthesize(inner), suffix));
+                }
+                // The qualifiers of a pointer follow its star; any others
+                // come before the type.
+                Type::Qualified(ref base, qualifiers) => {
+                    if let Type::Pointer(ref pointee) = **base {
+                        if inner.is_empty() {
+                            return pointee.declarator(&format!("*{}", qualifiers));
+                        }
+                        return pointee.declarator(&format!("*{} {}", qualifiers, inner));
+                    }
+                    return format!("{} {}", qualifiers, base.declarator(inner));
+                }
+                Type::Function(ref ret, ref params, variadic) => {
+                    let mut names: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+                    if variadic {
+                        names.push(String::from("..."));
+                    }
+                    // `()` declares a function without parameters here, just
+                    // as `(void)` does, which is how it is written out.
+                    if names.is_empty() {
+                        names.push(String::from("void"));
+                    }
+                    let inner = format!("{}({})", parenthesize(inner), names.join(", "));
+                    return ret.declarator(&inner);
+                }
+                _ => {
+                    let base = self.basic_name();
+                    if inner.is_empty() || inner.starts_with('[') {
+                        return format!("{}{}", base, inner);
+                    }
+                    return format!("{} {}", base, inner);
+                }
+            }
+        }
+
+        fn basic_name(&self) -> &'static str {
+            match *self {
+                Type::Void => return "void",
+                Type::Char => return "char",
+                Type::SignedChar => return "signed char",
+                Type::UnsignedChar => return "unsigned char",
+                Type::Short => return "short",
+                Type::UnsignedShort => return "unsigned short",
+                Type::Int => return "int",
+                Type::UnsignedInt => return "unsigned int",
+                Type::Long => return "long",
+                Type::UnsignedLong => return "unsigned long",
+                Type::LongLong => return "long long",
+                Type::UnsignedLongLong => return "unsigned long long",
+                Type::Float => return "float",
+                Type::Double => return "double",
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    fn parenthesize(inner: &str) -> String {
+        if inner.starts_with('*') {
+            return format!("({})", inner);
+        }
+        return String::from(inner);
+    }
+
+    impl Display for Type {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}", self.declarator(""))
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::{Qualifiers, Type};
+
+        #[test]
+        fn test_common_type() {
+            assert_eq!(Type::Char.common_type(&Type::Short), Type::Int);
+            assert_eq!(Type::UnsignedShort.common_type(&Type::Char), Type::Int);
+            assert_eq!(Type::Int.common_type(&Type::UnsignedInt), Type::UnsignedInt);
+            assert_eq!(Type::Int.common_type(&Type::Long), Type::Long);
+            assert_eq!(Type::UnsignedInt.common_type(&Type::Long), Type::Long);
+            assert_eq!(
+                Type::UnsignedLong.common_type(&Type::LongLong),
+                Type::UnsignedLongLong
+            );
+            assert_eq!(Type::Long.common_type(&Type::LongLong), Type::LongLong);
+            assert_eq!(
+                Type::UnsignedLong.common_type(&Type::Int),
+                Type::UnsignedLong
+            );
+            assert_eq!(Type::Float.common_type(&Type::Long), Type::Float);
+            assert_eq!(Type::Char.common_type(&Type::Float), Type::Float);
+            assert_eq!(Type::Float.common_type(&Type::Double), Type::Double);
+        }
+
+        #[test]
+        fn test_truncate() {
+            assert_eq!(Type::Char.truncate(200), -56);
+            assert_eq!(Type::UnsignedChar.truncate(-1), 255);
+            assert_eq!(Type::Short.truncate(40000), -25536);
+            assert_eq!(Type::UnsignedInt.truncate(-1), 4294967295);
+            assert_eq!(Type::Int.truncate(1 << 32), 0);
+            assert_eq!(Type::UnsignedLong.truncate(-1), -1);
+        }
+
+        #[test]
+        fn test_display() {
+            assert_eq!(Type::UnsignedLongLong.to_string(), "unsigned long long");
+            assert_eq!(
+                Type::pointer_to(Type::SignedChar).to_string(),
+                "signed char *"
+            );
+            assert_eq!(
+                Type::Array(Box::new(Type::UnsignedShort), 4).to_string(),
+                "unsigned short[4]"
+            );
+            let binary = Type::Function(Box::new(Type::Int), vec![Type::Int, Type::Long], false);
+            assert_eq!(
+                Type::pointer_to(binary.clone()).to_string(),
+                "int (*)(int, long)"
+            );
+            assert_eq!(
+                Type::Array(Box::new(Type::pointer_to(binary)), 3).to_string(),
+                "int (*[3])(int, long)"
+            );
+            let printf = Type::Function(
+                Box::new(Type::Int),
+                vec![Type::pointer_to(Type::Char)],
+                true,
+            );
+            assert_eq!(printf.to_string(), "int (char *, ...)");
+            let getchar = Type::Function(Box::new(Type::Int), Vec::new(), false);
+            assert_eq!(Type::pointer_to(getchar).to_string(), "int (*)(void)");
+        }
+
+        #[test]
+        fn test_qualifiers() {
+            let constant = Qualifiers {
+                is_const: true,
+                ..Qualifiers::default()
+            };
+            let volatile = Qualifiers {
+                is_volatile: true,
+                ..Qualifiers::default()
+            };
+            let const_int = Type::Int.qualified(constant);
+            assert_eq!(const_int.to_string(), "const int");
+            assert_eq!(const_int.size(), 4);
+            assert!(const_int.is_integer() && const_int.is_signed());
+            assert_eq!(const_int.promote(), Type::Int);
+            assert_eq!(
+                const_int.clone().qualified(volatile).to_string(),
+                "const volatile int"
+            );
+            assert_eq!(
+                Type::pointer_to(Type::Char.qualified(constant)).to_string(),
+                "const char *"
+            );
+            let const_pointer = Type::pointer_to(Type::Int).qualified(constant);
+            assert_eq!(const_pointer.to_string(), "int *const");
+            assert!(const_pointer.is_pointer());
+            assert_eq!(const_pointer.pointee(), Some(&Type::Int));
+            let array = Type::Array(Box::new(Type::Int), 3).qualified(constant);
+            assert_eq!(array, Type::Array(Box::new(const_int), 3));
+            assert!(array.qualifiers().is_const);
+            assert!(constant.is_subset(constant.union(volatile)));
+            assert!(!volatile.union(constant).is_subset(constant));
+        }
+    }
+}
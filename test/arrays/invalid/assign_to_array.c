@@ -0,0 +1,6 @@
+int main() {
+    int a[3];
+    int b[3];
+    a = b;
+    return 0;
+}
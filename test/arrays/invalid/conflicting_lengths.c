@@ -0,0 +1,6 @@
+int values[3];
+int values[4];
+
+int main() {
+    return 0;
+}
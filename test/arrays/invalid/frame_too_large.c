@@ -0,0 +1,7 @@
+int main(void) {
+    char first[1500000000];
+    char second[1500000000];
+    first[0] = 1;
+    second[0] = 2;
+    return first[0] + second[0];
+}
@@ -0,0 +1,5 @@
+int main() {
+    int a[][2];
+    int b[2][];
+    return 0;
+}
@@ -0,0 +1,7 @@
+int a[2];
+int *p = 0;
+int b[2] = {1, a[0]};
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,8 @@
+int global[1000000000];
+long huge[3000000000];
+
+int main(void) {
+    char local[3000000000];
+    local[0] = 0;
+    return sizeof(char[2147483647][2]);
+}
@@ -0,0 +1,5 @@
+int main() {
+    int n = 3;
+    int a[n];
+    return 0;
+}
@@ -0,0 +1,13 @@
+int main() {
+    int x = 1;
+    int y = 2;
+    int z = 3;
+    int *ptrs[3] = {&x, &y, &z};
+    int **pp = ptrs;
+    *ptrs[1] = 20;
+    **(pp + 2) = 30;
+    int sum = 0;
+    for (int i = 0; i < 3; i = i + 1)
+        sum = sum + *ptrs[i];
+    return sum;
+}
@@ -0,0 +1,21 @@
+int fill(int values[], int n, int start) {
+    for (int i = 0; i < n; i = i + 1)
+        values[i] = start + i;
+    return n;
+}
+
+int sum_rows(int rows[][3], int n) {
+    int sum = 0;
+    for (int i = 0; i < n; i = i + 1)
+        sum = sum + rows[i][0] + rows[i][2];
+    return sum;
+}
+
+int main() {
+    int a[4];
+    int m[2][3];
+    fill(a, 4, 10);
+    fill(m[0], 3, 1);
+    fill(m[1], 3, 5);
+    return a[3] + sum_rows(m, 2);
+}
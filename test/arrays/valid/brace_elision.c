@@ -0,0 +1,8 @@
+int grid[2][2][2] = {1, 2, {3}, 4, 5, 6};
+
+int main() {
+    int m[2][3] = {1, 2, 3, 4, 5,};
+    int s = { 6 };
+    return grid[0][0][1] + grid[0][1][0] * 2 + grid[1][0][0] * 3 + grid[1][1][0] * 4
+        + m[1][1] + m[1][2] + s;
+}
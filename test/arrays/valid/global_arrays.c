@@ -0,0 +1,19 @@
+int primes[] = {2, 3, 5, 7, 11};
+int zeros[100];
+int grid[2][3] = {{1, 2, 3}, {4}};
+static int counts[4] = {0, 0, 9};
+int *pointers[3];
+
+int total(int *values, int n) {
+    int sum = 0;
+    for (int i = 0; i < n; i = i + 1)
+        sum = sum + values[i];
+    return sum;
+}
+
+int main() {
+    pointers[1] = &primes[2];
+    zeros[99] = 1;
+    return total(primes, 5) + total(grid[0], 6) + counts[2] + *pointers[1] + zeros[99]
+        + (pointers[0] == 0);
+}
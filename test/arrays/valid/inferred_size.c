@@ -0,0 +1,11 @@
+int main() {
+    int a[] = {4, 8, 15, 16, 23, 42};
+    int *end = a + 6;
+    int count = 0;
+    int sum = 0;
+    for (int *p = a; p < end; p = p + 1) {
+        count = count + 1;
+        sum = sum + *p;
+    }
+    return count * 100 + sum;
+}
@@ -0,0 +1,8 @@
+int main() {
+    int m[3][4];
+    for (int i = 0; i < 3; i = i + 1)
+        for (int j = 0; j < 4; j = j + 1)
+            m[i][j] = i * 10 + j;
+    int *row = m[2];
+    return m[1][3] + row[1] + *(*(m + 2) + 3);
+}
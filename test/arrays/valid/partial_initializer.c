@@ -0,0 +1,9 @@
+int main() {
+    int a[6] = {1, 2};
+    int b[2][3] = {{1}, {4, 5}};
+    int c[2][2] = {1, 2, 3};
+    int result = 0;
+    for (int i = 0; i < 6; i = i + 1)
+        result = result + a[i] * (i + 1);
+    return result * 100 + b[0][1] + b[0][2] * 2 + b[1][1] * 3 + c[1][0] + c[1][1];
+}
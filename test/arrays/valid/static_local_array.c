@@ -0,0 +1,12 @@
+int next(void) {
+    static int history[4] = {1, 1};
+    static int index = 2;
+    history[index] = history[index - 1] + history[index - 2];
+    index = index + 1;
+    return history[index - 1];
+}
+
+int main() {
+    next();
+    return next();
+}
@@ -0,0 +1,9 @@
+int main() {
+    int a[3] = {7, 8, 9};
+    int *p = &a[1];
+    int x = 1[a];
+    int y = p[-1];
+    int z = *(&a[0] + 2);
+    int w = (*&a)[2];
+    return x * 1000 + y * 100 + z * 10 + (w == z) + (p - a);
+}
@@ -0,0 +1,9 @@
+int main() {
+    int a[5];
+    for (int i = 0; i < 5; i = i + 1)
+        a[i] = i * i;
+    int sum = 0;
+    for (int i = 0; i < 5; i = i + 1)
+        sum = sum + a[i];
+    return sum;
+}
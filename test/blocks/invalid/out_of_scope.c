@@ -0,0 +1,6 @@
+int main() {
+    {
+        int a = 2;
+    }
+    return a;
+}
@@ -0,0 +1,8 @@
+int main() {
+    int a = 2;
+    {
+        int a = 3;
+        int b = a + 1;
+        return a * b - ~0;
+    }
+}
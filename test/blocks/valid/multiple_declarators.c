@@ -0,0 +1,39 @@
+int first = 2, second, *pointer = &first;
+int twice(int x), thrice(int x);
+typedef int number, *number_pointer;
+
+struct point {
+    int x, y;
+    long z, *w;
+};
+
+enum color { RED, GREEN = 4 } background = GREEN, foreground;
+
+int twice(int x) {
+    return 2 * x;
+}
+
+int thrice(int x) {
+    return 3 * x;
+}
+
+int main(void) {
+    int a = 1, b;
+    int *p, q;
+    struct point left, right = {3, 4, 5, 0};
+    number n = 7, *np = &n;
+    number_pointer other = np;
+    static int calls = 1, limit;
+    extern int second, first;
+    int total = 0;
+    b = 2;
+    q = 5;
+    p = &q;
+    left.x = 1;
+    left.y = a + b;
+    for (int i = 0, j = 4; i < j; i = i + 1)
+        total = total + i + j;
+    return total + a + b + *p + left.x + left.y + right.x + right.y + (int) right.z + *other +
+           calls + limit + first + second + *pointer + twice(1) + thrice(1) + background +
+           foreground;
+}
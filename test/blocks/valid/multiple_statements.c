@@ -0,0 +1,8 @@
+int main() {
+    int a;
+    int b = 4;
+    a = b = 6;
+    ;
+    {}
+    return (a + b) % 7 == 5 && !(a < b) || -a;
+}
@@ -0,0 +1,15 @@
+int main() {
+    int x = 1;
+    {
+        int y = x + 1;
+        {
+            int x = y * 10;
+            {
+                x = x + 1;
+            }
+            y = x;
+        }
+        x = y - x;
+    }
+    return x;
+}
@@ -0,0 +1,8 @@
+int main() {
+    int a = 1;
+    {
+        int a = 2;
+        a = a + 40;
+    }
+    return a;
+}
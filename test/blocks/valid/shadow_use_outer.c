@@ -0,0 +1,10 @@
+int main() {
+    int a = 3;
+    int b = 0;
+    {
+        b = a * 2;
+        int a = 10;
+        b = b + a;
+    }
+    return b;
+}
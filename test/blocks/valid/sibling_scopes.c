@@ -0,0 +1,12 @@
+int main() {
+    int total = 0;
+    {
+        int v = 5;
+        total = total + v;
+    }
+    {
+        int v = 7;
+        total = total + v;
+    }
+    return total;
+}
@@ -0,0 +1,5 @@
+int main() {
+    double d = 1.0;
+    int *p = (int *)d;
+    return 0;
+}
@@ -0,0 +1,9 @@
+struct point {
+    int x;
+};
+
+int main() {
+    struct point p;
+    p.x = 1;
+    return (int)p;
+}
@@ -0,0 +1,9 @@
+struct point {
+    int x;
+};
+
+int main() {
+    int value = 1;
+    struct point p = (struct point)value;
+    return 0;
+}
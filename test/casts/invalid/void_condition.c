@@ -0,0 +1,8 @@
+void nothing() {
+}
+
+int main() {
+    if (nothing())
+        return 1;
+    return 0;
+}
@@ -0,0 +1,7 @@
+int take(void value) {
+    return 0;
+}
+
+int main() {
+    return 0;
+}
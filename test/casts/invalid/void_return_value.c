@@ -0,0 +1,8 @@
+void nothing() {
+    return 1;
+}
+
+int main() {
+    nothing();
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main() {
+    int x = 1;
+    int y = (void)x;
+    return y;
+}
@@ -0,0 +1,37 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+
+long table = (long)3.9;
+char small = (char)1000;
+unsigned int wrapped = (unsigned int)-1;
+double ratio = (double)1 / 8;
+int *none = (int *)0;
+void *nothing = (void *)0;
+char sizes[(int)sizeof(long) + (int)2.5];
+
+int main() {
+    print(table);
+    print(small);
+    print(wrapped);
+    print((long)(ratio * 1000));
+    print(none == 0);
+    print(nothing == 0);
+    print(sizeof sizes);
+    putchar(10);
+    return 0;
+}
@@ -0,0 +1,88 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+
+struct pair {
+    int first;
+    int second;
+};
+
+int side_effects = 0;
+
+int touch() {
+    side_effects = side_effects + 1;
+    return 7;
+}
+
+void ignore(int value) {
+    if (value < 0)
+        return;
+    side_effects = side_effects + value;
+}
+
+typedef unsigned char byte;
+
+int main() {
+    long big = 4294967298;
+    double d = -3.75;
+    int negative = -1;
+
+    print((int)big);
+    print((char)300);
+    print((byte)300);
+    print((unsigned char)negative);
+    print((short)70000);
+    print((unsigned short)negative);
+    print((unsigned int)negative);
+    print((long)(unsigned int)negative);
+    print((long)negative);
+    print((int)d);
+    print((long)(d * 100));
+    print((int)(float)d);
+    print((unsigned long)2.5e18 / 1000000000);
+    print((long)((double)1 / 4 * 1000));
+    print((int)(long long)7);
+    print((int)-d);
+    putchar(10);
+
+    struct pair pairs[2];
+    pairs[0].first = 1;
+    pairs[0].second = 2;
+    pairs[1].first = 3;
+    long address = (long)&pairs[1];
+    struct pair *again = (struct pair *)address;
+    print(again->first);
+    int *as_ints = (int *)pairs;
+    print(as_ints[1] + as_ints[2]);
+    char *bytes = (char *)&pairs[1];
+    print((long)bytes - (long)pairs);
+    void *anything = (void *)as_ints;
+    int *back = (int *)anything;
+    print(*back);
+    int *null = (void *)0;
+    print(null == 0);
+    print((long)(char *)0);
+
+    (void)touch();
+    (void)big;
+    ignore(5);
+    ignore(-5);
+    print(side_effects);
+    print((int)(1 == 1) + (int)sizeof(int));
+    putchar(10);
+    return (char)513;
+}
@@ -0,0 +1,6 @@
+enum { A };
+
+int main() {
+    A = 3;
+    return 0;
+}
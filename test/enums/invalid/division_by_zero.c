@@ -0,0 +1,5 @@
+enum { A = 1 / 0 };
+
+int main() {
+    return 0;
+}
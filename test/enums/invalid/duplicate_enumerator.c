@@ -0,0 +1,5 @@
+enum { A, B, A };
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,6 @@
+int x = 3;
+enum { A = x };
+
+int main() {
+    return 0;
+}
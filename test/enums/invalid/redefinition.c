@@ -0,0 +1,6 @@
+enum e { X };
+enum e { Y };
+
+int main() {
+    return 0;
+}
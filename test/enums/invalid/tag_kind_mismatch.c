@@ -0,0 +1,8 @@
+struct e {
+    int a;
+};
+enum e { X };
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,5 @@
+enum { BIG = 2147483647, OVERFLOW };
+
+int main() {
+    return 0;
+}
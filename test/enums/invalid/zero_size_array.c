@@ -0,0 +1,6 @@
+enum { ZERO };
+int values[ZERO];
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,18 @@
+enum sizes { ROWS = 3, COLS = ROWS + 1, CELLS = ROWS * COLS };
+
+int grid[ROWS][COLS];
+int counts[CELLS - 2];
+
+int main() {
+    int local[COLS * 2];
+    int total = 0;
+    for (int i = 0; i < ROWS; i = i + 1)
+        for (int j = 0; j < COLS; j = j + 1)
+            grid[i][j] = i + j;
+    for (int i = 0; i < COLS * 2; i = i + 1)
+        local[i] = i;
+    for (int i = 0; i < ROWS; i = i + 1)
+        total = total + grid[i][COLS - 1];
+    counts[CELLS - 3] = local[7];
+    return total + counts[9];
+}
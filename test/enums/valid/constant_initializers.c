@@ -0,0 +1,10 @@
+enum level { LOW = 1, MEDIUM = 4, HIGH = 16 };
+
+int threshold = MEDIUM + HIGH;
+static int levels[] = {LOW, MEDIUM, HIGH, HIGH - LOW};
+
+int main() {
+    static int counter = HIGH / MEDIUM;
+    counter = counter + 1;
+    return threshold + levels[3] + counter + (MEDIUM > LOW && HIGH != 0);
+}
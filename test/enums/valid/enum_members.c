@@ -0,0 +1,22 @@
+enum kind { NUMBER, POINTER };
+
+struct value {
+    enum kind kind;
+    int number;
+    int *pointer;
+};
+
+int read(struct value *v) {
+    if (v->kind == NUMBER)
+        return v->number;
+    return *v->pointer;
+}
+
+int main() {
+    int x = 30;
+    struct value a = {NUMBER, 12};
+    struct value b = {POINTER, 0, &x};
+    enum kind k;
+    k = b.kind;
+    return read(&a) + read(&b) + k;
+}
@@ -0,0 +1,12 @@
+enum {
+    FIRST = 5,
+    SECOND,
+    NEGATIVE = -3,
+    AFTER_NEGATIVE,
+    DERIVED = SECOND * 2 + 1,
+    LAST,
+};
+
+int main() {
+    return FIRST + SECOND + NEGATIVE + AFTER_NEGATIVE + DERIVED + LAST;
+}
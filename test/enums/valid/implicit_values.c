@@ -0,0 +1,6 @@
+enum color { RED, GREEN, BLUE };
+
+int main() {
+    enum color c = BLUE;
+    return RED * 100 + GREEN * 10 + c;
+}
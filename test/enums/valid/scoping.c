@@ -0,0 +1,16 @@
+enum { A = 1, B = 2 };
+
+int main() {
+    int result = A;
+    {
+        int A = 10;
+        enum { B = 20, C };
+        result = result + A + B + C;
+    }
+    {
+        enum shade { A = 100 };
+        enum shade s = A;
+        result = result + s;
+    }
+    return result + A + B;
+}
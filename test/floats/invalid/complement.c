@@ -0,0 +1,4 @@
+int main() {
+    float f = 1.0f;
+    return ~f;
+}
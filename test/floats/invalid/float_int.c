@@ -0,0 +1,4 @@
+int main() {
+    float int x = 1.0;
+    return 0;
+}
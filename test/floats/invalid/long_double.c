@@ -0,0 +1,4 @@
+int main() {
+    long double d = 1.0;
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main() {
+    double d = 0.0;
+    int *p = d;
+    return 0;
+}
@@ -0,0 +1,59 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int print_fixed(double x) {
+    long scaled = x * 1000;
+    return print(scaled);
+}
+
+double average(double a, double b) {
+    return (a + b) / 2;
+}
+
+float scale(float x, int factor) {
+    return x * factor;
+}
+
+int main() {
+    double a = 1.5;
+    double b = -2.25;
+    float f = 0.1f;
+    float g = 3e2f;
+
+    print_fixed(a + b);
+    print_fixed(a - b);
+    print_fixed(a * b);
+    print_fixed(a / b);
+    print_fixed(-a);
+    print_fixed(f + g);
+    print_fixed(f * 10);
+    print_fixed(average(a, b));
+    print_fixed(scale(f, 7));
+    print_fixed(.5 + 1e-3);
+    print_fixed(2.5E+2);
+    print_fixed(1 / 3.0);
+
+    double sum = 0;
+    for (int i = 1; i <= 10; i = i + 1)
+        sum = sum + 1.0 / i;
+    print_fixed(sum);
+
+    float mixed = f + a;
+    print_fixed(mixed);
+    putchar(10);
+    return a > b;
+}
@@ -0,0 +1,58 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int print_fixed(double x) {
+    long scaled = x * 1000;
+    return print(scaled);
+}
+
+int main() {
+    double zero = 0.0;
+    double nan = zero / zero;
+    double one = 1.0;
+    float half = 0.5f;
+
+    print(one == 1);
+    print(one != 1);
+    print(one < half);
+    print(one <= 1.0);
+    print(one > half);
+    print(half >= one);
+    print(nan == nan);
+    print(nan != nan);
+    print(nan < one);
+    print(nan > one);
+    print(nan <= one);
+    print(nan >= one);
+    print(!zero);
+    print(!nan);
+    print(!half);
+
+    if (nan)
+        print(1);
+    if (zero)
+        print(2);
+    while (half) {
+        print(3);
+        half = 0;
+    }
+    print(zero && one);
+    print(zero || one);
+    print(-zero == zero);
+    putchar(10);
+    return 0;
+}
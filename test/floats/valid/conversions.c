@@ -0,0 +1,65 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int print_fixed(double x) {
+    long scaled = x * 1000;
+    return print(scaled);
+}
+
+int main() {
+    double d = -7.9;
+    int i = d;
+    long l = 1e15;
+    char c = 65.7;
+    short s = -300.5;
+    unsigned int u = 4000000000.0;
+    unsigned long big = 1.5e19;
+    unsigned long small = 12345.9;
+    unsigned char uc = 200.2f;
+
+    print(i);
+    print(l);
+    print(c);
+    print(s);
+    print(u);
+    print(big / 1000000000);
+    print(small);
+    print(uc);
+
+    unsigned long top = 18446744073709551615ul;
+    double from_top = top;
+    print(from_top == 18446744073709551616.0);
+    unsigned long odd = 9007199254740993ul;
+    double rounded = odd;
+    print(rounded == 9007199254740992.0);
+    unsigned int large = 4294967295u;
+    double from_large = large;
+    print_fixed(from_large / 1000000);
+    long negative = -5000000000;
+    float from_negative = negative;
+    print_fixed(from_negative / 1000000);
+    char minus = -3;
+    double from_char = minus;
+    print_fixed(from_char);
+
+    float narrow = 1.0 / 3;
+    double wide = narrow;
+    print(wide == 1.0 / 3);
+    print_fixed(wide);
+    putchar(10);
+    return i;
+}
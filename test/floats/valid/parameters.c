@@ -0,0 +1,49 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int print_fixed(double x) {
+    long scaled = x * 1000;
+    return print(scaled);
+}
+
+double mix(int a, double b, long c, float d, char e, double f, int g, double h, int i,
+           double j, double k, int l, double m, double n, float o) {
+    return a + b + c + d + e + f + g + h + i + j + k + l + m + n + o;
+}
+
+double weigh(double a, double b, double c, double d, double e, double f, double g,
+             double h, double i, double j) {
+    return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g + 8 * h + 9 * i + 10 * j;
+}
+
+long ints(int a, int b, int c, int d, int e, int f, int g, double h, int i) {
+    return a + b + c + d + e + f + g * h + i;
+}
+
+float half(float x) {
+    return x / 2;
+}
+
+int main() {
+    print_fixed(mix(1, 0.5, 3, 0.25f, 5, 0.125, 7, 8.5, 9, 10.25, 11, 12, 13.5, 14, 15.75f));
+    print_fixed(weigh(0.5, 1, 1.5, 2, 2.5, 3, 3.5, 4, 4.5, 5));
+    print(ints(1, 2, 3, 4, 5, 6, 7, 0.5, 9));
+    print_fixed(half(half(3)));
+    print_fixed(weigh(half(1), 1, 1, 1, 1, 1, 1, 1, 1, half(half(8))));
+    putchar(10);
+    return 0;
+}
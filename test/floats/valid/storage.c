@@ -0,0 +1,68 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int print_fixed(double x) {
+    long scaled = x * 1000;
+    return print(scaled);
+}
+
+struct point {
+    char tag;
+    double x;
+    float y;
+};
+
+double global_double = 2.75;
+float global_float = -1.5f;
+double from_int = 3;
+float from_double = 0.1;
+static double zero;
+double table[3] = {1.25, 2, 3.5f};
+struct point origin = {1, 0.5, 0.25};
+
+double accumulate(double amount) {
+    static double total = 10;
+    total = total + amount;
+    return total;
+}
+
+int main() {
+    print_fixed(global_double);
+    print_fixed(global_float);
+    print_fixed(from_int);
+    print_fixed(from_double);
+    print_fixed(zero);
+    print_fixed(table[0] + table[1] + table[2]);
+    print_fixed(origin.x + origin.y + origin.tag);
+    accumulate(1.5);
+    print_fixed(accumulate(2));
+
+    struct point p = {2, 1.75, 0.5f};
+    struct point *q = &p;
+    q->x = q->x * 2;
+    q->y = q->y + q->x;
+    print_fixed(p.x);
+    print_fixed(p.y);
+
+    double values[4];
+    double *cursor = values;
+    for (int i = 0; i < 4; i = i + 1)
+        values[i] = i * 0.5;
+    print_fixed(*(cursor + 3));
+    putchar(10);
+    return 0;
+}
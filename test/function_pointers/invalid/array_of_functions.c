@@ -0,0 +1,5 @@
+int table[3](int);
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,9 @@
+int one() {
+    return 1;
+}
+
+int main() {
+    int (*fp)() = one;
+    fp = fp + 1;
+    return 0;
+}
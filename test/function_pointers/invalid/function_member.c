@@ -0,0 +1,7 @@
+struct plugin {
+    int run(int);
+};
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,5 @@
+int make()[3];
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,5 @@
+int make(int)(int);
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,10 @@
+int one() {
+    return 1;
+}
+
+int (*fp)() = one;
+int (*copy)() = fp;
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    static int helper(int);
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main() {
+    int (*fp(int, int);
+    return 0;
+}
@@ -0,0 +1,8 @@
+int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    long (*fp)(int, int) = add;
+    return fp(1, 2);
+}
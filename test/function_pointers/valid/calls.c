@@ -0,0 +1,78 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+int add(int a, int b) {
+    return a + b;
+}
+
+int subtract(int a, int b) {
+    return a - b;
+}
+
+int multiply(int a, int b) {
+    return a * b;
+}
+
+int apply(int (*operation)(int, int), int a, int b) {
+    return operation(a, b);
+}
+
+int twice(int f(int, int), int a) {
+    return f(f(a, a), a);
+}
+
+int (*choose(int which))(int, int) {
+    if (which == 0)
+        return add;
+    if (which == 1)
+        return &subtract;
+    return multiply;
+}
+
+int main() {
+    int (*fp)(int, int) = add;
+    print(fp(2, 3));
+    print((*fp)(4, 5));
+    print((**fp)(6, 7));
+    fp = &multiply;
+    print(fp(6, 7));
+    print(apply(subtract, 10, 4));
+    print(apply(&add, 10, 4));
+    print(twice(multiply, 3));
+    print(choose(1)(20, 8));
+    print((*choose(2))(5, 5));
+
+    int (*table[3])(int, int);
+    table[0] = add;
+    table[1] = subtract;
+    table[2] = multiply;
+    int total = 0;
+    for (int i = 0; i < 3; i = i + 1)
+        total = total + table[i](7, 2);
+    print(total);
+
+    int (**cursor)(int, int) = table;
+    print((*(cursor + 2))(3, 3));
+    print(fp == multiply);
+    print(fp != add);
+    fp = 0;
+    print(fp == 0);
+    print(sizeof fp);
+    print(sizeof table);
+    print(sizeof(int (*)(int, int)));
+    putchar(10);
+    return 0;
+}
@@ -0,0 +1,77 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+typedef int (*handler)(int);
+typedef int transform(int);
+
+struct plugin {
+    int id;
+    handler run;
+    double (*scale)(double, float);
+};
+
+int negate(int x) {
+    return -x;
+}
+
+int square(int x) {
+    return x * x;
+}
+
+int increment(int x) {
+    return x + 1;
+}
+
+double scale(double value, float factor) {
+    return value * factor;
+}
+
+long many(long a, long b, long c, long d, long e, long f, long g, long h) {
+    return a - b + c - d + e - f + g - h;
+}
+
+transform negate;
+handler handlers[] = {negate, square, increment, 0};
+static struct plugin plugins[2] = {{1, square, scale}, {2, negate, 0}};
+int (*print_number)(long) = print;
+
+int run_all(handler *list, int value) {
+    while (*list) {
+        value = (*list)(value);
+        list = list + 1;
+    }
+    return value;
+}
+
+int main() {
+    print(run_all(handlers, 3));
+    print(run_all(handlers + 1, -4));
+    for (int i = 0; i < 2; i = i + 1) {
+        struct plugin *p = &plugins[i];
+        print(p->id);
+        print(p->run(p->id + 5));
+        if (p->scale)
+            print((long)(p->scale(2.5, 4.0f) * 10));
+    }
+    transform *local = increment;
+    print(local(41));
+    long (*wide)(long, long, long, long, long, long, long, long) = many;
+    print(wide(100, 1, 2, 3, 4, 5, 6, 7));
+    print_number(1234);
+    int (*put)(int) = putchar;
+    put(10);
+    return handlers[3] == 0;
+}
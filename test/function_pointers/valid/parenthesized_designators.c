@@ -0,0 +1,17 @@
+struct holder {
+    int a[3];
+};
+
+int twice(int x) {
+    return 2 * x;
+}
+
+int main(void) {
+    int a[4] = {1, 2, 3, 4};
+    struct holder s = {{5, 6, 7}};
+    int (*whole)[4] = &(a);
+    int (*member)[3] = &(s.a);
+    int (*function)(int) = &(twice);
+    int (*plain)(int) = (twice);
+    return (*whole)[3] + (*member)[1] + function(3) + plain(1) + (int) sizeof((a)) + (a)[2] + (twice)(4);
+}
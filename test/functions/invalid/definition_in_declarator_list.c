@@ -0,0 +1,7 @@
+int a, f(void) {
+    return 0;
+}
+
+int main(void) {
+    return f();
+}
@@ -0,0 +1,12 @@
+int add(int a, int b) {
+    return a + b;
+}
+
+int twice(int x) {
+    return add(x, x);
+}
+
+int main() {
+    int a = 3;
+    return add(twice(a), add(1, twice(add(a, 1)))) * 2 - twice(2);
+}
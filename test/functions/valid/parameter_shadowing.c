@@ -0,0 +1,13 @@
+int f(int a) {
+    {
+        int a = 5;
+        a = a + 1;
+    }
+    a = a * 3;
+    return a;
+}
+
+int main() {
+    int a = 4;
+    return f(a) + a;
+}
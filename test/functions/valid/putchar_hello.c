@@ -0,0 +1,15 @@
+int putchar(int c);
+
+int print_digit(int d) {
+    return putchar(48 + d);
+}
+
+int main() {
+    putchar(72);
+    putchar(105);
+    putchar(10);
+    for (int i = 0; i < 10; i = i + 1)
+        print_digit(i);
+    putchar(10);
+    return 0;
+}
@@ -0,0 +1,7 @@
+int last(int a, int b, int c, int d, int e, int f, int g) {
+    return g - a;
+}
+
+int main() {
+    return last(1, 2, 3, 4, 5, 6, 50);
+}
@@ -0,0 +1,7 @@
+int weighted(int a, int b, int c, int d, int e, int f, int g, int h, int i) {
+    return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g + 8 * h + 9 * i;
+}
+
+int main() {
+    return weighted(1, 2, 3, 4, 5, 6, 7, 8, 9) - 200;
+}
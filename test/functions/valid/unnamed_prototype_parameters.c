@@ -0,0 +1,9 @@
+int sub(int, int);
+
+int main() {
+    return sub(50, 8);
+}
+
+int sub(int x, int y) {
+    return x - y;
+}
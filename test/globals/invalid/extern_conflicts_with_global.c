@@ -0,0 +1,9 @@
+int x;
+
+int main(void) {
+    int x = 1;
+    {
+        extern long x;
+    }
+    return x;
+}
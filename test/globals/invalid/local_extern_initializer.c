@@ -0,0 +1,4 @@
+int main() {
+    extern int x = 3;
+    return x;
+}
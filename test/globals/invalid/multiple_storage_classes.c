@@ -0,0 +1,5 @@
+static extern int x;
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,8 @@
+int f();
+static int f() {
+    return 1;
+}
+
+int main() {
+    return f();
+}
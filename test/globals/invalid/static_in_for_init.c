@@ -0,0 +1,5 @@
+int main() {
+    for (static int i = 0; i < 3; i = i + 1) {
+    }
+    return 0;
+}
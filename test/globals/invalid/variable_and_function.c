@@ -0,0 +1,9 @@
+int f;
+
+int f() {
+    return 0;
+}
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,22 @@
+struct pair {
+    int tag;
+    int m[3];
+};
+
+int a[4] = {1, 2, 3, 4};
+struct pair g = {5, {6, 7, 8}};
+char text[] = "offsets";
+
+int *second = &a[1];
+int *third = a + 2;
+int *last = &a[4] - 1;
+int *member = &g.m[1];
+int *whole = &g.tag;
+char *tail = text + 3;
+static int *locals[2] = {a + 1, &g.m[2]};
+
+int main(void) {
+    int total = *second + *third + *last + *member + *whole;
+    total = total + *locals[0] + *locals[1];
+    return total + *tail;
+}
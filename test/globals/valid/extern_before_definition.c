@@ -0,0 +1,10 @@
+extern int limit;
+
+int main() {
+    int sum = 0;
+    for (int i = 0; i < limit; i = i + 1)
+        sum = sum + i;
+    return sum;
+}
+
+int limit = 8;
@@ -0,0 +1,15 @@
+int value = 9;
+int get();
+
+int main() {
+    int value = 1;
+    {
+        extern int value;
+        value = value + 1;
+    }
+    return value * 100 + get();
+}
+
+int get() {
+    return value;
+}
@@ -0,0 +1,8 @@
+static int state = 4;
+extern int state;
+int static spare;
+
+int main() {
+    spare = state * 2;
+    return spare + state;
+}
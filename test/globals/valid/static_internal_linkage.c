@@ -0,0 +1,10 @@
+static int hidden = -3;
+static int helper(int a);
+
+int main() {
+    return helper(hidden) + 50;
+}
+
+static int helper(int a) {
+    return a * 4;
+}
@@ -0,0 +1,18 @@
+int next_id() {
+    static int id = 10;
+    id = id + 1;
+    return id;
+}
+
+int other() {
+    static int id;
+    id = id + 100;
+    return id;
+}
+
+int main() {
+    next_id();
+    next_id();
+    other();
+    return next_id() + other();
+}
@@ -0,0 +1,10 @@
+int x;
+int x;
+extern int x;
+
+int main() {
+    x = x + 7;
+    return x;
+}
+
+int x;
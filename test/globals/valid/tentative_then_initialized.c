@@ -0,0 +1,12 @@
+int total;
+
+int add(int n) {
+    total = total + n;
+    return total;
+}
+
+int total = 30;
+
+int main() {
+    return add(12);
+}
@@ -0,0 +1,7 @@
+int main() {
+    int x = 0;
+here:
+    x = x + 1;
+here:
+    return x;
+}
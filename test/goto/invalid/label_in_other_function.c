@@ -0,0 +1,9 @@
+int helper() {
+target:
+    return 1;
+}
+
+int main() {
+    goto target;
+    return 0;
+}
@@ -0,0 +1,10 @@
+int main() {
+    int i = 0;
+    int total = 0;
+again:
+    total = total + i;
+    i = i + 1;
+    if (i < 10)
+        goto again;
+    return total;
+}
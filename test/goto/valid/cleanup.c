@@ -0,0 +1,33 @@
+int resources[3];
+
+int acquire(int i) {
+    if (i == 2)
+        return 0;
+    resources[i] = 1;
+    return 1;
+}
+
+int setup() {
+    int status = 0;
+    if (!acquire(0))
+        goto fail;
+    if (!acquire(1))
+        goto release_first;
+    if (!acquire(2))
+        goto release_second;
+    return 100;
+
+release_second:
+    resources[1] = 0;
+    status = status + 10;
+release_first:
+    resources[0] = 0;
+    status = status + 1;
+fail:
+    return status;
+}
+
+int main() {
+    int result = setup();
+    return result + resources[0] + resources[1];
+}
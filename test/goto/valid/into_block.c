@@ -0,0 +1,18 @@
+int main() {
+    int total = 1;
+    goto inside;
+    {
+        total = 100;
+    inside:
+        total = total + 2;
+    }
+    switch (total) {
+    case 3:
+        goto three;
+    default:
+        return 0;
+    }
+three:
+    label_on_null:;
+    return total * 10;
+}
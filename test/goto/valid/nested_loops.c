@@ -0,0 +1,13 @@
+int main() {
+    int found = 0;
+    for (int i = 0; i < 10; i = i + 1) {
+        for (int j = 0; j < 10; j = j + 1) {
+            if (i * j == 42) {
+                found = i * 10 + j;
+                goto done;
+            }
+        }
+    }
+done:
+    return found;
+}
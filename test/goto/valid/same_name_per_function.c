@@ -0,0 +1,24 @@
+int first(int x) {
+    if (x)
+        goto out;
+    x = 5;
+out:
+    return x + 1;
+}
+
+int second(int x) {
+    goto out;
+    x = 100;
+out:
+    return x * 2;
+}
+
+int out = 3;
+
+int main() {
+    int out = 4;
+    goto skip;
+    out = 0;
+skip:
+    return first(0) + first(2) + second(7) + out;
+}
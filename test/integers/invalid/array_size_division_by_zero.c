@@ -0,0 +1,5 @@
+int values[4 / (2 - 2)];
+
+int main(void) {
+    return 0;
+}
@@ -0,0 +1 @@
+int main() { char int x = 1; return x; }
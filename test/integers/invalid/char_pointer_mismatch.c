@@ -0,0 +1 @@
+int main() { char c = 1; short *p = &c; return *p; }
@@ -0,0 +1 @@
+int main() { unsigned unsigned x = 1; return x; }
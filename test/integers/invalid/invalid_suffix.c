@@ -0,0 +1 @@
+int main() { long x = 10lul; return 0; }
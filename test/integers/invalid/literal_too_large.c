@@ -0,0 +1,4 @@
+int main(void) {
+    long big = 99999999999999999999;
+    return big > 0;
+}
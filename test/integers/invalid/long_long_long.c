@@ -0,0 +1,2 @@
+long long long x;
+int main() { return 0; }
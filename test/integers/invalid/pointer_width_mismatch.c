@@ -0,0 +1 @@
+int main() { long x = 5; long *p = &x; int *q = p; return *q; }
@@ -0,0 +1 @@
+int main() { short long x = 1; return x; }
@@ -0,0 +1 @@
+int main() { signed unsigned x = 1; return x; }
@@ -0,0 +1,2 @@
+struct s { int a; };
+int main() { unsigned struct s x; return 0; }
@@ -0,0 +1,62 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+long factorial(long n) {
+    if (n <= 1)
+        return 1;
+    return n * factorial(n - 1);
+}
+
+long long fibonacci(int n) {
+    long long a = 0;
+    long long b = 1;
+    for (int i = 0; i < n; i = i + 1) {
+        long long next = a + b;
+        a = b;
+        b = next;
+    }
+    return a;
+}
+
+int main() {
+    long big = 5000000000;
+    long hex = 0x123456789aL;
+    long octal = 0777;
+    unsigned long huge = 18446744073709551615ul;
+    long long negative = -9000000000000LL;
+
+    print(big);
+    print(big * 3);
+    print(big / 7);
+    print(big % 7);
+    print(hex);
+    print(octal);
+    print(huge == -1);
+    print(huge / 3 > big);
+    print(negative / 1000);
+    print(factorial(20));
+    print(fibonacci(90));
+    print(2147483647 + 1L);
+    print(2147483648);
+    print(0xffffffff);
+    print(-2147483648);
+
+    int truncated = big;
+    print(truncated);
+    putchar(10);
+    return big > hex;
+}
@@ -0,0 +1,47 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+char to_char(int x) {
+    return x;
+}
+
+unsigned short to_ushort(long x) {
+    return x;
+}
+
+long mix(char a, short b, int c, long d, unsigned char e, unsigned short f, char g, long h) {
+    return a + b + c + d + e + f + g + h;
+}
+
+int sign_of(signed char c) {
+    if (c < 0)
+        return -1;
+    return c > 0;
+}
+
+int main() {
+    print(to_char(300));
+    print(to_char(-129));
+    print(to_ushort(-2));
+    print(to_ushort(70000));
+    print(mix(-1, -2, -3, 10000000000, 255, 65535, 200, -5));
+    print(sign_of(128));
+    print(sign_of(127));
+    print(sign_of(256));
+    putchar(10);
+    return to_char(511) + 10;
+}
@@ -0,0 +1,55 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+char text[6] = {104, 101, 108, 108, 111, 0};
+short shorts[4] = {-1, 2, -3, 4};
+long longs[3] = {10000000000, -2, 3};
+unsigned char bytes[] = {255, 128, 1};
+
+int length(char *s) {
+    int n = 0;
+    while (*s) {
+        s = s + 1;
+        n = n + 1;
+    }
+    return n;
+}
+
+int main() {
+    char *p = text;
+    for (char *q = text; *q; q = q + 1)
+        putchar(*q);
+    putchar(10);
+
+    *(p + 1) = 97;
+    p[4] = p[4] - 32;
+    for (int i = 0; i < 5; i = i + 1)
+        putchar(text[i]);
+    putchar(10);
+
+    short *sp = &shorts[1];
+    print(sp[-1] + sp[2]);
+    print(&shorts[3] - sp);
+    long *lp = longs;
+    print(*lp + lp[1]);
+    print(&longs[2] - lp);
+    unsigned char *bp = bytes;
+    print(bp[0] + bp[1] + bp[2]);
+    print(length(text));
+    putchar(10);
+    return text[1];
+}
@@ -0,0 +1,48 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int main() {
+    char c = 200;
+    unsigned char uc = 200;
+    signed char sc = -3;
+    short s = 40000;
+    unsigned short us = 65535;
+
+    print(c);
+    print(uc);
+    print(sc);
+    print(s);
+    print(us);
+    print(c + uc);
+    print(uc * uc);
+    print(us + 1);
+    print(-uc);
+    print(~sc);
+    print(sc / 2);
+    print(us % 256);
+
+    uc = uc + 100;
+    print(uc);
+    c = 127;
+    c = c + 1;
+    print(c);
+    s = -1;
+    us = s;
+    print(us);
+    putchar(10);
+    return uc;
+}
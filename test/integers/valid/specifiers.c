@@ -0,0 +1,21 @@
+typedef unsigned long size;
+typedef signed char byte;
+
+long int a = 1;
+unsigned b = 2;
+signed c = -3;
+long long unsigned int d = 4;
+short int e = -5;
+int long f = 6;
+unsigned short int g = 7;
+signed long long int h = -8;
+char unsigned i = 9;
+size j = 10;
+byte k = -11;
+int unsigned long l = 12;
+
+int main() {
+    static long total;
+    total = a + b + c + d + e + f + g + h + i + j + k + l;
+    return total;
+}
@@ -0,0 +1,51 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+char c = 300;
+unsigned char uc = -1;
+short s = 70000;
+unsigned short us = -1;
+unsigned int ui = -1;
+long l = 4294967296 * 3;
+unsigned long ul = -1;
+long from_unsigned = 4294967295u + 1;
+long folded = 2147483647 + 1L;
+int wrapped = 65536 * 65536 + 7;
+
+struct mixed {
+    char c;
+    long l;
+    short s;
+} m = {-2, 123456789012, -3};
+
+int main() {
+    static short local = -40000;
+    print(c);
+    print(uc);
+    print(s);
+    print(us);
+    print(ui);
+    print(l);
+    print(ul == -1);
+    print(from_unsigned);
+    print(folded);
+    print(wrapped);
+    print(m.c + m.l + m.s);
+    print(local);
+    putchar(10);
+    return 0;
+}
@@ -0,0 +1,68 @@
+int classify_long(long x) {
+    switch (x) {
+    case 10000000000:
+        return 1;
+    case -10000000000:
+        return 2;
+    case 5:
+        return 3;
+    case 4294967296 ... 4294967300:
+        return 4;
+    }
+    return 0;
+}
+
+int classify_unsigned(unsigned int x) {
+    switch (x) {
+    case 4000000000u:
+        return 1;
+    case 1:
+        return 2;
+    case 2:
+        return 3;
+    case 3:
+        return 4;
+    case 3000000000u ... 3000000010u:
+        return 5;
+    case 7:
+        return 6;
+    }
+    return 0;
+}
+
+int classify_char(char c) {
+    switch (c) {
+    case 97 ... 122:
+        return 1;
+    case -1:
+        return 2;
+    case 65:
+    case 66:
+    case 67:
+    case 68:
+        return 3;
+    }
+    return 0;
+}
+
+int dense_long(long x) {
+    switch (x) {
+    case 1: return 10;
+    case 2: return 20;
+    case 3: return 30;
+    case 4: return 40;
+    case 6: return 60;
+    }
+    return 0;
+}
+
+int main() {
+    int total = 0;
+    total = total + classify_long(10000000000) + classify_long(-10000000000) * 10;
+    total = total + classify_long(5) + classify_long(4294967298) + classify_long(4294967301);
+    total = total + classify_unsigned(4000000000u) + classify_unsigned(2) + classify_unsigned(3000000005u);
+    total = total + classify_unsigned(-1) + classify_unsigned(7) * 3;
+    total = total + classify_char(100) + classify_char(255) + classify_char(67);
+    total = total + dense_long(4) + dense_long(4294967300) + dense_long(-1);
+    return total;
+}
@@ -0,0 +1,49 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+int widened_compare() {
+    unsigned short a = 65535;
+    short b = -1;
+    return a == b;
+}
+
+int main() {
+    int minus_one = -1;
+    unsigned int one = 1;
+    long wide = -1;
+    unsigned long big = 1;
+    unsigned int max = 4294967295u;
+
+    print(minus_one < one);
+    print(minus_one < wide);
+    print(wide < one);
+    print(wide < big);
+    print(minus_one + one);
+    print(max + one);
+    print(max + wide);
+    print(max / 2);
+    print(-7 / 2);
+    print(-7 % 2);
+    print(max / minus_one);
+    print(4000000000u % 7);
+    print(minus_one > 0u);
+    print(3u - 5 > 0);
+    print(3 - 5 > 0);
+    print(widened_compare());
+    putchar(10);
+    return 0;
+}
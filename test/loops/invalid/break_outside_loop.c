@@ -0,0 +1,4 @@
+int main() {
+    break;
+    return 0;
+}
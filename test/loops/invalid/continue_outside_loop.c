@@ -0,0 +1,6 @@
+int main() {
+    int a = 1;
+    if (a)
+        continue;
+    return a;
+}
@@ -0,0 +1,5 @@
+int main() {
+    int a = 0;
+    do a = a + 1; while (a < 3)
+    return a;
+}
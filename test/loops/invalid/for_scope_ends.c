@@ -0,0 +1,5 @@
+int main() {
+    for (int i = 0; i < 3; i = i + 1) {
+    }
+    return i;
+}
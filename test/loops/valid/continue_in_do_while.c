@@ -0,0 +1,11 @@
+int main() {
+    int i = 0;
+    int hits = 0;
+    do {
+        i = i + 1;
+        if (i == 3)
+            continue;
+        hits = hits + 1;
+    } while (i < 6);
+    return hits * 10 + i;
+}
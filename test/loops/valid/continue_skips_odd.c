@@ -0,0 +1,9 @@
+int main() {
+    int sum = 0;
+    for (int i = 0; i < 20; i = i + 1) {
+        if (i % 2)
+            continue;
+        sum = sum + i;
+    }
+    return sum;
+}
@@ -0,0 +1,7 @@
+int main() {
+    int count = 0;
+    do {
+        count = count + 7;
+    } while (count < 0);
+    return count;
+}
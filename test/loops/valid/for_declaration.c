@@ -0,0 +1,7 @@
+int main() {
+    int i = 100;
+    int total = 0;
+    for (int i = 0; i < 5; i = i + 1)
+        total = total + i;
+    return total + i;
+}
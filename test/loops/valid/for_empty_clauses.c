@@ -0,0 +1,9 @@
+int main() {
+    int n = 0;
+    for (;;) {
+        n = n + 3;
+        if (n > 20)
+            break;
+    }
+    return n;
+}
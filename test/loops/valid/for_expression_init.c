@@ -0,0 +1,8 @@
+int main() {
+    int i;
+    int product = 1;
+    for (i = 1; i <= 5; i = i + 1) {
+        product = product * i;
+    }
+    return product + i;
+}
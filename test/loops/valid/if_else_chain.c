@@ -0,0 +1,15 @@
+int main() {
+    int x = 15;
+    int r = 0;
+    if (x < 10)
+        r = 1;
+    else if (x < 20)
+        r = 2;
+    else
+        r = 3;
+    if (r == 2) {
+        int x = 4;
+        r = r + x;
+    }
+    return r;
+}
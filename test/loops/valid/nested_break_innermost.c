@@ -0,0 +1,15 @@
+int main() {
+    int outer = 0;
+    int inner_total = 0;
+    while (outer < 4) {
+        int j = 0;
+        while (1) {
+            if (j >= outer)
+                break;
+            inner_total = inner_total + 1;
+            j = j + 1;
+        }
+        outer = outer + 1;
+    }
+    return inner_total * 10 + outer;
+}
@@ -0,0 +1,6 @@
+int main() {
+    int x = 1;
+    int *p = &x;
+    int *q = p + p;
+    return 0;
+}
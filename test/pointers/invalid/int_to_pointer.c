@@ -0,0 +1,5 @@
+int main() {
+    int x = 1;
+    int *p = x;
+    return 0;
+}
@@ -0,0 +1,6 @@
+int main(void) {
+    int x = 1;
+    void *v = &x;
+    int *p = &x;
+    return p < v;
+}
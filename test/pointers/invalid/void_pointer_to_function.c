@@ -0,0 +1,9 @@
+int answer(void) {
+    return 42;
+}
+
+int main(void) {
+    void *v = 0;
+    int (*f)(void) = v;
+    return f();
+}
@@ -0,0 +1,8 @@
+int get(int *p) {
+    return *p;
+}
+
+int main() {
+    int x = 1;
+    return get(x);
+}
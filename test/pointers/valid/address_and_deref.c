@@ -0,0 +1,6 @@
+int main() {
+    int x = 3;
+    int *p = &x;
+    *p = *p + 4;
+    return x;
+}
@@ -0,0 +1,15 @@
+int value = 7;
+int *global_ptr;
+static int **indirect;
+
+int bump(void) {
+    *global_ptr = *global_ptr + 1;
+    return **indirect;
+}
+
+int main() {
+    global_ptr = &value;
+    indirect = &global_ptr;
+    bump();
+    return bump();
+}
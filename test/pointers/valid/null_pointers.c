@@ -0,0 +1,21 @@
+int *find(int *p, int want) {
+    if (*p == want)
+        return p;
+    return 0;
+}
+
+int main() {
+    int x = 4;
+    int *hit = find(&x, 4);
+    int *miss = find(&x, 5);
+    int result = 0;
+    if (hit)
+        result = result + 1;
+    if (!miss)
+        result = result + 2;
+    if (miss == 0)
+        result = result + 4;
+    while (miss)
+        miss = 0;
+    return result + (hit && !miss) * 8;
+}
@@ -0,0 +1,9 @@
+int main() {
+    int x = 0;
+    int *p = &x;
+    int *q = p + 3;
+    int *r = q - 1;
+    int **pp = &p;
+    int **pq = pp + 2;
+    return (q - p) * 10 + (r - p) + (pq - pp) * 100;
+}
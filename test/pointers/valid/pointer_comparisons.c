@@ -0,0 +1,17 @@
+int main() {
+    int x = 0;
+    int *p = &x;
+    int *q = p + 1;
+    int result = 0;
+    if (p < q)
+        result = result + 1;
+    if (q >= p)
+        result = result + 2;
+    if (p != q)
+        result = result + 4;
+    if (p == &x)
+        result = result + 8;
+    if (q > p && !(q <= p))
+        result = result + 16;
+    return result;
+}
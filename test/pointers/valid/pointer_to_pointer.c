@@ -0,0 +1,10 @@
+int main() {
+    int x = 5;
+    int *p = &x;
+    int **pp = &p;
+    **pp = 9;
+    int y = 1;
+    *pp = &y;
+    *p = *p + 20;
+    return x + y;
+}
@@ -0,0 +1,35 @@
+void *malloc(unsigned long size);
+void free(void *pointer);
+void *memset(void *destination, int value, unsigned long count);
+
+int global = 9;
+void *global_pointer = &global;
+
+void *identity(void *pointer) {
+    return pointer;
+}
+
+int *as_int(void *pointer) {
+    return pointer;
+}
+
+int main(void) {
+    int *p = malloc(8);
+    int x = 3;
+    void *v = &x;
+    int *q = v;
+    int total = 0;
+    memset(p, 0, 8);
+    p[1] = 4;
+    if (p == v)
+        total = total + 100;
+    if (v != p)
+        total = total + 1;
+    if (q == v)
+        total = total + 2;
+    v = p;
+    q = identity(&x);
+    total = total + *q + *as_int(v) + p[1] + *(int *) global_pointer;
+    free(p);
+    return total;
+}
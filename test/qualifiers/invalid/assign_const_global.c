@@ -0,0 +1,6 @@
+const int limit = 3;
+
+int main(void) {
+    limit = 4;
+    return limit;
+}
@@ -0,0 +1,10 @@
+struct s {
+    const int x;
+};
+
+int main(void) {
+    struct s v = {1};
+    struct s *p = &v;
+    p->x = 2;
+    return v.x;
+}
@@ -0,0 +1,8 @@
+int f(const int n) {
+    n = 1;
+    return n;
+}
+
+int main(void) {
+    return f(2);
+}
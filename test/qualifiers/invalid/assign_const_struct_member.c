@@ -0,0 +1,9 @@
+struct s {
+    int x;
+};
+
+int main(void) {
+    const struct s v = {1};
+    v.x = 2;
+    return v.x;
+}
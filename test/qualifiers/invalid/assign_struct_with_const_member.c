@@ -0,0 +1,10 @@
+struct s {
+    const int x;
+};
+
+int main(void) {
+    struct s a = {1};
+    struct s b = {2};
+    a = b;
+    return a.x;
+}
@@ -0,0 +1,6 @@
+int main(void) {
+    const char *s = "abc";
+    const char **p = &s;
+    char **q = p;
+    return **q;
+}
@@ -0,0 +1,4 @@
+int main(void) {
+    restrict int x = 1;
+    return x;
+}
@@ -0,0 +1,16 @@
+int printf(char *format, ...);
+
+int first(char *s) {
+    return s[0];
+}
+
+int main(void) {
+    const char *name = "discarded";
+    char *plain = name;
+    volatile int v = 4;
+    int *p = &v;
+    const void *constant = name;
+    void *erased = name;
+    char *restored = constant;
+    return first(name) - plain[0] + *p + (erased == restored);
+}
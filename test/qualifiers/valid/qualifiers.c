@@ -0,0 +1,64 @@
+int printf(char *format, ...);
+
+const int limit = 10;
+const double scale = 2.5;
+static const char *const names[] = {"alpha", "beta"};
+volatile int counter;
+
+struct point {
+    const int x;
+    int y;
+};
+
+typedef const int constant;
+
+int sum(const int *values, int count) {
+    int total = 0;
+    for (int i = 0; i < count; i = i + 1)
+        total = total + values[i];
+    return total;
+}
+
+int length(const char *s) {
+    const char *p = s;
+    while (*p)
+        p = p + 1;
+    return p - s;
+}
+
+void fill(int *restrict out, const int *restrict in, int count) {
+    for (int i = 0; i < count; i = i + 1)
+        out[i] = in[i] * 2;
+}
+
+const int twice(const int n) {
+    return n * 2;
+}
+
+int main(void) {
+    const int local = 5;
+    constant alias = 7;
+    int values[3] = {1, 2, 3};
+    int doubled[3];
+    int *const fixed = values;
+    const float half = 0.5f;
+    struct point p = {3, 4};
+    const struct point origin = {0, 0};
+
+    fill(doubled, values, 3);
+    *fixed = 10;
+    p.y = p.x + origin.y;
+    for (int i = 0; i < limit; i = i + 1)
+        counter = counter + i;
+    printf("%d %d %d %d\n", local, alias, sum(values, 3), sum(doubled, 3));
+    printf("%d %d %s %s\n", length(names[0]), length("qualified"), names[1], names[0]);
+    printf("%.2f %.2f %d %d %d\n", scale * half, half, p.y, counter, twice(local));
+
+    const int *cp = &local;
+    int const *same = cp;
+    const volatile int *cv = cp;
+    printf("%d %d %d\n", *cp, *same == *cv, cp == same);
+    cp = values;
+    printf("%d %d\n", cp[0], (int)(cp + 2 - values));
+    return sizeof(const int) + _Alignof(const volatile double);
+}
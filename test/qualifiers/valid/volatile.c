@@ -0,0 +1,30 @@
+int printf(char *format, ...);
+
+volatile int ticks;
+
+struct device {
+    volatile int status;
+    int reads;
+};
+
+int poll(volatile struct device *d) {
+    d->reads = d->reads + 1;
+    return d->status;
+}
+
+int main(void) {
+    volatile int local = 1;
+    struct device dev = {3, 0};
+    volatile long samples[4];
+    for (int i = 0; i < 4; i = i + 1)
+        samples[i] = i * local;
+    ticks = ticks + 1;
+    ticks = ticks + 1;
+    local;
+    poll(&dev);
+    poll(&dev);
+    volatile int *p = &ticks;
+    *p = *p + samples[3];
+    printf("%d %d %d %ld\n", ticks, dev.reads, poll(&dev), samples[2]);
+    return local;
+}
@@ -0,0 +1,8 @@
+int broken(void) {
+    int x = 1 2;
+    return x;
+}
+
+int main(void) {
+    return undeclared;
+}
@@ -0,0 +1,7 @@
+int main(void) {
+    int unused;
+    int a[3 = {1};
+    int b = (3;
+    int c = undeclared(1) + 2;
+    return c;
+}
@@ -0,0 +1,15 @@
+int add(int a, int b) {
+    int sum = (a + ;
+    return sum +;
+}
+
+struct point { int x; int ; int y; };
+
+int twice( {
+    return 2;
+}
+
+int main(void) {
+    int y = 3
+    y = y * ;
+    return add(y, 1) }
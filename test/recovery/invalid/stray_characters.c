@@ -0,0 +1,7 @@
+int main(void) {
+    int x = 1;
+    x = x @;
+    return x #;
+}
+
+int y = 2;
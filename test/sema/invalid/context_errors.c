@@ -0,0 +1,24 @@
+int twice(int x) {
+    return x * 2;
+}
+
+void nothing(void) {
+    return 1;
+}
+
+int main(void) {
+    const int limit = 3;
+    int total = 0;
+    limit = 4;
+    total + 1 = 2;
+    int *p = &(total + 1);
+    twice(1, 2);
+    break;
+    switch (total) {
+    case 1:
+    case 257 - 256:
+        continue;
+    }
+    goto missing;
+    return;
+}
@@ -0,0 +1,31 @@
+int printf(char *format, ...);
+
+long widen(long value) {
+    return value * 3;
+}
+
+char narrow(int value) {
+    return value;
+}
+
+int main(void) {
+    double d = 2.75;
+    long l;
+    unsigned char c;
+    int i = 0;
+    l = d;
+    c = 300;
+    float f = 1.5f;
+    printf("%ld %d %ld %d %f\n", l, c, widen(c), narrow(321), f);
+    switch (c) {
+    case 44L:
+        i = 1;
+        goto done;
+    case 'a':
+        i = 2;
+        break;
+    }
+    i = 3;
+done:
+    return i + (int) l;
+}
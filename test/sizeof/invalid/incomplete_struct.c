@@ -0,0 +1,5 @@
+struct later;
+
+int main() {
+    return sizeof(struct later);
+}
@@ -0,0 +1,70 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+struct point {
+    int x;
+    int y;
+};
+
+int calls = 0;
+
+int count() {
+    calls = calls + 1;
+    return calls;
+}
+
+int main() {
+    int numbers[10];
+    char text[7];
+    struct point points[4];
+    struct point *p = points;
+    long matrix[3][5];
+    double d = 1.5;
+
+    print(sizeof numbers);
+    print(sizeof(numbers));
+    print(sizeof numbers / sizeof numbers[0]);
+    print(sizeof text);
+    print(sizeof points);
+    print(sizeof p);
+    print(sizeof *p);
+    print(sizeof p->x);
+    print(sizeof matrix);
+    print(sizeof matrix[1]);
+    print(sizeof matrix[1][2]);
+    print(sizeof(numbers + 1));
+    print(sizeof &numbers);
+    print(sizeof d * 2);
+    print(sizeof 1.0f);
+    print(sizeof 10L);
+    print(sizeof(count()));
+    print(calls);
+    print(sizeof -1 > 0);
+
+    int array[sizeof(struct point) * 2];
+    print(sizeof array);
+    switch (sizeof(long)) {
+    case sizeof(int):
+        print(4);
+        break;
+    case sizeof(double):
+        print(8);
+        break;
+    }
+    putchar(10);
+    return sizeof(int);
+}
@@ -0,0 +1,66 @@
+int putchar(int c);
+
+int print_digits(long n) {
+    if (n >= 10)
+        print_digits(n / 10);
+    return putchar(48 + n % 10);
+}
+
+int print(long n) {
+    if (n < 0) {
+        putchar(45);
+        n = -n;
+    }
+    print_digits(n);
+    return putchar(32);
+}
+
+struct small {
+    char c;
+    short s;
+};
+
+struct mixed {
+    char tag;
+    double value;
+    int counts[3];
+    struct small inner;
+};
+
+union either {
+    char bytes[5];
+    int number;
+};
+
+typedef struct mixed mixed_t;
+
+int main() {
+    print(sizeof(char));
+    print(sizeof(short));
+    print(sizeof(int));
+    print(sizeof(long));
+    print(sizeof(long long));
+    print(sizeof(float));
+    print(sizeof(double));
+    print(sizeof(void *));
+    print(sizeof(int *[3]));
+    print(sizeof(int (*)[3]));
+    print(sizeof(char[2][3]));
+    print(sizeof(double (*)[4][5]));
+    print(sizeof(struct small));
+    print(sizeof(struct mixed));
+    print(sizeof(mixed_t *));
+    print(sizeof(union either));
+    putchar(10);
+
+    print(_Alignof(char));
+    print(_Alignof(short));
+    print(_Alignof(long));
+    print(_Alignof(double));
+    print(_Alignof(struct small));
+    print(_Alignof(struct mixed));
+    print(_Alignof(union either));
+    print(_Alignof(int[7]));
+    putchar(10);
+    return 0;
+}
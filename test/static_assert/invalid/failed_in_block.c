@@ -0,0 +1,4 @@
+int main(void) {
+    _Static_assert(2 - 2, "never true");
+    return 0;
+}
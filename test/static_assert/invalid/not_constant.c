@@ -0,0 +1,6 @@
+int size = 4;
+_Static_assert(size == 4, "size is a variable");
+
+int main(void) {
+    return 0;
+}
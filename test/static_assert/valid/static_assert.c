@@ -0,0 +1,22 @@
+_Static_assert(sizeof(int) == 4, "int is 4 bytes");
+_Static_assert(sizeof(long) == 2 * sizeof(int), "long is twice " "as wide as int");
+
+enum { SIZE = 3 };
+
+struct point {
+    int x;
+    int y;
+    _Static_assert(SIZE > 2, "a point has room for three");
+};
+
+int main(void) {
+    _Static_assert(sizeof(struct point) == 8, "members are not padded");
+    int total = SIZE;
+    _Static_assert(1, "declarations may follow");
+    int more = 4;
+    for (int i = 0; i < 2; i = i + 1) {
+        _Static_assert(SIZE, "");
+        total = total + more;
+    }
+    return total;
+}
@@ -0,0 +1,13 @@
+struct a {
+    int x;
+};
+struct b {
+    int x;
+};
+
+int main() {
+    struct a va;
+    struct b vb;
+    va = vb;
+    return 0;
+}
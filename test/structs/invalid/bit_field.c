@@ -0,0 +1,10 @@
+struct flags {
+    unsigned ready : 1;
+    unsigned mode : 3;
+};
+
+int main(void) {
+    struct flags f;
+    f.mode = 2;
+    return f.mode;
+}
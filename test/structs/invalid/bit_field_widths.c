@@ -0,0 +1,15 @@
+int size;
+
+struct widths {
+    int negative : -1;
+    int zero : 0;
+    int : 0;
+    char wide : 9;
+    float real : 3;
+    int varying : size;
+    unsigned whole : 32;
+};
+
+int main(void) {
+    return 0;
+}
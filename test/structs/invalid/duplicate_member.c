@@ -0,0 +1,8 @@
+struct s {
+    int a;
+    int a;
+};
+
+int main() {
+    return 0;
+}
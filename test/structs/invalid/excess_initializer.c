@@ -0,0 +1,8 @@
+struct s {
+    int a;
+};
+
+int main() {
+    struct s v = {1, 2};
+    return 0;
+}
@@ -0,0 +1,9 @@
+struct s {
+    int n;
+    int d[];
+    int after;
+};
+
+int main(void) {
+    return 0;
+}
@@ -0,0 +1,7 @@
+struct s {
+    int d[];
+};
+
+int main(void) {
+    return 0;
+}
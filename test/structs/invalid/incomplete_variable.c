@@ -0,0 +1,6 @@
+struct s;
+
+int main() {
+    struct s v;
+    return 0;
+}
@@ -0,0 +1,12 @@
+struct s {
+    int x;
+    struct s {
+        int y;
+    } z;
+};
+
+int main(void) {
+    struct s value;
+    value.x = 1;
+    return value.x;
+}
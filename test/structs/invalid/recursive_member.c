@@ -0,0 +1,8 @@
+struct s {
+    int a;
+    struct s inner;
+};
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,11 @@
+struct s {
+    int a;
+};
+
+int get(struct s v) {
+    return v.a;
+}
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,9 @@
+int main() {
+    {
+        struct s {
+            int a;
+        };
+    }
+    struct s *p;
+    return p->a;
+}
@@ -0,0 +1,13 @@
+struct huge {
+    char a[2147483647];
+    char b[2];
+};
+
+union fits {
+    char a[2147483647];
+    char b[2];
+};
+
+int main(void) {
+    return sizeof(union fits) > 0;
+}
@@ -0,0 +1,9 @@
+struct s {
+    int a;
+};
+
+union s *p;
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,20 @@
+struct ints {
+    int n;
+    int d[];
+};
+
+struct padded {
+    char c;
+    double values[];
+};
+
+struct nested {
+    short s;
+    char tag;
+    struct ints *inner;
+    long counts[];
+};
+
+struct ints v1;
+struct padded v2;
+struct nested v3;
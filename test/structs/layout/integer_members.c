@@ -0,0 +1,33 @@
+struct mixed {
+    char c;
+    long l;
+    short s;
+    unsigned char uc;
+    int i;
+    unsigned short us;
+};
+
+struct bytes {
+    char a;
+    signed char b;
+    unsigned char c;
+};
+
+struct shorts_then_long {
+    short s[3];
+    long long ll;
+    char tail;
+};
+
+union widths {
+    char c;
+    short s;
+    unsigned int u;
+    long l;
+};
+
+struct mixed v1;
+struct bytes v2;
+struct shorts_then_long v3;
+union widths v4;
+struct bytes v5[3];
@@ -0,0 +1,25 @@
+struct inner {
+    int x;
+    int *p;
+};
+
+struct outer {
+    int a;
+    struct inner in;
+    int b;
+    struct inner pair[2];
+    int c[3];
+};
+
+struct only_ints {
+    int a[5];
+    int b;
+};
+
+struct holds_ints {
+    int a;
+    struct only_ints ints;
+};
+
+struct outer v1;
+struct holds_ints v2;
@@ -0,0 +1,22 @@
+struct int_then_pointer {
+    int a;
+    int *p;
+};
+
+struct pointer_then_int {
+    int *p;
+    int a;
+};
+
+struct mixed {
+    int a;
+    int *p;
+    int b;
+    int c;
+    int **pp;
+    int d;
+};
+
+struct int_then_pointer v1;
+struct pointer_then_int v2;
+struct mixed v3;
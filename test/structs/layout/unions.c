@@ -0,0 +1,30 @@
+union small {
+    int a;
+    int b[3];
+};
+
+union wide {
+    int a[3];
+    int *p;
+};
+
+struct with_union {
+    int tag;
+    union wide w;
+    int after;
+};
+
+union of_structs {
+    struct {
+        int a;
+        int b;
+        int c;
+    } three;
+    struct {
+        int *p;
+    } one;
+};
+
+union small v1;
+struct with_union v2;
+union of_structs v3;
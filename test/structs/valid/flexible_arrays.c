@@ -0,0 +1,27 @@
+void *malloc(unsigned long size);
+void free(void *pointer);
+
+struct buffer {
+    int length;
+    int data[];
+};
+
+struct buffer *make(int length) {
+    struct buffer *b = malloc(sizeof(struct buffer) + length * sizeof(int));
+    b->length = length;
+    for (int i = 0; i < length; i = i + 1) {
+        b->data[i] = i * 3;
+    }
+    return b;
+}
+
+int main(void) {
+    struct buffer *b = make(6);
+    int *p = b->data;
+    int total = sizeof(struct buffer);
+    for (int i = 0; i < b->length; i = i + 1) {
+        total = total + p[i];
+    }
+    free(b);
+    return total;
+}
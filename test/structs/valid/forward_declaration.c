@@ -0,0 +1,27 @@
+struct tree;
+
+struct tree *root;
+
+struct tree {
+    int value;
+    struct tree *left;
+    struct tree *right;
+};
+
+int depth(struct tree *t) {
+    if (!t)
+        return 0;
+    int l = depth(t->left);
+    int r = depth(t->right);
+    if (l > r)
+        return 1 + l;
+    return 1 + r;
+}
+
+int main() {
+    struct tree leaf = {3, 0, 0};
+    struct tree mid = {2, &leaf, 0};
+    struct tree top = {1, 0, &mid};
+    root = &top;
+    return depth(root) * 10 + root->right->left->value;
+}
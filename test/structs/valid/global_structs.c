@@ -0,0 +1,22 @@
+struct config {
+    int width;
+    int *target;
+    int flags[3];
+};
+
+int value = 11;
+struct config defaults = {80, 0, {1, 0, 3}};
+struct config settings;
+static struct config table[2] = {{1, 0, {2}}, {4}};
+union number {
+    int *p;
+    int i;
+} initial = {0};
+
+int main() {
+    settings = defaults;
+    settings.target = &value;
+    table[1].flags[2] = 5;
+    return settings.width + *settings.target + settings.flags[2] + table[0].flags[0]
+        + table[1].width + table[1].flags[2] + (initial.p == 0);
+}
@@ -0,0 +1,22 @@
+struct node {
+    int value;
+    struct node *next;
+};
+
+int sum(struct node *head) {
+    int total = 0;
+    for (struct node *n = head; n; n = n->next)
+        total = total + n->value;
+    return total;
+}
+
+int main() {
+    struct node nodes[4];
+    for (int i = 0; i < 4; i = i + 1) {
+        nodes[i].value = i + 1;
+        nodes[i].next = 0;
+        if (i > 0)
+            nodes[i - 1].next = &nodes[i];
+    }
+    return sum(&nodes[0]) + sum(nodes + 2);
+}
@@ -0,0 +1,13 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main() {
+    struct point p;
+    p.x = 3;
+    p.y = 4;
+    struct point *pp = &p;
+    pp->y = pp->y * 10;
+    return p.x + p.y;
+}
@@ -0,0 +1,19 @@
+struct inner {
+    int a;
+    int *p;
+};
+
+struct outer {
+    int tag;
+    struct inner in;
+    int values[3];
+};
+
+int main() {
+    int x = 5;
+    struct outer o = {1, {2, &x}, {3, 4}};
+    o.in.a = o.in.a + *o.in.p;
+    o.values[2] = 6;
+    struct outer *po = &o;
+    return po->tag + po->in.a * 10 + po->values[0] + o.values[1] + o.values[2];
+}
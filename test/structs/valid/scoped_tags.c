@@ -0,0 +1,24 @@
+struct s {
+    int a;
+};
+
+int main() {
+    struct s outer = {1};
+    int result = outer.a;
+    {
+        struct s {
+            int b;
+            int c;
+        };
+        struct s inner = {2, 3};
+        result = result + inner.b + inner.c;
+    }
+    struct s again = {4};
+    struct anonymous_holder {
+        struct {
+            int x;
+        } anon;
+    } holder;
+    holder.anon.x = 10;
+    return result + again.a + holder.anon.x;
+}
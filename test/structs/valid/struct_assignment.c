@@ -0,0 +1,17 @@
+struct pair {
+    int first;
+    int second;
+    int *extra;
+};
+
+int main() {
+    int x = 9;
+    struct pair a = {1, 2, &x};
+    struct pair b;
+    struct pair c;
+    c = b = a;
+    a.first = 100;
+    struct pair d = c;
+    d.second = 20;
+    return b.first + c.second + *d.extra + d.second + a.first;
+}
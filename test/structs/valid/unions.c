@@ -0,0 +1,22 @@
+union value {
+    int i;
+    int *p;
+    int pair[2];
+};
+
+struct tagged {
+    int kind;
+    union value v;
+};
+
+int main() {
+    int x = 40;
+    struct tagged t;
+    t.kind = 1;
+    t.v.p = &x;
+    int result = *t.v.p;
+    t.v.i = 2;
+    union value u = {7};
+    u.pair[1] = 3;
+    return result + t.v.pair[0] + u.i + u.pair[1];
+}
@@ -0,0 +1,8 @@
+int main() {
+    int x = 2;
+    while (x) {
+    case 1:
+        x = x - 1;
+    }
+    return x;
+}
@@ -0,0 +1,4 @@
+int main() {
+default:
+    return 0;
+}
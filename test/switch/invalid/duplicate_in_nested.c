@@ -0,0 +1,13 @@
+int main() {
+    switch (1) {
+    case 1:
+        switch (2) {
+        case 1:
+            break;
+        case 3:
+        case 3:
+            break;
+        }
+    }
+    return 0;
+}
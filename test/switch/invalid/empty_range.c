@@ -0,0 +1,7 @@
+int main() {
+    switch (3) {
+    case 5 ... 1:
+        return 1;
+    }
+    return 0;
+}
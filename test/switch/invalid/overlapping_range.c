@@ -0,0 +1,10 @@
+int main() {
+    int x = 1;
+    switch (x) {
+    case 1 ... 5:
+        return 1;
+    case 5 ... 9:
+        return 2;
+    }
+    return 0;
+}
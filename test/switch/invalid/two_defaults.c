@@ -0,0 +1,9 @@
+int main() {
+    switch (3) {
+    default:
+        return 1;
+    case 2:
+    default:
+        return 2;
+    }
+}
@@ -0,0 +1,25 @@
+enum color { RED = 2, GREEN = RED * 2, BLUE = GREEN + 3 };
+
+int weight(int c) {
+    switch (c) {
+    case RED:
+        return 1;
+    case GREEN:
+        return 2;
+    case BLUE:
+        return 3;
+    case BLUE + 1:
+        return 4;
+    case -RED:
+        return 5;
+    case (4 * 4) + 1:
+        return 6;
+    case 10 * 10:
+        return 7;
+    }
+    return 0;
+}
+
+int main() {
+    return weight(RED) + 10 * weight(GREEN) + weight(BLUE) + weight(8) + weight(0 - 2) + weight(17) + weight(100) + weight(3);
+}
@@ -0,0 +1,20 @@
+int classify(int x) {
+    int r = 0;
+    switch (x) {
+    case 1:
+        r = 1;
+        break;
+    default:
+        r = 7;
+    case 2:
+        r = r + 2;
+        break;
+    case 5:
+        r = 5;
+    }
+    return r;
+}
+
+int main() {
+    return classify(1) * 1 + classify(2) * 10 + classify(5) * 3 + classify(9) * 4;
+}
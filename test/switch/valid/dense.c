@@ -0,0 +1,21 @@
+int digit_value(int c) {
+    switch (c) {
+    case 48: return 0;
+    case 49: return 1;
+    case 50: return 2;
+    case 51: return 3;
+    case 52: return 4;
+    case 53: return 5;
+    case 55: return 7;
+    case 56: return 8;
+    case 57: return 9;
+    default: return 0 - 1;
+    }
+}
+
+int main() {
+    int total = 0;
+    for (int c = 40; c < 60; c = c + 1)
+        total = total * 3 + digit_value(c) + 1;
+    return total % 251;
+}
@@ -0,0 +1,19 @@
+int count(int n) {
+    int total = 0;
+    switch (n) {
+    case 3:
+        total = total + 100;
+    case 2:
+        total = total + 10;
+    case 1:
+        total = total + 1;
+        break;
+    case 0:
+        total = 50;
+    }
+    return total;
+}
+
+int main() {
+    return count(3) + count(2) - count(1) + count(0) - count(7) - 100;
+}
@@ -0,0 +1,29 @@
+int main() {
+    int total = 0;
+    for (int i = 0; i < 4; i = i + 1) {
+        for (int j = 0; j < 3; j = j + 1) {
+            switch (i) {
+            case 0:
+                continue;
+            case 1:
+                switch (j) {
+                case 0:
+                    total = total + 1;
+                    break;
+                case 1:
+                    total = total + 2;
+                default:
+                    total = total + 4;
+                }
+                total = total + 8;
+                break;
+            case 3:
+                if (j == 1)
+                    break;
+                total = total + 16;
+            }
+            total = total + 32;
+        }
+    }
+    return total % 256;
+}
@@ -0,0 +1,19 @@
+int main() {
+    int x = 42;
+    switch (x) {
+    case 1:
+        x = 0;
+        break;
+    case 2:
+        x = 1;
+    }
+    switch (x)
+        x = x + 1;
+    switch (x) {
+    }
+    switch (x + 1) {
+    case 43:
+        x = x * 2;
+    }
+    return x;
+}
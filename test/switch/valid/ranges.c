@@ -0,0 +1,36 @@
+int grade(int score) {
+    switch (score) {
+    case 90 ... 100:
+        return 4;
+    case 80 ... 89:
+        return 3;
+    case 70 ... 79:
+        return 2;
+    case 60 ... 69:
+        return 1;
+    case -10 ... -1:
+        return 9;
+    default:
+        return 0;
+    }
+}
+
+int small(int x) {
+    switch (x) {
+    case 0 ... 2:
+        return 1;
+    case 3:
+    case 4 ... 5:
+        return 2;
+    }
+    return 3;
+}
+
+int main() {
+    int total = 0;
+    for (int s = -20; s <= 110; s = s + 3)
+        total = total + grade(s);
+    for (int x = -1; x < 8; x = x + 1)
+        total = total * 2 + small(x);
+    return total % 256;
+}
@@ -0,0 +1,22 @@
+int lookup(int key) {
+    switch (key) {
+    case 1: return 3;
+    case 10: return 5;
+    case 100: return 7;
+    case 1000: return 11;
+    case 10000: return 13;
+    case 100000: return 17;
+    case 1000000: return 19;
+    case -5: return 23;
+    case -5000: return 29;
+    }
+    return 1;
+}
+
+int main() {
+    int keys[12] = {1, 10, 100, 1000, 10000, 100000, 1000000, -5, -5000, 2, 0, -1};
+    int total = 0;
+    for (int i = 0; i < 12; i = i + 1)
+        total = total * 2 + lookup(keys[i]);
+    return total % 256;
+}
@@ -0,0 +1,32 @@
+enum state { START, WORD, SPACE, DONE };
+
+int main() {
+    int input[12] = {1, 1, 0, 0, 1, 0, 1, 1, 1, 0, 1, 2};
+    enum state state = START;
+    int words = 0;
+    int i = 0;
+    while (state != DONE) {
+        int c = input[i];
+        i = i + 1;
+        switch (state) {
+        case START:
+        case SPACE:
+            if (c == 1) {
+                words = words + 1;
+                state = WORD;
+            } else if (c == 2) {
+                state = DONE;
+            } else {
+                state = SPACE;
+            }
+            break;
+        case WORD:
+            if (c == 0)
+                state = SPACE;
+            else if (c == 2)
+                state = DONE;
+            break;
+        }
+    }
+    return words;
+}
@@ -0,0 +1,6 @@
+typedef int T;
+
+int main() {
+    T int x;
+    return 0;
+}
@@ -0,0 +1,7 @@
+int main() {
+    {
+        typedef int T;
+    }
+    T x = 1;
+    return x;
+}
@@ -0,0 +1,5 @@
+int main() {
+    for (typedef int T; 0;)
+        ;
+    return 0;
+}
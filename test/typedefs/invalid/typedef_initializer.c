@@ -0,0 +1,5 @@
+typedef int T = 3;
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,7 @@
+int f(typedef int x) {
+    return x;
+}
+
+int main() {
+    return 0;
+}
@@ -0,0 +1,15 @@
+typedef int vec3[3];
+typedef vec3 mat3[3];
+typedef int list[];
+
+int dot(int *a, int *b) {
+    return a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+}
+
+int main() {
+    vec3 v = {1, 2, 3};
+    mat3 m = {{1, 0, 0}, {0, 2, 0}, {0, 0, 3}};
+    list values = {4, 5, 6, 7};
+    list more = {1};
+    return dot(v, m[1]) + dot(m[2], v) + values[3] + more[0];
+}
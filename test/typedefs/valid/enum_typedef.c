@@ -0,0 +1,14 @@
+typedef enum { OFF, ON } state;
+
+state toggle(state s) {
+    if (s == ON)
+        return OFF;
+    return ON;
+}
+
+int main() {
+    state s = OFF;
+    s = toggle(s);
+    s = toggle(toggle(s));
+    return s * 10 + toggle(s);
+}
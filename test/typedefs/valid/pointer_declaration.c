@@ -0,0 +1,14 @@
+typedef int T;
+
+int main() {
+    T a = 3;
+    T * p = &a;
+    T result = *p;
+    {
+        int T = 4;
+        result = result + T * a;
+    }
+    T * q;
+    q = p;
+    return result + *q;
+}
@@ -0,0 +1,25 @@
+typedef int number;
+typedef number count;
+typedef int number;
+
+static count total = 5;
+
+count add(number a, count b) {
+    return a + b;
+}
+
+int main() {
+    typedef int *handle;
+    int x = 7;
+    handle h = &x;
+    for (count i = 0; i < 3; i = i + 1)
+        total = add(total, i);
+    {
+        typedef struct {
+            number n;
+        } number_box;
+        number_box box = {total};
+        *h = *h + box.n;
+    }
+    return x;
+}
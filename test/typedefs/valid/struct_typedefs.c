@@ -0,0 +1,30 @@
+typedef struct node node;
+
+struct node {
+    int value;
+    node *next;
+};
+
+typedef struct {
+    int x;
+    int y;
+} point;
+
+typedef point *point_ptr;
+
+int length(node *list) {
+    int n = 0;
+    for (node *it = list; it; it = it->next)
+        n = n + 1;
+    return n;
+}
+
+int main() {
+    node c = {3, 0};
+    node b = {2, &c};
+    node a = {1, &b};
+    point p = {4, 5};
+    point_ptr pp = &p;
+    pp->y = pp->y + length(&a);
+    return p.x * 10 + p.y;
+}
@@ -0,0 +1,4 @@
+int main(void) {
+    "abc" = 0;
+    return 0;
+}
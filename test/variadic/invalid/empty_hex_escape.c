@@ -0,0 +1,4 @@
+int main(void) {
+    char *s = "\x";
+    return 0;
+}
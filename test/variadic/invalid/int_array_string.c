@@ -0,0 +1,4 @@
+int main(void) {
+    int a[3] = "ab";
+    return a[0];
+}
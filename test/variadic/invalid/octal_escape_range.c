@@ -0,0 +1,4 @@
+int main(void) {
+    char *s = "\777";
+    return 0;
+}
@@ -0,0 +1,4 @@
+int main(void) {
+    char s[2] = "abc";
+    return s[0];
+}
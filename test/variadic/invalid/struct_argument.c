@@ -0,0 +1,10 @@
+int printf(char *format, ...);
+
+struct s {
+    int x;
+};
+
+int main(void) {
+    struct s v = {1};
+    return printf("%d", v);
+}
@@ -0,0 +1,5 @@
+int printf(char *format, ...);
+
+int main(void) {
+    return printf();
+}
@@ -0,0 +1,4 @@
+int main(void) {
+    char *s = "\q";
+    return 0;
+}
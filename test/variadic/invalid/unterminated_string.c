@@ -0,0 +1,4 @@
+int main(void) {
+    char *s = "open;
+    return 0;
+}
@@ -0,0 +1,9 @@
+int f(int a, ...) {
+    __builtin_va_list args;
+    __builtin_va_start(args, a);
+    return __builtin_va_arg(args, char);
+}
+
+int main(void) {
+    return f(1, 2);
+}
@@ -0,0 +1,9 @@
+double f(int a, ...) {
+    __builtin_va_list args;
+    __builtin_va_start(args, a);
+    return __builtin_va_arg(args, float);
+}
+
+int main(void) {
+    return f(1, 2.0);
+}
@@ -0,0 +1,13 @@
+struct s {
+    int x;
+};
+
+int f(int a, ...) {
+    __builtin_va_list args;
+    __builtin_va_start(args, a);
+    return __builtin_va_arg(args, struct s).x;
+}
+
+int main(void) {
+    return f(1);
+}
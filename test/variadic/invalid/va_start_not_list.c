@@ -0,0 +1,9 @@
+int f(int a, ...) {
+    int args;
+    __builtin_va_start(args, a);
+    return 0;
+}
+
+int main(void) {
+    return f(1);
+}
@@ -0,0 +1,8 @@
+int printf(char *format, ...);
+
+void nothing(void) {
+}
+
+int main(void) {
+    return printf("%d", nothing());
+}
@@ -0,0 +1,20 @@
+int printf(char *format, ...);
+
+int main(void) {
+    char c = 'x';
+    short s = -7;
+    float f = 1.5f;
+    long big = 1234567890123L;
+    char *name = "world";
+
+    printf("hello, %s!\n", name);
+    printf("%d %c %d %ld\n", c, c, s, big);
+    printf("%.2f %.3f\n", f, 2.0 / 3.0);
+    printf("%d %d %d %d %d %d %d %d\n", 1, 2, 3, 4, 5, 6, 7, 8);
+    printf("%.1f %.1f %.1f %.1f %.1f %.1f %.1f %.1f %.1f %.1f\n",
+           0.5, 1.5, 2.5, 3.5, 4.5, 5.5, 6.5, 7.5, 8.5, 9.5);
+    printf("%d %.1f %d %.1f %s\n", 10, 0.25, 20, 0.75, "mixed");
+    int (*fp)(char *, ...) = printf;
+    fp("%s %d\n", "through a pointer", 42);
+    return printf("") + 3;
+}
@@ -0,0 +1,81 @@
+int printf(char *format, ...);
+
+int sum(int count, ...) {
+    __builtin_va_list args;
+    __builtin_va_start(args, count);
+    int total = 0;
+    for (int i = 0; i < count; i = i + 1)
+        total = total + __builtin_va_arg(args, int);
+    __builtin_va_end(args);
+    return total;
+}
+
+double average(int count, ...) {
+    __builtin_va_list args;
+    __builtin_va_start(args, count);
+    double total = 0;
+    for (int i = 0; i < count; i = i + 1)
+        total = total + __builtin_va_arg(args, double);
+    __builtin_va_end(args);
+    return total / count;
+}
+
+typedef __builtin_va_list va_list;
+
+long mixed(char *kinds, ...) {
+    va_list args;
+    va_list again;
+    __builtin_va_start(args, kinds);
+    __builtin_va_copy(again, args);
+    long total = 0;
+    for (char *k = kinds; *k; k = k + 1) {
+        if (*k == 'i')
+            total = total + __builtin_va_arg(args, int);
+        else if (*k == 'l')
+            total = total + __builtin_va_arg(args, long);
+        else if (*k == 'd')
+            total = total + (long)(__builtin_va_arg(args, double) * 10);
+        else if (*k == 's')
+            total = total + *__builtin_va_arg(args, char *);
+    }
+    long first = __builtin_va_arg(again, int);
+    __builtin_va_end(again);
+    __builtin_va_end(args);
+    return total * 1000 + first;
+}
+
+int late(int a, int b, int c, int d, int e, int f, int g, double x, ...) {
+    va_list args;
+    __builtin_va_start(args, x);
+    int result = a + b + c + d + e + f + g + (int)x;
+    result = result + __builtin_va_arg(args, int) * 100;
+    result = result + (int)__builtin_va_arg(args, double) * 1000;
+    __builtin_va_end(args);
+    return result;
+}
+
+void show(char *label, va_list args) {
+    int first = __builtin_va_arg(args, int);
+    int second = __builtin_va_arg(args, int);
+    printf("%s: %d %d\n", label, first, second);
+}
+
+void forward(char *label, ...) {
+    va_list args;
+    __builtin_va_start(args, label);
+    show(label, args);
+    __builtin_va_end(args);
+}
+
+int main(void) {
+    printf("%d %d %d\n", sum(0), sum(3, 1, 2, 3), sum(10, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10));
+    printf("%.3f %.3f\n", average(2, 1.0, 2.0),
+           average(10, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0));
+    float f = 2.5f;
+    char c = 3;
+    printf("%ld\n", mixed("ilds", c, 100000000000L, f, "A"));
+    printf("%ld\n", mixed("iiiiiiiidd", 1, 2, 3, 4, 5, 6, 7, 8, 0.1, 0.2));
+    printf("%d\n", late(1, 2, 3, 4, 5, 6, 7, 8.0, 9, 10.0));
+    forward("pair", 11, 22);
+    return sum(2, 20, 22);
+}
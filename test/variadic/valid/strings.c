@@ -0,0 +1,52 @@
+int putchar(int c);
+int printf(char *format, ...);
+
+int puts_chars(char *s) {
+    int count = 0;
+    while (*s) {
+        putchar(*s);
+        s = s + 1;
+        count = count + 1;
+    }
+    putchar('\n');
+    return count;
+}
+
+char greeting[] = "hello";
+char padded[8] = "abc";
+char *names[] = {"zero", "one", "two"};
+static char *message = "static " "pointer";
+char grid[2][4] = {"ab", {"cde"}};
+
+struct named {
+    char tag[4];
+    int value;
+};
+
+struct named item = {"id", 7};
+
+int main(void) {
+    puts_chars("plain");
+    puts_chars("tab\there, quote\" and backslash\\");
+    puts_chars("adjacent " "literals " "joined");
+    puts_chars(greeting);
+    puts_chars(names[2]);
+    puts_chars(message);
+    puts_chars(grid[1]);
+    puts_chars(item.tag);
+
+    char local[] = "local";
+    char exact[3] = "abc";
+    char braced[] = {"xy"};
+    printf("%d %d %d %d\n", (int)sizeof(local), (int)sizeof(braced), exact[2], padded[5]);
+    printf("%d %d\n", (int)sizeof("four"), (int)sizeof(greeting));
+
+    printf("%d %d %d %d %d\n", 'a', '\n', '\0', '\x41', '\101');
+    printf("%d %d %d\n", '\xff', '\'', '"');
+    printf("%d %d\n", "\x7f\1"[0], "\x7f\1"[1]);
+
+    char *p = "pointer";
+    p = p + 3;
+    puts_chars(p);
+    return "abc"[1];
+}
@@ -0,0 +1,23 @@
+enum { LARGEST = 2147483647, WRAPPED = LARGEST + 1 };
+
+long table[2 * 3 - 5 + (0 && 1 / 0)];
+unsigned int all_ones = 0u - 1;
+int smallest = -2147483647 - 1;
+long big = 2147483647L + 1;
+int modulo = (-2147483647 - 1) % -1;
+unsigned long all_bits = 18446744073709551615UL * 18446744073709551615UL;
+long doubled = 9223372036854775807L * 2 - (-9223372036854775807L - 1);
+
+int main(void) {
+    int total = 7;
+    int zero = 0;
+    int product = 65536 * 65536;
+    if (zero)
+        total = total / 0;
+    switch (total) {
+    case 3 * 4 - 5:
+        total = total + 1;
+        break;
+    }
+    return (WRAPPED < 0) + (all_ones == 4294967295u) + (smallest < 0) + (big > 0) + product + total;
+}
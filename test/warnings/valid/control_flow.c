@@ -0,0 +1,50 @@
+int sign(int x) {
+    if (x < 0)
+        return -1;
+    else if (x > 0)
+        return 1;
+}
+
+int forever(int x) {
+    while (1) {
+        if (x > 10)
+            return x;
+        x = x + 1;
+    }
+}
+
+int pick(int x) {
+    switch (x) {
+    case 1:
+        return 10;
+    default:
+        return 20;
+    }
+}
+
+int after_return(int x) {
+    return x;
+    x = x + 1;
+    x = x + 2;
+}
+
+int after_goto(int x) {
+    goto end;
+    x = 3;
+end:
+    return x;
+}
+
+int main(void) {
+    int total = 0;
+    switch (total) {
+        total = 5;
+    case 0:
+        total = total + 1;
+        break;
+    }
+    for (;;) {
+        break;
+    }
+    return sign(3) + forever(1) + pick(2) + after_return(1) + after_goto(2) + total;
+}
@@ -0,0 +1,24 @@
+int count = 2;
+
+int main(void) {
+    unsigned int u = 3;
+    int i = -1;
+    char c = i;
+    char small = 65;
+    long l = 70000;
+    short s = l;
+    double d = 2.5;
+    int truncated = d;
+    int exact = 4.0;
+    float f = d;
+    float g = 0.5;
+    int count = 1;
+    int result = 0;
+    if (i < u)
+        result = result + 1;
+    if (u > 0)
+        result = result + 2;
+    if (c == small)
+        result = result + 4;
+    return result + c + s + truncated + exact + (int) f + (int) g + count;
+}
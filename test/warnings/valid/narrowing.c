@@ -0,0 +1,29 @@
+int truncated(void) {
+    return 99999999999;
+}
+
+unsigned char wrapped(void) {
+    return 300;
+}
+
+long widened(void) {
+    return 99999999999;
+}
+
+int main(void) {
+    unsigned int all_ones = -1;
+    short s = 40000;
+    int i = 4294967295u;
+    char c = 'a';
+    long fits = 2147483647;
+    int exact = (int) 99999999999;
+    return (truncated() == 1215752191) + wrapped() + (widened() > fits) + (all_ones > 0) + s + i + c + exact;
+}
+
+unsigned char file_scope = 257;
+int from_double = 3.5;
+
+int counter(void) {
+    static char kept = 1000;
+    return kept + file_scope + from_double;
+}
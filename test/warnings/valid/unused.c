@@ -0,0 +1,18 @@
+static int helper(int x) {
+    return x + 1;
+}
+
+static int unused_helper(int x) {
+    return unused_helper(x - 1);
+}
+
+static int by_pointer(int x) {
+    return x * 2;
+}
+
+int main(void) {
+    int unused;
+    int used = 4;
+    int (*f)(int) = by_pointer;
+    return helper(used) + f(1);
+}